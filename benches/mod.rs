@@ -29,8 +29,11 @@
 //! - **strings** - 字符串处理性能
 //! - **concurrency** - 并发操作性能
 //! - **projects** - 实战项目性能
+//! - **fuzzy_search** - 编辑距离与模糊搜索的开销
+//! - **patterns** - 设计模式不同实现方式的开销对比
 
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
+use rust_learn::projects::task_manager::{InMemoryStore, TaskManager};
 use std::time::Duration;
 
 // 配置基准测试
@@ -46,7 +49,9 @@ criterion_group!(
         collection_benchmarks,
         string_benchmarks,
         concurrency_benchmarks,
-        project_benchmarks
+        project_benchmarks,
+        fuzzy_search_benchmarks,
+        pattern_benchmarks
 );
 
 criterion_main!(benches);
@@ -485,29 +490,37 @@ fn concurrency_benchmarks(c: &mut Criterion) {
 
 // ==================== 实战项目基准测试 ====================
 
+/// 生成 `n` 个任务的内存任务管理器，标题与优先级由固定种子决定
+///
+/// 不落盘、每次运行结果一致，避免文件系统噪声混入任务管理器的基准数据。
+fn make_manager(n: usize) -> TaskManager<InMemoryStore> {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use rust_learn::basics::test_util::gen_titles;
+    use rust_learn::projects::task_manager::{Priority, Task};
+    
+    const PRIORITIES: [Priority; 4] = [Priority::Low, Priority::Medium, Priority::High, Priority::Urgent];
+    
+    let mut rng = StdRng::seed_from_u64(42);
+    gen_titles(n, 42)
+        .into_iter()
+        .map(|title| {
+            let priority = PRIORITIES[rng.random_range(0..PRIORITIES.len())];
+            Task::new(0, title, priority)
+        })
+        .collect()
+}
+
 fn project_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("projects");
     
     // 任务管理器性能测试
     group.bench_function("task_manager_add", |b| {
-        use rust_learn::projects::task_manager::{Task, TaskManager, Priority};
-        b.iter(|| {
-            let mut manager = TaskManager::new().unwrap();
-            for i in 0..100 {
-                let task = Task::new(0, format!("Task {}", i), Priority::Medium);
-                manager.add_task(task);
-            }
-            manager
-        });
+        b.iter(|| make_manager(100));
     });
     
     group.bench_function("task_manager_list", |b| {
-        use rust_learn::projects::task_manager::{Task, TaskManager, Priority};
-        let mut manager = TaskManager::new().unwrap();
-        for i in 0..100 {
-            let task = Task::new(0, format!("Task {}", i), Priority::Medium);
-            manager.add_task(task);
-        }
+        let manager = make_manager(100);
         
         b.iter(|| {
             manager.list_tasks(None)
@@ -515,18 +528,45 @@ fn project_benchmarks(c: &mut Criterion) {
     });
     
     group.bench_function("task_manager_search", |b| {
-        use rust_learn::projects::task_manager::{Task, TaskManager, Priority};
-        let mut manager = TaskManager::new().unwrap();
-        for i in 0..100 {
-            let task = Task::new(0, format!("Task {}", i), Priority::Medium);
-            manager.add_task(task);
-        }
+        let manager = make_manager(100);
         
         b.iter(|| {
-            manager.search_tasks("Task 50")
+            manager.search_tasks("report 50")
         });
     });
     
+    // 加载 10k 任务文件：对比不预分配与按任务数预分配 HashMap 容量
+    {
+        use rust_learn::basics::test_util::gen_titles;
+        use rust_learn::projects::task_manager::{Task, Priority};
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage_path = temp_dir.path().join("tasks_10k.json");
+        {
+            let mut tasks = std::collections::HashMap::new();
+            for (id, title) in (1..=10_000u64).zip(gen_titles(10_000, 7)) {
+                tasks.insert(id, Task::new(id, title, Priority::Medium));
+            }
+            std::fs::write(&storage_path, serde_json::to_vec(&tasks).unwrap()).unwrap();
+        }
+        
+        group.bench_function("task_manager_load_10k_no_hint", |b| {
+            b.iter(|| TaskManager::with_capacity(&storage_path, 0).unwrap())
+        });
+        
+        group.bench_function("task_manager_load_10k_with_hint", |b| {
+            b.iter(|| TaskManager::with_capacity(&storage_path, 10_000).unwrap())
+        });
+    }
+    
+    // 100k 任务的统计：单次遍历累计全部计数
+    {
+        let manager = make_manager(100_000);
+        
+        group.bench_function("task_manager_statistics_100k", |b| {
+            b.iter(|| manager.get_statistics())
+        });
+    }
+    
     // JSON 序列化/反序列化性能
     group.bench_function("json_serialize", |b| {
         use serde::{Serialize, Deserialize};
@@ -573,3 +613,72 @@ fn project_benchmarks(c: &mut Criterion) {
     
     group.finish();
 }
+
+// ==================== 模糊搜索基准测试 ====================
+
+fn fuzzy_search_benchmarks(c: &mut Criterion) {
+    use rust_learn::basics::levenshtein;
+    use std::hint::black_box;
+    
+    let mut group = c.benchmark_group("fuzzy_search");
+    
+    // 编辑距离本身：动态规划开销随两个字符串长度之积增长
+    let short_pair = ("kitten", "sitting");
+    let long_a = "the quick brown fox jumps over the lazy dog ".repeat(8);
+    let long_b = "the quack brown fix jumped over a lazy dog ".repeat(8);
+    
+    group.bench_function("levenshtein_short", |b| {
+        b.iter(|| levenshtein(black_box(short_pair.0), black_box(short_pair.1)))
+    });
+    
+    group.bench_function("levenshtein_long", |b| {
+        b.iter(|| levenshtein(black_box(&long_a), black_box(&long_b)))
+    });
+    
+    // 10k 任务上的模糊搜索：每个任务都要算一次距离，max_distance 只影响结果数量与排序量
+    let manager = make_manager(10_000);
+    for max_distance in [0, 2, 5, 10].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("task_manager_10k", max_distance),
+            max_distance,
+            |b, &max_distance| {
+                b.iter(|| manager.fuzzy_search(black_box("Review report 5000"), max_distance))
+            },
+        );
+    }
+    
+    group.finish();
+}
+
+// ==================== 设计模式基准测试 ====================
+
+fn pattern_benchmarks(c: &mut Criterion) {
+    use rust_learn::advanced_patterns::{Order, OrderStatus};
+    use std::hint::black_box;
+    
+    let mut group = c.benchmark_group("patterns");
+    
+    // 状态模式：特征对象（每次转换返回新的 Box<dyn OrderState>）对比枚举状态机
+    // 每次迭代从待处理开始推进 1000 次，绝大多数转换发生在终态上，主要比较转换本身的开销
+    group.bench_function("order_state_trait_object", |b| {
+        b.iter(|| {
+            let mut order = Order::new("ORD-BENCH");
+            for _ in 0..1000 {
+                order.advance();
+            }
+            order
+        })
+    });
+    
+    group.bench_function("order_status_enum", |b| {
+        b.iter(|| {
+            let mut status = black_box(OrderStatus::Pending);
+            for _ in 0..1000 {
+                status = black_box(status.advance());
+            }
+            status
+        })
+    });
+    
+    group.finish();
+}
@@ -5,24 +5,143 @@
 
 #![allow(dead_code)]
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::rc::{Rc, Weak};
 
 // ============== Builder 模式 ==============
 
 /// 数据库连接配置
 ///
 /// 这个结构体表示数据库连接的配置信息，包括主机、端口、数据库名等。
-#[derive(Clone)]
+/// 序列化时会跳过 `password`，避免把凭据写入配置文件；
+/// 反序列化同样经过 [`DatabaseConfigBuilder::build`] 校验，非法配置无法从文件读入。
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "DatabaseConfigRecord")]
 pub struct DatabaseConfig {
     host: String,
     port: u16,
     database: String,
     username: String,
+    #[serde(skip_serializing)]
     password: String,
     pool_size: usize,
     timeout_secs: u64,
 }
 
+/// 配置文件中的原始字段，缺省的 `password` 读作空串
+#[derive(Deserialize)]
+struct DatabaseConfigRecord {
+    host: String,
+    port: u16,
+    database: String,
+    username: String,
+    #[serde(default)]
+    password: String,
+    pool_size: usize,
+    timeout_secs: u64,
+}
+
+impl TryFrom<DatabaseConfigRecord> for DatabaseConfig {
+    type Error = BuilderError;
+
+    fn try_from(record: DatabaseConfigRecord) -> Result<Self, Self::Error> {
+        DatabaseConfigBuilder::new()
+            .host(&record.host)
+            .port(record.port)
+            .database(&record.database)
+            .username(&record.username)
+            .password(&record.password)
+            .pool_size(record.pool_size)
+            .timeout_secs(record.timeout_secs)
+            .build()
+    }
+}
+
+impl DatabaseConfig {
+    /// 从 `DB_HOST`、`DB_PORT`、`DB_NAME`、`DB_USER`、`DB_PASSWORD`、
+    /// `DB_POOL_SIZE`、`DB_TIMEOUT_SECS` 环境变量读取配置，未设置的项使用Builder默认值
+    pub fn from_env() -> Result<DatabaseConfig, BuilderError> {
+        Self::from_env_with(|key| std::env::var(key).ok())
+    }
+
+    /// 与 [`DatabaseConfig::from_env`] 相同，但通过 `lookup` 查找变量，便于注入测试数据
+    pub fn from_env_with(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<DatabaseConfig, BuilderError> {
+        fn parse<T: std::str::FromStr>(name: &str, value: String) -> Result<T, BuilderError> {
+            value.parse().map_err(|_| BuilderError::InvalidEnvVar {
+                name: name.to_string(),
+                value,
+            })
+        }
+
+        let mut builder = DatabaseConfigBuilder::new();
+        if let Some(host) = lookup("DB_HOST") {
+            builder = builder.host(&host);
+        }
+        if let Some(port) = lookup("DB_PORT") {
+            builder = builder.port(parse("DB_PORT", port)?);
+        }
+        if let Some(database) = lookup("DB_NAME") {
+            builder = builder.database(&database);
+        }
+        if let Some(username) = lookup("DB_USER") {
+            builder = builder.username(&username);
+        }
+        if let Some(password) = lookup("DB_PASSWORD") {
+            builder = builder.password(&password);
+        }
+        if let Some(pool_size) = lookup("DB_POOL_SIZE") {
+            builder = builder.pool_size(parse("DB_POOL_SIZE", pool_size)?);
+        }
+        if let Some(timeout_secs) = lookup("DB_TIMEOUT_SECS") {
+            builder = builder.timeout_secs(parse("DB_TIMEOUT_SECS", timeout_secs)?);
+        }
+
+        builder.build()
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    pub fn pool_size(&self) -> usize {
+        self.pool_size
+    }
+
+    pub fn timeout_secs(&self) -> u64 {
+        self.timeout_secs
+    }
+}
+
+/// 构建DatabaseConfig时的错误
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BuilderError {
+    #[error("Host cannot be empty")]
+    EmptyHost,
+    #[error("Port must be greater than 0")]
+    InvalidPort,
+    #[error("Invalid value for {name}: {value}")]
+    InvalidEnvVar { name: String, value: String },
+}
+
 /// Builder用于构建DatabaseConfig
 pub struct DatabaseConfigBuilder {
     host: String,
@@ -88,12 +207,12 @@ impl DatabaseConfigBuilder {
         self
     }
 
-    pub fn build(self) -> Result<DatabaseConfig, String> {
+    pub fn build(self) -> Result<DatabaseConfig, BuilderError> {
         if self.host.is_empty() {
-            return Err("Host cannot be empty".to_string());
+            return Err(BuilderError::EmptyHost);
         }
         if self.port == 0 {
-            return Err("Port must be greater than 0".to_string());
+            return Err(BuilderError::InvalidPort);
         }
 
         Ok(DatabaseConfig {
@@ -124,9 +243,24 @@ impl fmt::Debug for DatabaseConfig {
 
 // ============== Strategy 模式 ==============
 
+/// 支付错误
+///
+/// 实现了 `std::error::Error`，可以直接配合 `?`、`Box<dyn Error>` 与 `anyhow` 使用。
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PaymentError {
+    #[error("Invalid credit card")]
+    InvalidCard,
+    #[error("Invalid PayPal account")]
+    InvalidAccount,
+    #[error("Invalid crypto wallet")]
+    InvalidWallet,
+    #[error("Payment declined: {reason}")]
+    Declined { reason: String },
+}
+
 /// 支付策略特征
 pub trait PaymentStrategy: fmt::Debug {
-    fn pay(&self, amount: f64) -> Result<String, String>;
+    fn pay(&self, amount: f64) -> Result<String, PaymentError>;
     fn validate(&self) -> bool;
 }
 
@@ -147,9 +281,9 @@ impl CreditCardPayment {
 }
 
 impl PaymentStrategy for CreditCardPayment {
-    fn pay(&self, amount: f64) -> Result<String, String> {
+    fn pay(&self, amount: f64) -> Result<String, PaymentError> {
         if !self.validate() {
-            return Err("Invalid credit card".to_string());
+            return Err(PaymentError::InvalidCard);
         }
         Ok(format!("💳 Credit card payment of ${:.2} processed", amount))
     }
@@ -174,9 +308,9 @@ impl PayPalPayment {
 }
 
 impl PaymentStrategy for PayPalPayment {
-    fn pay(&self, amount: f64) -> Result<String, String> {
+    fn pay(&self, amount: f64) -> Result<String, PaymentError> {
         if !self.validate() {
-            return Err("Invalid PayPal account".to_string());
+            return Err(PaymentError::InvalidAccount);
         }
         Ok(format!("🅿️ PayPal payment of ${:.2} to {} processed", amount, self.email))
     }
@@ -203,9 +337,9 @@ impl CryptoPayment {
 }
 
 impl PaymentStrategy for CryptoPayment {
-    fn pay(&self, amount: f64) -> Result<String, String> {
+    fn pay(&self, amount: f64) -> Result<String, PaymentError> {
         if !self.validate() {
-            return Err("Invalid crypto wallet".to_string());
+            return Err(PaymentError::InvalidWallet);
         }
         Ok(format!("🪙 {} payment of {:.2} {} processed", self.currency, amount, self.currency))
     }
@@ -215,11 +349,30 @@ impl PaymentStrategy for CryptoPayment {
     }
 }
 
+/// 使用每个策略支付相同金额，汇总成功扣款总额
+///
+/// 返回成功扣款的总金额，以及所有失败策略产生的错误。
+pub fn charge_all(strategies: &[Box<dyn PaymentStrategy>], amount: f64) -> (f64, Vec<PaymentError>) {
+    let mut total = 0.0;
+    let mut errors = Vec::new();
+
+    for strategy in strategies {
+        match strategy.pay(amount) {
+            Ok(_) => total += amount,
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (total, errors)
+}
+
 // ============== Observer 模式 ==============
 
 /// 观察者特征
+///
+/// 通知可能失败（例如邮件网关不可用），失败原因以字符串返回给发布者。
 pub trait Observer: fmt::Debug {
-    fn update(&self, message: &str);
+    fn update(&self, message: &str) -> Result<(), String>;
 }
 
 /// Email订阅者
@@ -237,8 +390,9 @@ impl EmailSubscriber {
 }
 
 impl Observer for EmailSubscriber {
-    fn update(&self, message: &str) {
+    fn update(&self, message: &str) -> Result<(), String> {
         println!("📧 Email to {}: {}", self.email, message);
+        Ok(())
     }
 }
 
@@ -257,33 +411,96 @@ impl SmsSubscriber {
 }
 
 impl Observer for SmsSubscriber {
-    fn update(&self, message: &str) {
+    fn update(&self, message: &str) -> Result<(), String> {
         println!("📱 SMS to {}: {}", self.phone, message);
+        Ok(())
     }
 }
 
+/// 订阅标识，由 [`EventPublisher::subscribe`] 分配
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
 /// 事件发布者
 #[derive(Debug)]
 pub struct EventPublisher {
-    observers: Vec<Box<dyn Observer>>,
+    observers: Vec<(SubscriptionId, Box<dyn Observer>)>,
+    next_id: u64,
 }
 
 impl EventPublisher {
     pub fn new() -> Self {
         Self {
             observers: Vec::new(),
+            next_id: 0,
         }
     }
 
-    pub fn subscribe(&mut self, observer: Box<dyn Observer>) {
-        self.observers.push(observer);
+    pub fn subscribe(&mut self, observer: Box<dyn Observer>) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+        self.observers.push((id, observer));
+        id
     }
 
+    /// 通知所有订阅者；个别订阅者的失败只以 `tracing::warn!` 记录，不会返回给调用方
+    ///
+    /// 需要自行处理失败时改用 [`EventPublisher::notify_collecting`]。
     pub fn notify(&self, message: &str) {
-        for observer in &self.observers {
-            observer.update(message);
+        for (id, error) in self.notify_collecting(message) {
+            tracing::warn!(subscription = ?id, %error, "订阅者处理通知失败");
         }
     }
+
+    /// 通知所有订阅者，并返回失败的订阅及其错误信息
+    ///
+    /// 某个订阅者失败不会中断对其余订阅者的通知。
+    pub fn notify_collecting(&self, message: &str) -> Vec<(SubscriptionId, String)> {
+        self.observers
+            .iter()
+            .filter_map(|(id, observer)| observer.update(message).err().map(|e| (*id, e)))
+            .collect()
+    }
+}
+
+/// 持有弱引用的事件发布者
+///
+/// 订阅者由调用方以 `Rc` 持有；订阅者被释放后会在下一次 `notify` 时自动脱离。
+#[derive(Debug, Default)]
+pub struct WeakEventPublisher {
+    observers: Vec<Weak<dyn Observer>>,
+}
+
+impl WeakEventPublisher {
+    pub fn new() -> Self {
+        Self {
+            observers: Vec::new(),
+        }
+    }
+
+    pub fn subscribe(&mut self, observer: &Rc<dyn Observer>) {
+        self.observers.push(Rc::downgrade(observer));
+    }
+
+    /// 通知所有仍然存活的订阅者，并清理已失效的弱引用
+    ///
+    /// 订阅者返回的错误只以 `tracing::warn!` 记录，不会中断通知，也不会返回给调用方。
+    pub fn notify(&mut self, message: &str) {
+        self.observers.retain(|weak| match weak.upgrade() {
+            Some(observer) => {
+                if let Err(error) = observer.update(message) {
+                    tracing::warn!(%error, "订阅者处理通知失败");
+                }
+                true
+            }
+            None => false,
+        });
+    }
+
+    /// 当前记录的订阅者数量（包括尚未清理的失效引用）
+    pub fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
 }
 
 // ============== State 模式 ==============
@@ -292,6 +509,8 @@ impl EventPublisher {
 pub trait OrderState: fmt::Debug {
     fn next_state(&self) -> Box<dyn OrderState>;
     fn get_status(&self) -> String;
+    /// 对应的枚举状态，用于持久化
+    fn status(&self) -> OrderStatus;
 }
 
 #[derive(Debug)]
@@ -305,6 +524,10 @@ impl OrderState for PendingState {
     fn get_status(&self) -> String {
         "⏳ Pending".to_string()
     }
+
+    fn status(&self) -> OrderStatus {
+        OrderStatus::Pending
+    }
 }
 
 #[derive(Debug)]
@@ -318,6 +541,10 @@ impl OrderState for ConfirmedState {
     fn get_status(&self) -> String {
         "✅ Confirmed".to_string()
     }
+
+    fn status(&self) -> OrderStatus {
+        OrderStatus::Confirmed
+    }
 }
 
 #[derive(Debug)]
@@ -331,6 +558,10 @@ impl OrderState for ShippingState {
     fn get_status(&self) -> String {
         "🚚 Shipping".to_string()
     }
+
+    fn status(&self) -> OrderStatus {
+        OrderStatus::Shipping
+    }
 }
 
 #[derive(Debug)]
@@ -344,9 +575,33 @@ impl OrderState for DeliveredState {
     fn get_status(&self) -> String {
         "📦 Delivered".to_string()
     }
+
+    fn status(&self) -> OrderStatus {
+        OrderStatus::Delivered
+    }
+}
+
+#[derive(Debug)]
+pub struct CancelledState;
+
+impl OrderState for CancelledState {
+    fn next_state(&self) -> Box<dyn OrderState> {
+        Box::new(CancelledState)
+    }
+
+    fn get_status(&self) -> String {
+        "❌ Cancelled".to_string()
+    }
+
+    fn status(&self) -> OrderStatus {
+        OrderStatus::Cancelled
+    }
 }
 
 /// 订单
+///
+/// 状态以特征对象保存，无法直接派生 serde；序列化时通过 [`OrderStatus`] 转换为
+/// `{ "id": ..., "status": ... }`，反序列化时再还原为对应的状态对象。
 pub struct Order {
     id: String,
     state: Box<dyn OrderState>,
@@ -367,6 +622,114 @@ impl Order {
     pub fn advance(&mut self) {
         self.state = self.state.next_state();
     }
+
+    /// 订单编号
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 当前状态对应的枚举值
+    pub fn status(&self) -> OrderStatus {
+        self.state.status()
+    }
+
+    /// 序列化为 JSON，便于在生命周期中途保存订单
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// 从 [`Order::to_json`] 的输出恢复订单
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+/// 订单的可序列化形式
+#[derive(Serialize, Deserialize)]
+struct OrderRecord {
+    id: String,
+    status: OrderStatus,
+}
+
+impl Serialize for Order {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        OrderRecord {
+            id: self.id.clone(),
+            status: self.status(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Order {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = OrderRecord::deserialize(deserializer)?;
+        Ok(Self {
+            id: record.id,
+            state: record.status.into_state(),
+        })
+    }
+}
+
+/// 基于枚举的订单状态机
+///
+/// 与 [`OrderState`] 特征对象方案等价，但状态是 `Copy` 的枚举值：
+/// 转换只是返回另一个变体，不需要构造 `Box<dyn OrderState>` 或经过动态分发，
+/// 并且所有状态都在一个 `match` 中列出，新增状态时编译器会提示遗漏的分支。
+/// 序列化为 snake_case 字符串（如 `"shipping"`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Pending,
+    Confirmed,
+    Shipping,
+    Delivered,
+    Cancelled,
+}
+
+impl OrderStatus {
+    /// 推进到下一个状态；`Delivered` 与 `Cancelled` 是终态，保持不变
+    pub fn advance(self) -> OrderStatus {
+        match self {
+            OrderStatus::Pending => OrderStatus::Confirmed,
+            OrderStatus::Confirmed => OrderStatus::Shipping,
+            OrderStatus::Shipping => OrderStatus::Delivered,
+            OrderStatus::Delivered => OrderStatus::Delivered,
+            OrderStatus::Cancelled => OrderStatus::Cancelled,
+        }
+    }
+
+    /// 发货前（待处理或已确认）才允许取消
+    pub fn can_cancel(&self) -> bool {
+        matches!(self, OrderStatus::Pending | OrderStatus::Confirmed)
+    }
+
+    /// 允许取消时返回 `Cancelled`，否则返回 `None`
+    pub fn cancel(self) -> Option<OrderStatus> {
+        self.can_cancel().then_some(OrderStatus::Cancelled)
+    }
+
+    /// 转换为特征对象方案中对应的状态
+    pub fn into_state(self) -> Box<dyn OrderState> {
+        match self {
+            OrderStatus::Pending => Box::new(PendingState),
+            OrderStatus::Confirmed => Box::new(ConfirmedState),
+            OrderStatus::Shipping => Box::new(ShippingState),
+            OrderStatus::Delivered => Box::new(DeliveredState),
+            OrderStatus::Cancelled => Box::new(CancelledState),
+        }
+    }
+
+    /// 与 [`OrderState::get_status`] 相同风格的状态文案
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "⏳ Pending",
+            OrderStatus::Confirmed => "✅ Confirmed",
+            OrderStatus::Shipping => "🚚 Shipping",
+            OrderStatus::Delivered => "📦 Delivered",
+            OrderStatus::Cancelled => "❌ Cancelled",
+        }
+    }
 }
 
 // ============== Factory 模式 ==============
@@ -547,6 +910,11 @@ fn demo_builder() {
         }
         Err(e) => println!("❌ Error: {}", e),
     }
+
+    match DatabaseConfig::from_env() {
+        Ok(cfg) => println!("✅ Database config from env: {}:{}", cfg.host(), cfg.port()),
+        Err(e) => println!("❌ Error loading config from env: {}", e),
+    }
 }
 
 /// 演示Strategy模式
@@ -559,12 +927,15 @@ fn demo_strategy() {
     ];
 
     let amount = 99.99;
-    for strategy in strategies {
+    for strategy in &strategies {
         match strategy.pay(amount) {
             Ok(msg) => println!("{}", msg),
             Err(e) => println!("❌ {}", e),
         }
     }
+
+    let (total, errors) = charge_all(&strategies, amount);
+    println!("💰 成功扣款总额: ${:.2}，失败 {} 笔", total, errors.len());
 }
 
 /// 演示Observer模式
@@ -576,7 +947,21 @@ fn demo_observer() {
     publisher.subscribe(Box::new(SmsSubscriber::new("+1-555-0123")));
     
     println!("📢 Publishing event...");
-    publisher.notify("Important announcement: System maintenance scheduled");
+    let failures =
+        publisher.notify_collecting("Important announcement: System maintenance scheduled");
+    println!("通知失败的订阅: {}", failures.len());
+
+    println!("🔗 使用弱引用发布者，订阅者释放后自动脱离...");
+    let mut weak_publisher = WeakEventPublisher::new();
+    let email: Rc<dyn Observer> = Rc::new(EmailSubscriber::new("ops@example.com"));
+    let sms: Rc<dyn Observer> = Rc::new(SmsSubscriber::new("+1-555-0199"));
+    weak_publisher.subscribe(&email);
+    weak_publisher.subscribe(&sms);
+    weak_publisher.notify("Deployment started");
+
+    drop(sms);
+    weak_publisher.notify("Deployment finished");
+    println!("剩余订阅者: {}", weak_publisher.observer_count());
 }
 
 /// 演示State模式
@@ -594,6 +979,32 @@ fn demo_state() {
     
     order.advance();
     println!("订单 {}: {}", order.id, order.get_status());
+    
+    // 枚举状态机：同样的流转，不需要特征对象
+    let mut status = OrderStatus::Pending;
+    let mut trail = vec![status.label()];
+    while status != OrderStatus::Delivered {
+        status = status.advance();
+        trail.push(status.label());
+    }
+    println!("枚举状态机: {}", trail.join(" → "));
+    println!("已发货订单能否取消: {}", OrderStatus::Shipping.can_cancel());
+    println!("待处理订单取消后: {:?}", OrderStatus::Pending.cancel());
+    
+    // 运输途中保存订单，之后恢复并继续推进
+    let mut in_transit = Order::new("ORD-002");
+    in_transit.advance();
+    in_transit.advance();
+    match in_transit.to_json() {
+        Ok(json) => {
+            println!("保存订单: {}", json);
+            if let Ok(mut restored) = Order::from_json(&json) {
+                restored.advance();
+                println!("恢复后推进: {}", restored.get_status());
+            }
+        }
+        Err(e) => println!("保存订单失败: {}", e),
+    }
 }
 
 /// 演示Factory模式
@@ -3,7 +3,10 @@
 //! 这个模块演示了Rust的高级类型系统和生命周期概念。
 //! 采用了现代化的Rust 2021/2024最佳实践。
 
+use std::cell::RefCell;
 use std::fmt::Display;
+use std::ops::{Add, Mul};
+use std::rc::{Rc, Weak};
 
 /// 现代化关联类型示例
 pub trait ModernIterator {
@@ -27,6 +30,16 @@ impl ModernCounter {
     pub fn new(max: usize) -> Self {
         Self { current: 0, max }
     }
+    
+    /// 尚未产出的元素个数，与 `size_hint` 的上下界一致
+    pub fn remaining(&self) -> usize {
+        self.max.saturating_sub(self.current)
+    }
+    
+    /// 进度 `(已产出个数, 总个数)`，便于构建进度条等展示
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current.min(self.max), self.max)
+    }
 }
 
 impl ModernIterator for ModernCounter {
@@ -43,7 +56,7 @@ impl ModernIterator for ModernCounter {
     }
     
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.max.saturating_sub(self.current);
+        let remaining = self.remaining();
         (remaining, Some(remaining))
     }
 }
@@ -55,7 +68,8 @@ pub fn modern_associated_types() {
     let mut counter = ModernCounter::new(5);
     println!("初始大小提示（剩余元素数）: {:?}", counter.size_hint());
     while let Some(value) = counter.next() {
-        println!("计数器: {}", value);
+        let (consumed, max) = counter.progress();
+        println!("计数器: {} (进度 {}/{}, 剩余 {})", value, consumed, max, counter.remaining());
     }
     
     let hint = counter.size_hint();
@@ -178,102 +192,137 @@ pub fn modern_lifetimes() {
     println!("静态消息: {}", GLOBAL_MESSAGE);
 }
 
-/// 现代化高级特征系统
-pub fn modern_trait_system() {
-    println!("🎨 现代化特征系统：");
+/// 现代化特征定义：带关联类型、可作为特征对象使用的可绘制组件
+pub trait ModernDraw {
+    type Color;
+    type Style;
+    
+    /// 组件名称，用于在 [`ModernScreen`] 中查找与移除
+    fn name(&self) -> &str;
+    fn draw(&self) -> String;
+    fn get_color(&self) -> Self::Color;
+    fn get_style(&self) -> Self::Style;
+}
+
+/// 屏幕中保存的组件特征对象
+pub type ScreenComponent = Box<dyn ModernDraw<Color = String, Style = String>>;
+
+/// 按钮组件
+#[derive(Debug)]
+pub struct ModernButton {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub label: String,
+    pub color: String,
+    pub style: String,
+}
+
+impl ModernDraw for ModernButton {
+    type Color = String;
+    type Style = String;
     
-    // 现代化特征定义
-    pub trait ModernDraw {
-        type Color;
-        type Style;
-        
-        fn draw(&self) -> String;
-        #[allow(dead_code)]
-        fn get_color(&self) -> Self::Color;
-        #[allow(dead_code)]
-        fn get_style(&self) -> Self::Style;
+    fn name(&self) -> &str {
+        &self.name
     }
     
-    // 现代化特征实现
-    #[derive(Debug)]
-    pub struct ModernButton {
-        pub width: u32,
-        pub height: u32,
-        pub label: String,
-        pub color: String,
-        pub style: String,
+    fn draw(&self) -> String {
+        format!("绘制按钮: {} ({}x{}, {}-{})",
+                self.label, self.width, self.height, self.color, self.style)
     }
     
-    impl ModernDraw for ModernButton {
-        type Color = String;
-        type Style = String;
-        
-        fn draw(&self) -> String {
-            format!("绘制按钮: {} ({}x{}, {}-{})",
-                    self.label, self.width, self.height, self.color, self.style)
-        }
-        
-        fn get_color(&self) -> Self::Color {
-            self.color.clone()
-        }
-        
-        fn get_style(&self) -> Self::Style {
-            self.style.clone()
-        }
+    fn get_color(&self) -> Self::Color {
+        self.color.clone()
     }
     
-    #[derive(Debug)]
-    pub struct ModernText {
-        pub text: String,
-        pub font_size: u32,
-        pub color: String,
-        pub style: String,
+    fn get_style(&self) -> Self::Style {
+        self.style.clone()
+    }
+}
+
+/// 文本组件
+#[derive(Debug)]
+pub struct ModernText {
+    pub name: String,
+    pub text: String,
+    pub font_size: u32,
+    pub color: String,
+    pub style: String,
+}
+
+impl ModernDraw for ModernText {
+    type Color = String;
+    type Style = String;
+    
+    fn name(&self) -> &str {
+        &self.name
     }
     
-    impl ModernDraw for ModernText {
-        type Color = String;
-        type Style = String;
-        
-        fn draw(&self) -> String {
-            format!("绘制文本: '{}' (大小:{}, {}-{})",
-                    self.text, self.font_size, self.color, self.style)
-        }
-        
-        fn get_color(&self) -> Self::Color {
-            self.color.clone()
-        }
-        
-        fn get_style(&self) -> Self::Style {
-            self.style.clone()
-        }
+    fn draw(&self) -> String {
+        format!("绘制文本: '{}' (大小:{}, {}-{})",
+                self.text, self.font_size, self.color, self.style)
     }
     
-    // 现代化特征对象
-    pub struct ModernScreen {
-        pub components: Vec<Box<dyn ModernDraw<Color = String, Style = String>>>,
+    fn get_color(&self) -> Self::Color {
+        self.color.clone()
     }
     
-    impl ModernScreen {
-        pub fn new() -> Self {
-            Self { components: Vec::new() }
-        }
-        
-        pub fn add_component(&mut self, component: Box<dyn ModernDraw<Color = String, Style = String>>) {
-            self.components.push(component);
-        }
-        
-        pub fn render(&self) {
-            println!("🎨 渲染屏幕组件:");
-            for (i, component) in self.components.iter().enumerate() {
-                println!("  {}: {}", i + 1, component.draw());
-            }
+    fn get_style(&self) -> Self::Style {
+        self.style.clone()
+    }
+}
+
+/// 现代化特征对象：按添加顺序保存异构组件，并支持按名称查找
+#[derive(Default)]
+pub struct ModernScreen {
+    pub components: Vec<ScreenComponent>,
+}
+
+impl ModernScreen {
+    pub fn new() -> Self {
+        Self { components: Vec::new() }
+    }
+    
+    pub fn add_component(&mut self, component: ScreenComponent) {
+        self.components.push(component);
+    }
+    
+    /// 查找第一个名为 `name` 的组件
+    pub fn find(&self, name: &str) -> Option<&dyn ModernDraw<Color = String, Style = String>> {
+        self.components
+            .iter()
+            .find(|component| component.name() == name)
+            .map(|component| component.as_ref())
+    }
+    
+    /// 移除并返回第一个名为 `name` 的组件，其余组件保持原有顺序
+    pub fn remove(&mut self, name: &str) -> Option<ScreenComponent> {
+        let index = self.components.iter().position(|component| component.name() == name)?;
+        Some(self.components.remove(index))
+    }
+    
+    /// 按添加顺序绘制所有组件
+    pub fn render_lines(&self) -> Vec<String> {
+        self.components.iter().map(|component| component.draw()).collect()
+    }
+    
+    pub fn render(&self) {
+        println!("🎨 渲染屏幕组件:");
+        for (i, line) in self.render_lines().iter().enumerate() {
+            println!("  {}: {}", i + 1, line);
         }
     }
+}
+
+/// 现代化高级特征系统
+pub fn modern_trait_system() {
+    println!("🎨 现代化特征系统：");
     
     // 使用现代化特征系统
     let mut screen = ModernScreen::new();
     
     screen.add_component(Box::new(ModernButton {
+        name: "ok_button".to_string(),
         width: 100,
         height: 30,
         label: "确定".to_string(),
@@ -282,6 +331,7 @@ pub fn modern_trait_system() {
     }));
     
     screen.add_component(Box::new(ModernText {
+        name: "welcome".to_string(),
         text: "欢迎使用现代化Rust程序".to_string(),
         font_size: 16,
         color: "黑色".to_string(),
@@ -289,6 +339,121 @@ pub fn modern_trait_system() {
     }));
     
     screen.render();
+    
+    // 按名称查找与移除组件
+    if let Some(button) = screen.find("ok_button") {
+        println!("🔍 找到组件 ok_button: 颜色 {}, 样式 {}", button.get_color(), button.get_style());
+    }
+    if let Some(removed) = screen.remove("welcome") {
+        println!("🗑️ 已移除组件: {}", removed.name());
+    }
+    println!("剩余组件数: {}", screen.components.len());
+}
+
+/// 尺寸在编译期确定的 `R` 行 `C` 列矩阵（元素为 `u64`）
+///
+/// 行列数是类型的一部分：只有内维一致的矩阵才能相乘，
+/// 维度不匹配会在编译期被拒绝，而不是运行时 panic。
+///
+/// ```
+/// use rust_learn::advanced_types::Matrix;
+///
+/// let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+/// let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+/// assert_eq!(a * b, Matrix::new([[58, 64], [139, 154]]));
+/// ```
+///
+/// 内维不一致时无法通过编译：
+///
+/// ```compile_fail
+/// use rust_learn::advanced_types::Matrix;
+///
+/// let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+/// let b = Matrix::new([[1, 2], [3, 4]]);
+/// let _ = a.matmul(&b);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    data: [[u64; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub fn new(data: [[u64; C]; R]) -> Self {
+        Self { data }
+    }
+    
+    /// 全零矩阵
+    pub fn zero() -> Self {
+        Self { data: [[0; C]; R] }
+    }
+    
+    /// 读取第 `row` 行第 `col` 列的元素，越界时 panic
+    pub fn get(&self, row: usize, col: usize) -> u64 {
+        self.data[row][col]
+    }
+    
+    /// 以二维数组形式借用全部元素
+    pub fn as_array(&self) -> &[[u64; C]; R] {
+        &self.data
+    }
+    
+    /// 矩阵乘法：`Matrix<R, K> × Matrix<K, C2> -> Matrix<R, C2>`
+    pub fn matmul<const C2: usize>(&self, rhs: &Matrix<C, C2>) -> Matrix<R, C2> {
+        let mut result = Matrix::<R, C2>::zero();
+        for i in 0..R {
+            for j in 0..C2 {
+                result.data[i][j] = (0..C).map(|k| self.data[i][k] * rhs.data[k][j]).sum();
+            }
+        }
+        result
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    /// `N` 阶单位矩阵，只有方阵才提供
+    pub fn identity() -> Self {
+        let mut result = Self::zero();
+        for i in 0..N {
+            result.data[i][i] = 1;
+        }
+        result
+    }
+}
+
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Self;
+    
+    /// 逐元素相加
+    fn add(mut self, rhs: Self) -> Self {
+        for (row, rhs_row) in self.data.iter_mut().zip(rhs.data.iter()) {
+            for (cell, rhs_cell) in row.iter_mut().zip(rhs_row.iter()) {
+                *cell += rhs_cell;
+            }
+        }
+        self
+    }
+}
+
+impl<const R: usize, const K: usize, const C: usize> Mul<Matrix<K, C>> for Matrix<R, K> {
+    type Output = Matrix<R, C>;
+    
+    fn mul(self, rhs: Matrix<K, C>) -> Matrix<R, C> {
+        self.matmul(&rhs)
+    }
+}
+
+/// 演示 const 泛型矩阵
+pub fn const_generic_matrix() {
+    println!("🧮 const 泛型矩阵：");
+    
+    let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+    let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+    let product = a * b;
+    println!("2x3 × 3x2 = {:?}", product.as_array());
+    
+    let sum = product + Matrix::<2, 2>::identity();
+    println!("再加上单位矩阵: {:?}", sum.as_array());
+    println!("维度不匹配的乘法（如 2x3 × 2x2）会在编译期报错");
 }
 
 /// 现代化泛型约束和特征对象
@@ -353,38 +518,69 @@ pub fn modern_generic_constraints() {
     }
 }
 
-/// 现代化NewType模式
-pub fn modern_newtype_pattern() {
-    println!("📦 现代化NewType模式：");
-    
-    // NewType用于类型安全
-    #[derive(Debug, Clone)]
-    pub struct UserId(u64);
+/// 用户ID NewType，可比较、可作为 HashMap 键
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserId(u64);
+
+/// 会话ID NewType，可比较、可作为 HashMap 键
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId(String);
+
+impl UserId {
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
     
-    #[derive(Debug, Clone)]
-    pub struct SessionId(String);
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for UserId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl SessionId {
+    pub fn new(session: String) -> Self {
+        Self(session)
+    }
     
-    impl UserId {
-        pub fn new(id: u64) -> Self {
-            Self(id)
-        }
-        
-        pub fn as_u64(&self) -> u64 {
-            self.0
-        }
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SessionId {
+    fn from(session: String) -> Self {
+        Self(session)
+    }
+}
+
+/// 取值范围由 const 泛型限定在 `MIN..=MAX` 内的 `u32`
+///
+/// 范围是类型的一部分，构造时检查一次，之后的持有者无需再校验，
+/// 例如百分比可以写成 `BoundedU32<0, 100>`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedU32<const MIN: u32, const MAX: u32>(u32);
+
+impl<const MIN: u32, const MAX: u32> BoundedU32<MIN, MAX> {
+    /// `value` 落在 `MIN..=MAX` 内时返回 `Some`
+    pub fn new(value: u32) -> Option<Self> {
+        (MIN..=MAX).contains(&value).then_some(Self(value))
     }
     
-    impl SessionId {
-        pub fn new(session: String) -> Self {
-            Self(session)
-        }
-        
-        pub fn as_str(&self) -> &str {
-            &self.0
-        }
+    pub fn get(&self) -> u32 {
+        self.0
     }
+}
+
+/// 现代化NewType模式
+pub fn modern_newtype_pattern() {
+    println!("📦 现代化NewType模式：");
     
-    // 类型安全的使用
+    // NewType用于类型安全
     let user_id = UserId::new(12345);
     let session_id = SessionId::new("abc-def-ghi".to_string());
     
@@ -396,7 +592,94 @@ pub fn modern_newtype_pattern() {
         println!("处理用户ID: {}, 会话ID: {}", user_id.as_u64(), session_id.as_str());
     }
     
+    // 派生 Eq + Hash 后可以作为 HashMap 的键
+    let mut sessions = std::collections::HashMap::new();
+    sessions.insert(UserId::from(12345), SessionId::from("abc-def-ghi".to_string()));
+    println!("会话表中用户 12345 存在: {}", sessions.contains_key(&user_id));
+    
     process_user(user_id, session_id);
+    
+    // const 泛型限定取值范围：百分比只能是 0..=100
+    type Percentage = BoundedU32<0, 100>;
+    for raw in [42, 100, 101] {
+        match Percentage::new(raw) {
+            Some(progress) => println!("进度: {}%", progress.get()),
+            None => println!("{} 超出百分比范围 0..=100", raw),
+        }
+    }
+}
+
+/// 树节点的共享句柄
+pub type TreeNodeRef<T> = Rc<RefCell<TreeNode<T>>>;
+
+/// 通过 `Rc` 持有子节点、`Weak` 指向父节点的树
+///
+/// 父子之间只有一个方向是强引用，因此不会形成引用循环：
+/// 丢弃根节点的最后一个句柄后，整棵树都会被释放。
+pub struct TreeNode<T> {
+    value: T,
+    parent: Weak<RefCell<TreeNode<T>>>,
+    children: Vec<TreeNodeRef<T>>,
+}
+
+impl<T> TreeNode<T> {
+    /// 创建没有父节点的根节点
+    pub fn new(value: T) -> TreeNodeRef<T> {
+        Rc::new(RefCell::new(Self {
+            value,
+            parent: Weak::new(),
+            children: Vec::new(),
+        }))
+    }
+
+    /// 在 `parent` 下新增子节点并返回其句柄
+    pub fn add_child(parent: &TreeNodeRef<T>, value: T) -> TreeNodeRef<T> {
+        let child = Self::new(value);
+        child.borrow_mut().parent = Rc::downgrade(parent);
+        parent.borrow_mut().children.push(Rc::clone(&child));
+        child
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// 父节点；根节点或父节点已被释放时返回 `None`
+    pub fn parent(&self) -> Option<TreeNodeRef<T>> {
+        self.parent.upgrade()
+    }
+
+    pub fn children(&self) -> &[TreeNodeRef<T>] {
+        &self.children
+    }
+
+    /// 到根节点的距离，根节点为 0
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut current = self.parent();
+        while let Some(node) = current {
+            depth += 1;
+            current = node.borrow().parent();
+        }
+        depth
+    }
+}
+
+/// 演示用 `Weak` 父指针构建无循环的树
+pub fn modern_weak_tree() {
+    println!("🌳 Rc + Weak 构建的树：");
+
+    let root = TreeNode::new("根");
+    let branch = TreeNode::add_child(&root, "分支");
+    let leaf = TreeNode::add_child(&branch, "叶子");
+
+    let parent = leaf.borrow().parent().map(|node| *node.borrow().value());
+    println!("叶子的父节点: {:?}，深度: {}", parent, leaf.borrow().depth());
+    println!(
+        "根节点强引用数: {}，弱引用数: {}",
+        Rc::strong_count(&root),
+        Rc::weak_count(&root)
+    );
 }
 
 /// 演示工厂模式
@@ -652,24 +935,11 @@ pub fn decorator_pattern() {
         fn encrypt(&self, data: &str) -> String {
             // 这里使用 ROT13 作为“可逆变换”示例，便于演示装饰器链式处理。
             // 它不是安全加密算法，因此仅用于教学场景。
-            data.chars()
-                .map(|c| match c {
-                    'a'..='z' => {
-                        let offset = c as u8 - b'a';
-                        ((offset + 13) % 26 + b'a') as char
-                    }
-                    'A'..='Z' => {
-                        let offset = c as u8 - b'A';
-                        ((offset + 13) % 26 + b'A') as char
-                    }
-                    _ => c,
-                })
-                .collect()
+            crate::security::rot_n(data, 13)
         }
         
-        #[allow(dead_code)]
         fn decrypt(&self, data: &str) -> String {
-            self.encrypt(data) // ROT13 对英文字母是对称的
+            crate::security::rot_n_decode(data, 13)
         }
     }
     
@@ -736,15 +1006,70 @@ pub fn decorator_pattern() {
     compressed_encrypted_source.write_data("重要数据");
 }
 
+/// 观察者：接收类型为 `E` 的事件
+pub trait Observer<E> {
+    fn on_event(&self, event: &E);
+}
+
+/// 主题：持有一组观察者，并把类型化事件依次分发给它们
+///
+/// 事件是普通的结构体而不是字符串，编译器会检查发布方与观察者对事件内容的约定。
+pub struct Subject<E> {
+    observers: Vec<(u32, Box<dyn Observer<E>>)>,
+    next_id: u32,
+}
+
+impl<E> Subject<E> {
+    pub fn new() -> Self {
+        Self {
+            observers: Vec::new(),
+            next_id: 1,
+        }
+    }
+    
+    /// 注册观察者，返回用于 [`detach`](Self::detach) 的 ID
+    pub fn attach(&mut self, observer: Box<dyn Observer<E>>) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.observers.push((id, observer));
+        id
+    }
+    
+    /// 注销观察者；ID 不存在时返回 `false`
+    pub fn detach(&mut self, observer_id: u32) -> bool {
+        let before = self.observers.len();
+        self.observers.retain(|(id, _)| *id != observer_id);
+        self.observers.len() != before
+    }
+    
+    /// 按注册顺序把事件分发给所有观察者
+    pub fn notify(&self, event: &E) {
+        for (_, observer) in &self.observers {
+            observer.on_event(event);
+        }
+    }
+    
+    /// 当前注册的观察者数量
+    pub fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
+}
+
+impl<E> Default for Subject<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// 演示观察者模式
 pub fn observer_pattern() {
     println!("👀 观察者模式：");
     
-    use std::collections::HashMap;
-    
-    // 观察者特征
-    trait Observer {
-        fn update(&self, event: &str, data: &str);
+    // 类型化的事件
+    #[derive(Debug)]
+    struct NewsPublished {
+        headline: String,
+        content: String,
     }
     
     #[derive(Debug)]
@@ -753,59 +1078,38 @@ pub fn observer_pattern() {
         name: String,
     }
     
-    impl Observer for ConcreteObserver {
-        fn update(&self, event: &str, data: &str) {
-            println!("观察者 {} ({}) 收到通知 - 事件: {}, 数据: {}",
-                     self.id, self.name, event, data);
+    impl Observer<NewsPublished> for ConcreteObserver {
+        fn on_event(&self, event: &NewsPublished) {
+            println!("观察者 {} ({}) 收到通知 - 新闻: {}, 内容: {}",
+                     self.id, self.name, event.headline, event.content);
         }
     }
     
-    // 主题特征
-    trait Subject {
-        fn attach(&mut self, observer: Box<dyn Observer>);
-        fn detach(&mut self, observer_id: u32);
-        fn notify(&self, event: &str, data: &str);
-    }
-    
-    // 具体主题
+    // 具体主题：把发布新闻包装为事件分发
     struct NewsAgency {
-        observers: HashMap<u32, Box<dyn Observer>>,
-        next_id: u32,
+        subject: Subject<NewsPublished>,
     }
     
     impl NewsAgency {
         fn new() -> Self {
-            Self {
-                observers: HashMap::new(),
-                next_id: 1,
-            }
+            Self { subject: Subject::new() }
         }
         
-        fn publish_news(&self, headline: String, content: String) {
-            println!("📰 发布新闻: {}", headline);
-            self.notify("news_published", &format!("{}: {}", headline, content));
-        }
-    }
-    
-    impl Subject for NewsAgency {
-        fn attach(&mut self, observer: Box<dyn Observer>) {
-            let id = self.next_id;
-            self.observers.insert(id, observer);
-            self.next_id += 1;
+        fn attach(&mut self, observer: Box<dyn Observer<NewsPublished>>) {
+            let id = self.subject.attach(observer);
             println!("✅ 新的观察者已注册，ID: {}", id);
         }
         
         fn detach(&mut self, observer_id: u32) {
-            if self.observers.remove(&observer_id).is_some() {
+            if self.subject.detach(observer_id) {
                 println!("❌ 观察者 {} 已注销", observer_id);
             }
         }
         
-        fn notify(&self, event: &str, data: &str) {
-            for (id, observer) in &self.observers {
-                observer.update(event, data);
-                println!("📡 通知观察者 {} 已更新", id);
-            }
+        fn publish_news(&self, headline: String, content: String) {
+            println!("📰 发布新闻: {}", headline);
+            self.subject.notify(&NewsPublished { headline, content });
+            println!("📡 已通知 {} 个观察者", self.subject.observer_count());
         }
     }
     
@@ -966,6 +1270,12 @@ pub fn run_advanced_types_examples() {
     println!();
     
     modern_newtype_pattern();
+    println!();
+    
+    modern_weak_tree();
+    println!();
+    
+    const_generic_matrix();
     
     println!("\n✅ 所有高级类型和生命周期示例运行完成！");
 }
@@ -3,8 +3,259 @@
 //! 这个模块演示了Rust的基础语法概念，包括变量、函数、控制流等。
 //! 采用了现代化的Rust 2021/2024最佳实践。
 
-use std::collections::{HashMap, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+pub mod algorithms;
+pub mod test_util;
+
+/// 使用矩阵快速幂计算斐波那契数列第 `n` 项，溢出时返回 `None`
+///
+/// 计算 `[[1, 1], [1, 0]]^(n-1)`，其左上角即为 `F(n)`，所有中间项都不超过 `F(n)`，
+/// 因此 `u64` 能表示的最大项 `F(93)` 也可以精确算出。
+pub fn checked_fib(n: u32) -> Option<u64> {
+    type Matrix = [[u64; 2]; 2];
+
+    fn checked_multiply(a: Matrix, b: Matrix) -> Option<Matrix> {
+        let cell = |i: usize, j: usize| {
+            a[i][0]
+                .checked_mul(b[0][j])?
+                .checked_add(a[i][1].checked_mul(b[1][j])?)
+        };
+        Some([[cell(0, 0)?, cell(0, 1)?], [cell(1, 0)?, cell(1, 1)?]])
+    }
+
+    if n == 0 {
+        return Some(0);
+    }
+
+    let mut result: Matrix = [[1, 0], [0, 1]]; // 单位矩阵
+    let mut matrix: Matrix = [[1, 1], [1, 0]];
+    let mut power = n - 1;
+
+    while power > 0 {
+        if power % 2 == 1 {
+            result = checked_multiply(result, matrix)?;
+        }
+        power /= 2;
+        // 最后一轮无需再平方，避免不必要的溢出
+        if power > 0 {
+            matrix = checked_multiply(matrix, matrix)?;
+        }
+    }
+
+    Some(result[0][0])
+}
+
+/// 计算斐波那契数列第 `n` 项，溢出时饱和为 `u64::MAX`
+pub fn saturating_fib(n: u32) -> u64 {
+    checked_fib(n).unwrap_or(u64::MAX)
+}
+
+/// 计算两个字符串之间的 Levenshtein 编辑距离
+///
+/// 按 Unicode `char` 而不是字节比较；动态规划只保留一行，
+/// 额外空间为 O(min(len(a), len(b)))。
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // 让较短的字符串作为行，减少空间占用
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    let mut row: Vec<usize> = (0..=short.len()).collect();
+    for (i, long_char) in long.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, short_char) in short.iter().enumerate() {
+            let substitution = diagonal + usize::from(long_char != short_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[short.len()]
+}
+
+/// 不区分大小写的子串匹配
+///
+/// 只把 `needle` 转小写一次，再在 `haystack` 的每个字符位置上逐字符比较其小写形式，
+/// 不为 `haystack` 分配完整的小写副本。空 `needle` 总是匹配。
+///
+/// 已知限制：只做 Unicode 默认的逐字符小写映射，不处理语言相关规则。
+/// 例如土耳其语的 `İ` 小写为 `i` 加组合点，因此 `icontains("İstanbul", "istanbul")` 为 `false`。
+pub fn icontains(haystack: &str, needle: &str) -> bool {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return true;
+    }
+
+    haystack.char_indices().any(|(start, _)| {
+        let mut candidate = haystack[start..].chars().flat_map(char::to_lowercase);
+        needle
+            .iter()
+            .all(|expected| candidate.next() == Some(*expected))
+    })
+}
+
+/// 取 `slice[start..start + len]`，越界（含部分越界）时返回 `None` 而不是 panic
+pub fn safe_window<T>(slice: &[T], start: usize, len: usize) -> Option<&[T]> {
+    slice.get(start..start.checked_add(len)?)
+}
+
+/// 按 `sep` 切分一行文本并去掉每个字段两端的空白
+///
+/// 字段直接借用自 `line`，不分配新字符串；相邻分隔符之间的空字段保留为 `""`。
+pub fn split_trim(line: &str, sep: char) -> impl Iterator<Item = &str> {
+    line.split(sep).map(str::trim)
+}
+
+/// 可复现的线性同余伪随机数生成器
+///
+/// 使用 Knuth MMIX 常量，相同种子总是产生相同序列，适合需要稳定输出的演示与测试；
+/// 不具备密码学安全性。
+#[derive(Debug, Clone)]
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    const INCREMENT: u64 = 1_442_695_040_888_963_407;
+
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// 生成下一个 `u64`
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(Self::INCREMENT);
+        self.state
+    }
+
+    /// 生成 `[low, high)` 区间内的整数
+    ///
+    /// 用乘法取高位映射到区间，避免 LCG 低位周期短的问题。`low >= high` 时 panic。
+    pub fn gen_range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low < high, "gen_range 要求 low < high");
+        let span = (high - low) as u128;
+        low + ((self.next_u64() as u128 * span) >> 64) as u64
+    }
+
+    /// Fisher-Yates 原地洗牌
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.gen_range(0, i as u64 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+}
+
+/// 单调递增的 ID 生成器
+///
+/// 默认从 1 开始，每次调用 [`IdGenerator::next`] 返回当前值并加一；
+/// 多线程共享时使用 [`AtomicIdGenerator`]。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdGenerator {
+    next: u64,
+}
+
+impl IdGenerator {
+    pub fn new() -> Self {
+        Self::new_starting_at(1)
+    }
+
+    /// 从 `n` 开始分配，常用于从持久化数据中恢复
+    pub fn new_starting_at(n: u64) -> Self {
+        Self { next: n }
+    }
+
+    /// 分配下一个 ID
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        let id = self.next;
+        self.next += 1;
+        id
+    }
+
+    /// 查看下一次将分配的 ID，不消耗它
+    pub fn peek(&self) -> u64 {
+        self.next
+    }
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 线程安全的单调递增 ID 生成器，可通过 `&self` 在多个线程间共享
+#[derive(Debug)]
+pub struct AtomicIdGenerator {
+    next: AtomicU64,
+}
+
+impl AtomicIdGenerator {
+    pub fn new() -> Self {
+        Self::new_starting_at(1)
+    }
+
+    /// 从 `n` 开始分配
+    pub fn new_starting_at(n: u64) -> Self {
+        Self {
+            next: AtomicU64::new(n),
+        }
+    }
+
+    /// 分配下一个 ID；并发调用也保证互不重复
+    pub fn next(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// 查看下一次将分配的 ID（并发场景下仅供参考）
+    pub fn peek(&self) -> u64 {
+        self.next.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for AtomicIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 蓄水池抽样（Algorithm R）
+///
+/// 单次遍历从长度未知的流中等概率抽取 `k` 个元素；流不足 `k` 个时返回全部元素。
+pub fn reservoir_sample<T: Clone>(
+    iter: impl Iterator<Item = T>,
+    k: usize,
+    rng: &mut Lcg,
+) -> Vec<T> {
+    let mut reservoir = Vec::with_capacity(k);
+
+    for (index, item) in iter.enumerate() {
+        if index < k {
+            reservoir.push(item);
+        } else {
+            let slot = rng.gen_range(0, index as u64 + 1) as usize;
+            if slot < k {
+                reservoir[slot] = item;
+            }
+        }
+    }
+
+    reservoir
+}
 
 /// 演示变量声明和基本类型
 pub fn variables_and_types() {
@@ -103,6 +354,219 @@ pub fn control_flow() {
     println!("偶数的平方: {:?}", even_squares);
 }
 
+/// 固定容量的环形缓冲区
+///
+/// 写满后继续 `push` 会覆盖最旧的元素，并将其返回给调用者。
+#[derive(Debug)]
+pub struct RingBuffer<T> {
+    slots: Vec<Option<T>>,
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// 创建指定容量的环形缓冲区
+    ///
+    /// # Panics
+    ///
+    /// 容量为 0 时 panic。
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "RingBuffer 容量必须大于 0");
+        Self {
+            slots: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    /// 写入元素；缓冲区已满时覆盖并返回最旧的元素
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let evicted = if self.is_full() {
+            self.head = (self.head + 1) % self.capacity();
+            self.slots[self.tail].take()
+        } else {
+            self.len += 1;
+            None
+        };
+
+        self.slots[self.tail] = Some(item);
+        self.tail = (self.tail + 1) % self.capacity();
+        evicted
+    }
+
+    /// 取出最旧的元素
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let item = self.slots[self.head].take();
+        self.head = (self.head + 1) % self.capacity();
+        self.len -= 1;
+        item
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+/// 带非负权重的有向图，使用邻接表存储
+///
+/// 节点以 `0..node_count` 的下标表示。
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    adjacency: Vec<Vec<(usize, u64)>>,
+}
+
+impl Graph {
+    /// 创建包含 `node_count` 个孤立节点的图
+    pub fn new(node_count: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); node_count],
+        }
+    }
+
+    /// 添加一条 `from -> to` 的有向边
+    ///
+    /// # Panics
+    ///
+    /// 任一端点超出节点范围时 panic。
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        assert!(to < self.node_count(), "边的终点 {to} 超出节点范围");
+        self.adjacency[from].push((to, weight));
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Dijkstra 单源最短路径，不可达的节点距离为 `None`
+    ///
+    /// 总权重超出 `u64` 范围的路径视为不可走，不会溢出回绕。
+    ///
+    /// # Panics
+    ///
+    /// `start` 超出节点范围时 panic。
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<u64>> {
+        self.dijkstra_with_predecessors(start).0
+    }
+
+    /// 返回 `start` 到 `end` 的最小总权重及途经节点（含两端），不可达时返回 `None`
+    pub fn shortest_path(&self, start: usize, end: usize) -> Option<(u64, Vec<usize>)> {
+        if start >= self.node_count() || end >= self.node_count() {
+            return None;
+        }
+
+        let (distances, predecessors) = self.dijkstra_with_predecessors(start);
+        let cost = distances[end]?;
+
+        let mut path = vec![end];
+        let mut node = end;
+        while let Some(previous) = predecessors[node] {
+            path.push(previous);
+            node = previous;
+        }
+        path.reverse();
+
+        Some((cost, path))
+    }
+
+    /// 沿有向边广度优先遍历，返回从 `start` 出发的访问顺序
+    ///
+    /// # Panics
+    ///
+    /// `start` 超出节点范围时 panic。
+    pub fn bfs(&self, start: usize) -> Vec<usize> {
+        bfs_order(&self.adjacency, start, &mut vec![false; self.node_count()])
+    }
+
+    /// 忽略边的方向后求连通分量，每个分量内节点升序，分量按最小节点排序
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut undirected = vec![Vec::new(); self.node_count()];
+        for (from, edges) in self.adjacency.iter().enumerate() {
+            for &(to, weight) in edges {
+                undirected[from].push((to, weight));
+                undirected[to].push((from, weight));
+            }
+        }
+
+        let mut visited = vec![false; self.node_count()];
+        let mut components = Vec::new();
+        for node in 0..self.node_count() {
+            if !visited[node] {
+                let mut component = bfs_order(&undirected, node, &mut visited);
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+        components
+    }
+
+    /// 同时记录每个节点在最短路径上的前驱，用于还原路径
+    fn dijkstra_with_predecessors(&self, start: usize) -> (Vec<Option<u64>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; self.node_count()];
+        let mut predecessors = vec![None; self.node_count()];
+        let mut heap: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+
+        distances[start] = Some(0);
+        heap.push(Reverse((0, start)));
+
+        while let Some(Reverse((distance, node))) = heap.pop() {
+            // 堆中可能残留同一节点的过期条目，跳过比已知距离更长的
+            if distances[node].is_some_and(|best| distance > best) {
+                continue;
+            }
+
+            for &(next, weight) in &self.adjacency[node] {
+                let Some(candidate) = distance.checked_add(weight) else {
+                    continue;
+                };
+                if distances[next].is_none_or(|best| candidate < best) {
+                    distances[next] = Some(candidate);
+                    predecessors[next] = Some(node);
+                    heap.push(Reverse((candidate, next)));
+                }
+            }
+        }
+
+        (distances, predecessors)
+    }
+}
+
+/// 从 `start` 开始广度优先遍历邻接表，跳过并标记 `visited` 中的节点
+fn bfs_order(adjacency: &[Vec<(usize, u64)>], start: usize, visited: &mut [bool]) -> Vec<usize> {
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for &(next, _) in &adjacency[node] {
+            if !visited[next] {
+                visited[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    order
+}
+
 /// 演示现代化数据结构实现
 pub fn modern_data_structures() {
     println!("🏗️ 现代化数据结构：");
@@ -192,6 +656,23 @@ pub fn modern_data_structures() {
     while let Some(item) = queue.dequeue() {
         println!("处理: {}", item);
     }
+
+    // 固定容量的环形缓冲区
+    let mut ring = RingBuffer::new(3);
+    for reading in [10, 20, 30, 40] {
+        if let Some(evicted) = ring.push(reading) {
+            println!("🔁 缓冲区已满，覆盖最旧数据: {}", evicted);
+        }
+    }
+    println!(
+        "环形缓冲区长度: {}, 是否已满: {}",
+        ring.len(),
+        ring.is_full()
+    );
+
+    while let Some(reading) = ring.pop_front() {
+        println!("读取: {}", reading);
+    }
 }
 
 /// 演示高级算法实现（增强版）
@@ -205,61 +686,35 @@ pub fn modern_data_structures() {
 pub fn advanced_algorithms() {
     println!("🔬 高级算法实现：");
 
-    /// 原地快速排序算法（优化版）
-    ///
-    /// 这个函数实现了原地快速排序算法，使用Lomuto分区方案。
-    /// 与原始实现相比，这个版本减少了不必要的内存分配和克隆操作。
-    ///
-    /// # 参数
-    /// - `arr`: 要排序的可变切片
-    ///
-    /// # 示例
-    /// ```ignore
-    /// let mut numbers = vec![64, 34, 25, 12, 22, 11, 90];
-    /// quick_sort_optimized(&mut numbers);
-    /// assert_eq!(numbers, vec![11, 12, 22, 25, 34, 64, 90]);
-    /// ```
-    fn quick_sort_optimized<T: PartialOrd + std::fmt::Display>(arr: &mut [T]) {
-        if arr.len() <= 1 {
-            return;
-        }
+    let mut numbers = vec![64, 34, 25, 12, 22, 11, 90];
+    println!("排序前: {:?}", numbers);
+    algorithms::quick_sort_optimized(&mut numbers);
+    println!("排序后: {:?}", numbers);
 
-        let pivot = partition(arr);
-        quick_sort_optimized(&mut arr[..pivot]);
-        quick_sort_optimized(&mut arr[pivot + 1..]);
+    // 全部相等的元素会让 Lomuto 分区每次只剥离一个元素，深度保护会及时切换到堆排序
+    let mut repeated = vec![7u8; 100_000];
+    algorithms::quick_sort_optimized(&mut repeated);
+    println!("10 万个相同元素排序完成，未发生栈溢出");
+
+    // 运行时选择稳定/不稳定排序：只按首字母比较时，稳定排序保留同首字母单词的原顺序
+    #[derive(Debug, PartialEq, Eq)]
+    struct ByInitial(&'static str);
+    impl PartialOrd for ByInitial {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
     }
-
-    /// Lomuto分区方案
-    ///
-    /// 这个函数用于快速排序算法中的分区操作。
-    /// 它选择最后一个元素作为枢轴，并将数组重新排列，
-    /// 使得所有小于枢轴的元素都在枢轴左侧，所有大于枢轴的元素都在枢轴右侧。
-    ///
-    /// # 参数
-    /// - `arr`: 要分区的可变切片
-    ///
-    /// # 返回值
-    /// 枢轴的最终索引位置
-    fn partition<T: PartialOrd>(arr: &mut [T]) -> usize {
-        let pivot_index = arr.len() / 2;
-        arr.swap(pivot_index, arr.len() - 1);
-
-        let mut i = 0;
-        for j in 0..arr.len() - 1 {
-            if arr[j] < arr[arr.len() - 1] {
-                arr.swap(i, j);
-                i += 1;
-            }
+    impl Ord for ByInitial {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.chars().next().cmp(&other.0.chars().next())
         }
-
-        arr.swap(i, arr.len() - 1);
-        i
     }
-
-    let mut numbers = vec![64, 34, 25, 12, 22, 11, 90];
-    println!("排序前: {:?}", numbers);
-    quick_sort_optimized(&mut numbers);
-    println!("排序后: {:?}", numbers);
+    for stable in [true, false] {
+        let mut words = ["banana", "apple", "blueberry", "avocado", "cherry"].map(ByInitial);
+        algorithms::sort(&mut words, stable);
+        let sorted: Vec<&str> = words.iter().map(|word| word.0).collect();
+        println!("按首字母排序（stable = {}）: {:?}", stable, sorted);
+    }
 
     // === 2. 优化二分查找算法 ===
     fn binary_search_optimized(arr: &[i32], target: i32) -> Option<usize> {
@@ -286,148 +741,65 @@ pub fn advanced_algorithms() {
 
     // === 3. 优化斐波那契数列（矩阵快速幂） ===
     fn fibonacci_matrix(n: usize) -> u64 {
-        if n <= 1 {
-            return n as u64;
-        }
-
-        fn matrix_multiply(a: [[u64; 2]; 2], b: [[u64; 2]; 2]) -> [[u64; 2]; 2] {
-            [
-                [
-                    a[0][0] * b[0][0] + a[0][1] * b[1][0],
-                    a[0][0] * b[0][1] + a[0][1] * b[1][1],
-                ],
-                [
-                    a[1][0] * b[0][0] + a[1][1] * b[1][0],
-                    a[1][0] * b[0][1] + a[1][1] * b[1][1],
-                ],
-            ]
-        }
-
-        fn matrix_power(mut matrix: [[u64; 2]; 2], mut power: usize) -> [[u64; 2]; 2] {
-            let mut result = [[1, 0], [0, 1]]; // 单位矩阵
-
-            while power > 0 {
-                if power % 2 == 1 {
-                    result = matrix_multiply(result, matrix);
-                }
-                matrix = matrix_multiply(matrix, matrix);
-                power /= 2;
-            }
-
-            result
-        }
-
-        let base = [[1, 1], [1, 0]];
-        let result = matrix_power(base, n);
-        result[0][0]
+        use crate::advanced_types::Matrix;
+
+        let base = Matrix::new([[1, 1], [1, 0]]);
+        let result = algorithms::pow_by_squaring(
+            base,
+            n as u64,
+            |a, b| a.matmul(b),
+            Matrix::<2, 2>::identity(),
+        );
+        result.get(0, 1)
     }
 
     let n = 10;
     println!("斐波那契数列第{}项: {}", n, fibonacci_matrix(n));
+    println!("checked_fib(93): {:?}", checked_fib(93));
+    println!("checked_fib(94): {:?}", checked_fib(94));
+    println!("saturating_fib(94): {}", saturating_fib(94));
+    println!(
+        "编辑距离 kitten -> sitting: {}",
+        levenshtein("kitten", "sitting")
+    );
 
     // === 4. 动态规划：背包问题 ===
-    fn knapsack_01(weights: &[i32], values: &[i32], capacity: i32) -> i32 {
-        let n = weights.len();
-        let mut dp = vec![vec![0; capacity as usize + 1]; n + 1];
-
-        for i in 1..=n {
-            for w in 0..=capacity {
-                dp[i][w as usize] = dp[i - 1][w as usize]; // 不选当前物品
-
-                if weights[i - 1] <= w {
-                    dp[i][w as usize] = dp[i][w as usize]
-                        .max(dp[i - 1][(w - weights[i - 1]) as usize] + values[i - 1]);
-                }
-            }
-        }
-
-        dp[n][capacity as usize]
-    }
-
-    let weights = vec![2, 3, 4, 5];
-    let values = vec![3, 4, 5, 6];
+    let weights = [2, 3, 4, 5];
+    let values = [3, 4, 5, 6];
     let capacity = 8;
 
-    let max_value = knapsack_01(&weights, &values, capacity);
-    println!("背包问题最大价值: {}", max_value);
-
-    // === 5. 最短路径算法（Dijkstra） ===
-    use std::cmp::Ordering;
-    use std::collections::BinaryHeap;
-
-    #[derive(Copy, Clone)]
-    struct Edge {
-        to: usize,
-        weight: i32,
-    }
-
-    impl PartialEq for Edge {
-        fn eq(&self, other: &Self) -> bool {
-            self.weight == other.weight
-        }
-    }
-
-    impl Eq for Edge {}
-
-    impl PartialOrd for Edge {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.weight.cmp(&other.weight))
-        }
+    match algorithms::knapsack_01(&weights, &values, capacity) {
+        Ok(max_value) => println!("背包问题最大价值: {}", max_value),
+        Err(error) => println!("背包问题输入无效: {}", error),
     }
 
-    impl Ord for Edge {
-        fn cmp(&self, other: &Self) -> Ordering {
-            self.weight.cmp(&other.weight).reverse()
-        }
-    }
-
-    fn dijkstra(graph: &Vec<Vec<Edge>>, start: usize) -> Vec<i32> {
-        let n = graph.len();
-        let mut dist = vec![i32::MAX; n];
-        let mut visited = vec![false; n];
-        let mut pq = BinaryHeap::new();
-
-        dist[start] = 0;
-        pq.push(Edge {
-            to: start,
-            weight: 0,
-        });
-
-        while let Some(Edge {
-            to: node,
-            weight: _,
-        }) = pq.pop()
-        {
-            if visited[node] {
-                continue;
-            }
-            visited[node] = true;
-
-            for edge in &graph[node] {
-                let new_dist = dist[node] + edge.weight;
-                if new_dist < dist[edge.to] {
-                    dist[edge.to] = new_dist;
-                    pq.push(Edge {
-                        to: edge.to,
-                        weight: new_dist,
-                    });
-                }
-            }
-        }
-
-        dist
-    }
-
-    // 构建示例图
-    let mut graph = vec![vec![]; 4];
-    graph[0].push(Edge { to: 1, weight: 4 });
-    graph[0].push(Edge { to: 2, weight: 1 });
-    graph[1].push(Edge { to: 2, weight: 2 });
-    graph[1].push(Edge { to: 3, weight: 5 });
-    graph[2].push(Edge { to: 3, weight: 3 });
-
-    let distances = dijkstra(&graph, 0);
+    // === 5. 最短路径算法（Dijkstra） ===
+    let mut graph = Graph::new(4);
+    graph.add_edge(0, 1, 4);
+    graph.add_edge(0, 2, 1);
+    graph.add_edge(1, 2, 2);
+    graph.add_edge(1, 3, 5);
+    graph.add_edge(2, 3, 3);
+
+    let distances = graph.dijkstra(0);
     println!("从节点0的最短距离: {:?}", distances);
+    if let Some((cost, path)) = graph.shortest_path(0, 3) {
+        println!("节点0到节点3的最短路径: {:?}，总权重 {}", path, cost);
+    }
+    println!("从节点0广度优先遍历: {:?}", graph.bfs(0));
+
+    // 可复现的伪随机洗牌
+    let mut rng = Lcg::new(42);
+    let mut deck: Vec<u32> = (1..=8).collect();
+    rng.shuffle(&mut deck);
+    println!("种子 42 洗牌结果: {:?}", deck);
+    let sample = reservoir_sample(1..=100, 5, &mut rng);
+    println!("从 1..=100 中蓄水池抽样 5 个: {:?}", sample);
+
+    // 双调排序网络：长度不是 2 的幂时先填充再截断
+    let mut network_input = [9, -3, 7, 0, 7, 2, -8];
+    algorithms::bitonic_sort(&mut network_input);
+    println!("双调排序结果: {:?}", network_input);
 }
 
 /// 演示闭包和高阶函数
@@ -485,11 +857,144 @@ pub fn closures_and_higher_order_functions() {
     println!("计数器: {}", counter());
 }
 
+/// 基于锁文件的跨进程互斥写入守卫
+///
+/// 构造时原子地创建 `<路径>.lock`，析构时删除，从而让多个进程对同一文件的写入串行化。
+/// 锁文件中记录持有者的进程 ID 与加锁时间；进程崩溃留下的锁在超过
+/// [`FileLock::STALE_AFTER`] 或持有进程已不存在（仅 Linux 可检测）时视为失效并被回收。
+/// 这是协作式的建议锁，只对同样使用 `FileLock` 的写入方生效。
+#[derive(Debug)]
+pub struct FileLock {
+    lock_path: PathBuf,
+}
+
+impl FileLock {
+    /// 重试 `acquire_blocking` 时两次尝试之间的等待时间
+    const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// 锁文件存在超过该时长即视为持有者已崩溃
+    pub const STALE_AFTER: Duration = Duration::from_secs(30);
+
+    /// 尝试立即加锁；锁已被其他守卫持有时返回 `Ok(None)`
+    ///
+    /// 遇到失效的锁文件（见类型文档）时先删除再重试一次。
+    pub fn try_acquire(path: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        let lock_path = lock_path_for(path.as_ref());
+        match Self::create(&lock_path)? {
+            Some(lock) => Ok(Some(lock)),
+            None if lock_is_stale(&lock_path) => {
+                match fs::remove_file(&lock_path) {
+                    Ok(()) => {}
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                    Err(error) => return Err(error),
+                }
+                Self::create(&lock_path)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn create(lock_path: &Path) -> io::Result<Option<Self>> {
+        use std::io::Write;
+
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(lock_path)
+        {
+            Ok(mut file) => {
+                let lock = Self {
+                    lock_path: lock_path.to_path_buf(),
+                };
+                writeln!(file, "{}\n{}", std::process::id(), unix_millis_now())?;
+                Ok(Some(lock))
+            }
+            Err(error) if error.kind() == io::ErrorKind::AlreadyExists => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// 在 `timeout` 内反复尝试加锁，超时返回 `ErrorKind::TimedOut`
+    pub fn acquire_blocking(path: impl AsRef<Path>, timeout: Duration) -> io::Result<Self> {
+        let path = path.as_ref();
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(lock) = Self::try_acquire(path)? {
+                return Ok(lock);
+            }
+            if Instant::now() >= deadline {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("等待文件锁超时: {}", lock_path_for(path).display()),
+                ));
+            }
+            std::thread::sleep(Self::RETRY_INTERVAL);
+        }
+    }
+
+    /// 锁文件所在路径
+    pub fn lock_path(&self) -> &Path {
+        &self.lock_path
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.lock_path);
+    }
+}
+
+fn unix_millis_now() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+}
+
+/// 判断锁文件是否失效：记录的加锁时间过旧，或记录的进程已退出
+fn lock_is_stale(lock_path: &Path) -> bool {
+    let Ok(contents) = fs::read_to_string(lock_path) else {
+        // 读取失败多半是锁刚被释放，交给下一次 create_new 判断
+        return false;
+    };
+    let mut fields = contents.lines().map(str::trim);
+    let pid = fields.next().and_then(|field| field.parse::<u32>().ok());
+    let locked_at = fields.next().and_then(|field| field.parse::<u128>().ok());
+    let (Some(pid), Some(locked_at)) = (pid, locked_at) else {
+        // 持有者可能刚创建文件、尚未写入内容，按文件修改时间判断
+        return fs::metadata(lock_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > FileLock::STALE_AFTER);
+    };
+
+    let age = unix_millis_now().saturating_sub(locked_at);
+    age > FileLock::STALE_AFTER.as_millis() || !process_is_alive(pid)
+}
+
+/// 检查进程是否仍在运行；无法判断的平台一律视为存活，只依赖超时回收
+fn process_is_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+    if cfg!(target_os = "linux") {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        true
+    }
+}
+
+fn lock_path_for(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".lock");
+    path.with_file_name(file_name)
+}
+
 /// 演示文件操作和IO
 pub fn file_operations() {
     println!("📁 文件操作和IO：");
 
-    use std::fs;
     use std::time::{SystemTime, UNIX_EPOCH};
 
     let temp_dir = std::env::temp_dir();
@@ -506,10 +1011,28 @@ pub fn file_operations() {
 
     println!("🧪 使用临时文件演示，避免污染工作区: {:?}", demo_file);
 
-    match fs::write(&demo_file, content) {
-        Ok(_) => println!("✅ 创建临时演示文件成功"),
+    match FileLock::try_acquire(&demo_file) {
+        Ok(Some(lock)) => {
+            println!("🔒 已获取文件锁: {:?}", lock.lock_path());
+            match FileLock::try_acquire(&demo_file) {
+                Ok(None) => println!("🔒 锁被持有期间，第二次加锁被拒绝"),
+                Ok(Some(_)) => println!("⚠️ 第二次加锁意外成功"),
+                Err(e) => println!("⚠️ 第二次加锁失败: {}", e),
+            }
+            match fs::write(&demo_file, content) {
+                Ok(_) => println!("✅ 创建临时演示文件成功"),
+                Err(e) => {
+                    println!("❌ 创建临时文件失败: {}", e);
+                    return;
+                }
+            }
+        }
+        Ok(None) => {
+            println!("⚠️ 演示文件已被锁定，跳过写入");
+            return;
+        }
         Err(e) => {
-            println!("❌ 创建临时文件失败: {}", e);
+            println!("❌ 获取文件锁失败: {}", e);
             return;
         }
     }
@@ -527,7 +1050,10 @@ pub fn file_operations() {
     if let Ok(metadata) = fs::metadata(&demo_file) {
         println!("📊 文件信息:");
         println!("  路径: {:?}", demo_file);
-        println!("  大小: {} 字节", metadata.len());
+        println!(
+            "  大小: {}",
+            crate::best_practices::format_bytes(metadata.len())
+        );
         println!("  权限: {:?}", metadata.permissions());
     }
 
@@ -538,11 +1064,9 @@ pub fn file_operations() {
 
     if let Ok(entries) = fs::read_dir(&temp_dir) {
         println!("📂 临时目录示例内容（前5项）:");
-        for entry in entries.take(5) {
-            if let Ok(entry) = entry {
-                if let Some(name) = entry.file_name().to_str() {
-                    println!("  📄 {}", name);
-                }
+        for entry in entries.take(5).filter_map(Result::ok) {
+            if let Some(name) = entry.file_name().to_str() {
+                println!("  📄 {}", name);
             }
         }
     }
@@ -593,27 +1117,8 @@ pub fn error_handling_patterns() {
 pub fn modern_enums_and_patterns() {
     println!("🎯 现代枚举和模式匹配：");
 
-    // 使用更丰富的枚举类型
-    #[derive(Debug, Clone)]
-    enum Shape {
-        Circle { radius: f64 },
-        Rectangle { width: f64, height: f64 },
-        Triangle { a: f64, b: f64, c: f64 },
-    }
-
-    impl Shape {
-        fn area(&self) -> f64 {
-            match self {
-                Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
-                Shape::Rectangle { width, height } => width * height,
-                Shape::Triangle { a, b, c } => {
-                    // 使用海伦公式计算三角形面积
-                    let s = (a + b + c) / 2.0;
-                    (s * (s - a) * (s - b) * (s - c)).sqrt()
-                }
-            }
-        }
-    }
+    // 使用更丰富的枚举类型（定义在 types 模块中以便复用）
+    use crate::types::Shape;
 
     // 创建各种形状并计算面积
     let shapes = vec![
@@ -630,44 +1135,28 @@ pub fn modern_enums_and_patterns() {
     ];
 
     for (i, shape) in shapes.iter().enumerate() {
-        println!("形状{}的面积: {:.2}", i + 1, shape.area());
+        println!(
+            "形状{}的面积: {:.2}, 周长: {:.2}",
+            i + 1,
+            shape.area(),
+            shape.perimeter()
+        );
     }
 
-    // 复杂的状态机枚举
-    #[derive(Debug)]
-    enum TrafficLight {
-        Red,
-        Yellow,
-        Green,
-    }
-
-    impl TrafficLight {
-        fn next(&self) -> Self {
-            match self {
-                TrafficLight::Red => TrafficLight::Green,
-                TrafficLight::Green => TrafficLight::Yellow,
-                TrafficLight::Yellow => TrafficLight::Red,
-            }
-        }
-
-        fn get_duration(&self) -> u8 {
-            match self {
-                TrafficLight::Red => 30,
-                TrafficLight::Yellow => 5,
-                TrafficLight::Green => 25,
-            }
-        }
+    if let Err(e) = Shape::triangle(1.0, 2.0, 10.0) {
+        println!("无效三角形: {}", e);
     }
 
-    let mut light = TrafficLight::Red;
-    for i in 0..4 {
+    // 复杂的状态机枚举（定义在 types 模块中以便复用）
+    use crate::types::TrafficLight;
+
+    for (i, light) in TrafficLight::Red.cycle().take(4).enumerate() {
         println!(
             "第{}阶段: {:?} (持续{}秒)",
             i + 1,
             light,
-            light.get_duration()
+            light.duration().as_secs()
         );
-        light = light.next();
     }
 }
 
@@ -0,0 +1,272 @@
+//! 可独立测试的经典算法实现。
+//!
+//! 与 [`advanced_algorithms`](super::advanced_algorithms) 中的演示不同，这里的函数是公开 API，
+//! 处理任意长度的输入，并由测试与标准库实现对照验证。
+
+/// 双调排序（bitonic sort），一种与数据无关（data-oblivious）的排序网络
+///
+/// 比较-交换的顺序只取决于长度而不取决于元素值，因此适合并行硬件或需要恒定访问模式的场景。
+/// 排序网络要求长度为 2 的幂：这里先用 `i32::MAX` 填充到下一个 2 的幂，
+/// 升序排序后填充值全部落在末尾，再截掉它们并写回原切片。
+pub fn bitonic_sort(arr: &mut [i32]) {
+    if arr.len() < 2 {
+        return;
+    }
+
+    let mut padded = arr.to_vec();
+    padded.resize(arr.len().next_power_of_two(), i32::MAX);
+    let n = padded.len();
+
+    // `k` 为当前合并的双调序列长度，`j` 为比较距离
+    let mut k = 2;
+    while k <= n {
+        let mut j = k / 2;
+        while j > 0 {
+            for i in 0..n {
+                let partner = i ^ j;
+                if partner > i {
+                    let ascending = i & k == 0;
+                    if (padded[i] > padded[partner]) == ascending {
+                        padded.swap(i, partner);
+                    }
+                }
+            }
+            j /= 2;
+        }
+        k *= 2;
+    }
+
+    arr.copy_from_slice(&padded[..arr.len()]);
+}
+
+/// `knapsack_01` 的输入校验错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum KnapsackError {
+    #[error("weights has {weights} items but values has {values}")]
+    LengthMismatch { weights: usize, values: usize },
+    #[error("capacity {0} does not fit in memory on this platform")]
+    CapacityTooLarge(u64),
+    #[error("total value overflows u64")]
+    ValueOverflow,
+}
+
+/// 0-1 背包：每件物品最多选一次，返回总重量不超过 `capacity` 时的最大总价值
+///
+/// 使用按容量滚动的一维 DP 表，时间 `O(n * capacity)`、空间 `O(capacity)`；
+/// 所有加法都做溢出检查，`weights` 与 `values` 长度不一致时返回错误而不是越界。
+pub fn knapsack_01(weights: &[u64], values: &[u64], capacity: u64) -> Result<u64, KnapsackError> {
+    if weights.len() != values.len() {
+        return Err(KnapsackError::LengthMismatch {
+            weights: weights.len(),
+            values: values.len(),
+        });
+    }
+    let capacity_slots = usize::try_from(capacity)
+        .ok()
+        .and_then(|capacity| capacity.checked_add(1))
+        .ok_or(KnapsackError::CapacityTooLarge(capacity))?;
+
+    // best[w] 表示容量为 w 时已考虑物品的最大价值
+    let mut best = vec![0u64; capacity_slots];
+    for (&weight, &value) in weights.iter().zip(values) {
+        let Ok(weight) = usize::try_from(weight) else {
+            continue; // 比任何可表示的容量都重，放不下
+        };
+        // 逆序遍历容量，保证每件物品只被使用一次
+        for w in (weight..capacity_slots).rev() {
+            let with_item = best[w - weight]
+                .checked_add(value)
+                .ok_or(KnapsackError::ValueOverflow)?;
+            best[w] = best[w].max(with_item);
+        }
+    }
+
+    Ok(best[capacity_slots - 1])
+}
+
+/// 快速幂（平方求幂），对任意满足结合律的乘法通用
+///
+/// 计算 `base` 的 `exp` 次幂，只需 O(log exp) 次 `mul` 调用；`identity` 为乘法单位元，
+/// `exp == 0` 时直接返回它。整数、矩阵、模运算等只要提供对应的乘法即可复用。
+pub fn pow_by_squaring<T: Clone>(base: T, exp: u64, mul: impl Fn(&T, &T) -> T, identity: T) -> T {
+    let mut result = identity;
+    let mut base = base;
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(&result, &base);
+        }
+        exp >>= 1;
+        // 最后一轮无需再平方，避免不必要的计算与溢出
+        if exp > 0 {
+            base = mul(&base, &base);
+        }
+    }
+
+    result
+}
+
+/// 按需选择稳定或不稳定排序
+///
+/// - `stable = true`：[`merge_sort`]，相等元素保持原有相对顺序，额外占用 O(n) 的下标缓冲区；
+/// - `stable = false`：[`quick_sort`]，原地排序、常数更小，但相等元素的顺序可能被打乱。
+///
+/// 只有当“相等”的元素仍可区分（例如只按某个键比较的记录）时，稳定性才有意义。
+pub fn sort<T: Ord>(arr: &mut [T], stable: bool) {
+    if stable {
+        merge_sort(arr);
+    } else {
+        quick_sort(arr);
+    }
+}
+
+/// 稳定的自底向上归并排序
+///
+/// 归并的是下标而不是元素本身，因此不要求 `T: Clone`；
+/// 排好的下标序列最后按置换环原地交换到位。
+pub fn merge_sort<T: Ord>(arr: &mut [T]) {
+    let n = arr.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut buffer = order.clone();
+
+    let mut width = 1;
+    while width < n {
+        for start in (0..n).step_by(2 * width) {
+            let mid = (start + width).min(n);
+            let end = (start + 2 * width).min(n);
+            let (mut left, mut right) = (start, mid);
+            for slot in &mut buffer[start..end] {
+                // 右侧严格更小时才取右侧，相等时优先取左侧以保持稳定
+                let take_right =
+                    left == mid || (right < end && arr[order[right]] < arr[order[left]]);
+                if take_right {
+                    *slot = order[right];
+                    right += 1;
+                } else {
+                    *slot = order[left];
+                    left += 1;
+                }
+            }
+        }
+        std::mem::swap(&mut order, &mut buffer);
+        width *= 2;
+    }
+
+    // order[k] 是最终应位于 k 处的原下标，沿置换环交换即可原地就位
+    let mut placed = vec![false; n];
+    for cycle_start in 0..n {
+        let mut current = cycle_start;
+        while !placed[current] {
+            placed[current] = true;
+            let source = order[current];
+            if source == cycle_start {
+                break;
+            }
+            arr.swap(current, source);
+            current = source;
+        }
+    }
+}
+
+/// 不稳定的原地快速排序（取中间元素为枢轴的 Lomuto 分区）
+///
+/// 总是对较短的一侧递归、较长的一侧循环，递归深度不超过 O(log n)。
+pub fn quick_sort<T: Ord>(mut arr: &mut [T]) {
+    while arr.len() > 1 {
+        let last = arr.len() - 1;
+        arr.swap(arr.len() / 2, last);
+
+        let mut boundary = 0;
+        for j in 0..last {
+            if arr[j] < arr[last] {
+                arr.swap(boundary, j);
+                boundary += 1;
+            }
+        }
+        arr.swap(boundary, last);
+
+        let (left, rest) = arr.split_at_mut(boundary);
+        let right = &mut rest[1..];
+        if left.len() < right.len() {
+            quick_sort(left);
+            arr = right;
+        } else {
+            quick_sort(right);
+            arr = left;
+        }
+    }
+}
+
+/// 带递归深度保护的原地快速排序（introsort 风格）
+///
+/// 使用取中间元素为枢轴的 Lomuto 分区；递归深度超过 `2 * log2(n)` 时，
+/// 剩余区间改用 [`heap_sort`]。对大量重复元素等会让分区严重失衡的输入，
+/// 既避免了栈溢出，也把最坏情况限制在 O(n log n)。
+pub fn quick_sort_optimized<T: PartialOrd>(arr: &mut [T]) {
+    let max_depth = 2 * arr.len().max(1).ilog2();
+    quick_sort_with_depth_limit(arr, max_depth);
+}
+
+/// 以指定的最大递归深度执行快速排序，超出后对剩余区间使用 [`heap_sort`]
+///
+/// `max_depth` 为 0 时直接堆排序。
+pub fn quick_sort_with_depth_limit<T: PartialOrd>(arr: &mut [T], max_depth: u32) {
+    if arr.len() <= 1 {
+        return;
+    }
+    if max_depth == 0 {
+        heap_sort(arr);
+        return;
+    }
+
+    let pivot = lomuto_partition(arr);
+    let (left, rest) = arr.split_at_mut(pivot);
+    quick_sort_with_depth_limit(left, max_depth - 1);
+    quick_sort_with_depth_limit(&mut rest[1..], max_depth - 1);
+}
+
+/// Lomuto 分区：把中间元素换到末尾作为枢轴，返回枢轴的最终下标
+fn lomuto_partition<T: PartialOrd>(arr: &mut [T]) -> usize {
+    let last = arr.len() - 1;
+    arr.swap(arr.len() / 2, last);
+
+    let mut boundary = 0;
+    for j in 0..last {
+        if arr[j] < arr[last] {
+            arr.swap(boundary, j);
+            boundary += 1;
+        }
+    }
+
+    arr.swap(boundary, last);
+    boundary
+}
+
+/// 原地堆排序，最坏 O(n log n) 且不使用递归
+pub fn heap_sort<T: PartialOrd>(arr: &mut [T]) {
+    for start in (0..arr.len() / 2).rev() {
+        sift_down(arr, start);
+    }
+    for end in (1..arr.len()).rev() {
+        arr.swap(0, end);
+        sift_down(&mut arr[..end], 0);
+    }
+}
+
+/// 把 `root` 处的元素下沉到大顶堆中的正确位置
+fn sift_down<T: PartialOrd>(heap: &mut [T], mut root: usize) {
+    loop {
+        let mut largest = root;
+        for child in [2 * root + 1, 2 * root + 2] {
+            if child < heap.len() && heap[largest] < heap[child] {
+                largest = child;
+            }
+        }
+        if largest == root {
+            return;
+        }
+        heap.swap(root, largest);
+        root = largest;
+    }
+}
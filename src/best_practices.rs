@@ -7,8 +7,414 @@
 #![allow(dead_code)]
 
 use anyhow::Context;
+use rand::RngExt;
+use serde::de::DeserializeOwned;
+use std::cell::OnceCell;
+use std::future::Future;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
+/// 读取并解析 JSON 文件
+///
+/// 读取失败与解析失败都会附带包含文件路径的上下文，便于定位是哪个文件出错。
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("无法读取 JSON 文件: {}", path.display()))?;
+
+    serde_json::from_str(&content)
+        .with_context(|| format!("解析 JSON 文件失败: {}", path.display()))
+}
+
+/// 重试间隔的退避策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// 每次重试都等待 `base_delay`
+    Fixed,
+    /// 第 n 次重试等待 `base_delay * 2^(n-1)`
+    Exponential,
+    /// 在 `[0, 指数退避上限]` 内随机取值（full jitter），避免大量客户端同时重试
+    ExponentialJitter,
+}
+
+/// 同步与异步重试辅助函数共用的重试策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub backoff: Backoff,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration, backoff: Backoff) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+            backoff,
+        }
+    }
+
+    /// 第 `attempt` 次失败后、下一次尝试前应等待的时长（`attempt` 从 1 开始）
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        self.delay_for_with_rng(attempt, &mut rand::rng())
+    }
+
+    /// 与 [`RetryPolicy::delay_for`] 相同，但由调用方注入随机数生成器，便于测试抖动
+    pub fn delay_for_with_rng<R: rand::Rng + ?Sized>(&self, attempt: u32, rng: &mut R) -> Duration {
+        match self.backoff {
+            Backoff::Fixed => self.base_delay,
+            Backoff::Exponential => self.exponential_delay(attempt),
+            Backoff::ExponentialJitter => {
+                let cap = self.exponential_delay(attempt);
+                let cap_nanos = u64::try_from(cap.as_nanos()).unwrap_or(u64::MAX);
+                Duration::from_nanos(rng.random_range(0..=cap_nanos))
+            }
+        }
+    }
+
+    fn exponential_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1);
+        2u32.checked_pow(exponent)
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(100), Backoff::Exponential)
+    }
+}
+
+/// 按 [`RetryPolicy`] 重试异步操作
+///
+/// `operation` 会收到当前尝试次数（从 1 开始）；全部失败时返回最后一次的错误。
+/// `max_attempts` 为 0 时仍会执行一次。
+pub async fn retry_async<T, E, F, Fut>(policy: &RetryPolicy, mut operation: F) -> Result<T, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        match operation(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt >= max_attempts => return Err(error),
+            Err(_) => {
+                tokio::time::sleep(policy.delay_for(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// 为任意 future 加上超时预算，超时后丢弃该 future 并返回 `Elapsed`
+///
+/// 计时基于 tokio 时钟，测试中可用 `tokio::time::pause`/`advance` 驱动。
+pub async fn run_with_timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, tokio::time::error::Elapsed> {
+    tokio::time::timeout(duration, future).await
+}
+
+/// 防抖器：距离上一次实际调用不足 `delay` 时忽略触发，把突发的多次触发合并为一次
+///
+/// 时钟可通过 [`Debouncer::with_clock`] 注入，便于在测试中控制时间。
+pub struct Debouncer<F: FnMut(), C: Fn() -> Instant = fn() -> Instant> {
+    delay: Duration,
+    callback: F,
+    clock: C,
+    last_invoked: Option<Instant>,
+}
+
+impl<F: FnMut()> Debouncer<F> {
+    pub fn new(delay: Duration, callback: F) -> Self {
+        Self::with_clock(delay, callback, Instant::now)
+    }
+}
+
+impl<F: FnMut(), C: Fn() -> Instant> Debouncer<F, C> {
+    pub fn with_clock(delay: Duration, callback: F, clock: C) -> Self {
+        Self {
+            delay,
+            callback,
+            clock,
+            last_invoked: None,
+        }
+    }
+
+    /// 触发一次；实际调用了回调时返回 `true`
+    pub fn trigger(&mut self) -> bool {
+        let now = (self.clock)();
+        let ready = self
+            .last_invoked
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.delay);
+
+        if ready {
+            self.last_invoked = Some(now);
+            (self.callback)();
+        }
+        ready
+    }
+}
+
+/// 按词边界把文本折行，使每行不超过 `width` 个字符
+///
+/// 连续空白会被折叠为单个空格；长度超过 `width` 的单词会被硬切分。
+/// `width` 为 0 时无法折行，按原样返回整段文本作为唯一一行。
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![text.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+
+        if word_len > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let chars: Vec<char> = word.chars().collect();
+            let mut chunks = chars.chunks(width).peekable();
+            while let Some(chunk) = chunks.next() {
+                if chunks.peek().is_some() {
+                    lines.push(chunk.iter().collect());
+                } else {
+                    current = chunk.iter().collect();
+                    current_len = chunk.len();
+                }
+            }
+            continue;
+        }
+
+        if current.is_empty() {
+            current.push_str(word);
+            current_len = word_len;
+        } else if current_len + 1 + word_len <= width {
+            current.push(' ');
+            current.push_str(word);
+            current_len += 1 + word_len;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+            current_len = word_len;
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// 移除文本中的 ANSI CSI 转义序列（如 `\x1b[1;31m`），得到可写入日志文件的纯文本
+///
+/// CSI 序列以 `ESC [` 开头，随后是参数字节（`0x30..=0x3F`）、中间字节（`0x20..=0x2F`），
+/// 以一个终止字节（`0x40..=0x7E`）结束。不完整的序列会被丢弃到文本末尾，其他字符原样保留。
+pub fn strip_ansi(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+/// 把字节数格式化为人类可读的二进制单位（1 KiB = 1024 B）
+///
+/// 不足 1 KiB 时按整数字节显示，如 `1023 B`；其余保留一位小数，如 `1.0 KiB`、`3.4 MiB`。
+pub fn format_bytes(n: u64) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    if n < 1024 {
+        return format!("{n} B");
+    }
+
+    let mut value = n as f64 / 1024.0;
+    let mut unit = 0;
+    // 按保留一位小数后的值判断进位，避免出现 `1024.0 KiB` 这样的结果
+    while (value * 10.0).round() >= 10240.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// 返回迭代器中第一个 `Some`，遇到后立即停止消费
+///
+/// 等价于 `iter.into_iter().flatten().next()`，但名字直接表达了“按优先级取第一个可用值”的意图，
+/// 适合“环境变量 → 配置文件 → 默认值”这类回退链；配合惰性的 `map` 可避免计算后续来源。
+pub fn first_some<T>(iter: impl IntoIterator<Item = Option<T>>) -> Option<T> {
+    iter.into_iter().flatten().next()
+}
+
+/// 类型的内存布局信息（字节数与对齐要求）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutInfo {
+    /// `size_of::<T>()`，已包含为满足对齐而插入的填充
+    pub size: usize,
+    /// `align_of::<T>()`
+    pub align: usize,
+}
+
+/// 报告类型 `T` 的大小与对齐，便于比较字段排列对内存占用的影响
+pub fn describe_layout<T>() -> LayoutInfo {
+    LayoutInfo {
+        size: std::mem::size_of::<T>(),
+        align: std::mem::align_of::<T>(),
+    }
+}
+
+/// 延迟计算且只计算一次的值
+///
+/// 包装 [`OnceCell`]：首次调用 [`get_or_init`](Self::get_or_init) 时执行初始化闭包并缓存结果，
+/// 之后的调用直接返回缓存的引用。适合按需计算、代价较高的派生值；跨线程共享请使用 `OnceLock`。
+#[derive(Debug, Default)]
+pub struct Lazy<T> {
+    cell: OnceCell<T>,
+}
+
+impl<T> Lazy<T> {
+    pub fn new() -> Self {
+        Self {
+            cell: OnceCell::new(),
+        }
+    }
+
+    /// 返回缓存的值；尚未初始化时先调用 `f` 计算
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.cell.get_or_init(f)
+    }
+
+    /// 已初始化时返回缓存的值，否则返回 `None`，不会触发计算
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+}
+
+/// 分段计时器，记录多步流程中每个命名节点距开始的耗时
+///
+/// 每次 [`lap`](Self::lap) 记录的是从 [`start`](Self::start) 起的累计时长，
+/// 因此报告中的时长单调不减；相邻两项相减即为该步骤本身的耗时。
+#[derive(Debug, Clone)]
+pub struct Stopwatch {
+    started: Instant,
+    laps: Vec<(String, Duration)>,
+}
+
+impl Stopwatch {
+    /// 创建并立即开始计时
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            laps: Vec::new(),
+        }
+    }
+
+    /// 以 `label` 记录当前累计耗时
+    pub fn lap(&mut self, label: &str) {
+        self.laps.push((label.to_string(), self.started.elapsed()));
+    }
+
+    /// 按记录顺序返回所有节点及其累计耗时
+    pub fn report(&self) -> Vec<(String, Duration)> {
+        self.laps.clone()
+    }
+}
+
+/// 按最近秩法计算第 `p` 百分位（`p` 取 0–100，越界时截断）
+///
+/// 会对 `samples` 原地排序（NaN 按 [`f64::total_cmp`] 排在最后）；没有样本时返回 `None`。
+/// 与 [`LatencyHistogram::percentile`] 不同，这里保留全部样本，结果是精确的样本值。
+pub fn percentile(samples: &mut [f64], p: f64) -> Option<f64> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    samples.sort_unstable_by(f64::total_cmp);
+    let rank = ((p.clamp(0.0, 100.0) / 100.0) * samples.len() as f64).ceil() as usize;
+    Some(samples[rank.clamp(1, samples.len()) - 1])
+}
+
+/// 按指数间隔分桶的延迟直方图
+///
+/// 第 `i` 个桶覆盖 `[2^i, 2^(i+1))` 纳秒（第 0 个桶包含 0），64 个桶覆盖整个 `u64` 纳秒范围。
+/// 只保存每个桶的计数，内存占用固定；代价是百分位只能精确到所在桶，误差不超过 2 倍。
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    buckets: [u64; 64],
+    count: u64,
+}
+
+impl LatencyHistogram {
+    /// 创建空直方图
+    pub fn new() -> Self {
+        Self {
+            buckets: [0; 64],
+            count: 0,
+        }
+    }
+
+    /// 记录一次耗时，超出 `u64` 纳秒的值计入最后一个桶
+    pub fn record(&mut self, sample: Duration) {
+        let nanos = u64::try_from(sample.as_nanos()).unwrap_or(u64::MAX);
+        let bucket = (u64::BITS - 1).saturating_sub(nanos.leading_zeros()) as usize;
+        self.buckets[bucket] += 1;
+        self.count += 1;
+    }
+
+    /// 已记录的样本数
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 按最近秩法返回第 `p` 百分位（`p` 取 0–100，越界时截断）所在桶的上界
+    ///
+    /// 返回值是该桶内可能出现的最大耗时，即真实百分位的保守估计；没有样本时返回零。
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil() as u64;
+        let rank = rank.clamp(1, self.count);
+        let mut seen = 0;
+        for (bucket, &samples) in self.buckets.iter().enumerate() {
+            seen += samples;
+            if seen >= rank {
+                let upper = 1u64
+                    .checked_shl(bucket as u32 + 1)
+                    .map_or(u64::MAX, |bound| bound - 1);
+                return Duration::from_nanos(upper);
+            }
+        }
+        unreachable!("rank is at most count, so some bucket must reach it")
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// 现代化错误处理最佳实践
 pub fn modern_error_handling_best_practices() {
     println!("⚡ 现代化错误处理最佳实践：");
@@ -46,11 +452,9 @@ pub fn modern_error_handling_best_practices() {
 
     // 使用anyhow处理应用程序级别错误
     fn process_user_data(user_id: &str) -> AppResult<String> {
-        let content = std::fs::read_to_string(format!("data/{}.json", user_id))
-            .context(format!("无法读取用户文件: {}", user_id))?;
-
+        let path = format!("data/{}.json", user_id);
         let parsed: serde_json::Value =
-            serde_json::from_str(&content).context("解析用户数据失败")?;
+            read_json(Path::new(&path)).context(format!("无法加载用户数据: {}", user_id))?;
 
         let name = parsed
             .get("name")
@@ -79,6 +483,12 @@ pub fn modern_error_handling_best_practices() {
         Ok(user) => println!("✅ {}", user),
         Err(e) => println!("❌ {}", e),
     }
+
+    // 文件不存在时，错误链中会包含具体路径
+    match process_user_data("missing_demo_user") {
+        Ok(user) => println!("✅ {}", user),
+        Err(e) => println!("❌ {:#}", e),
+    }
 }
 
 /// 异步编程最佳实践
@@ -112,7 +522,7 @@ pub async fn async_programming_best_practices() {
 
     // 超时处理最佳实践
     async fn operation_with_timeout() -> Result<String, tokio::time::error::Elapsed> {
-        tokio::time::timeout(Duration::from_millis(50), async {
+        run_with_timeout(Duration::from_millis(50), async {
             sleep(Duration::from_millis(100)).await;
             "慢操作完成".to_string()
         })
@@ -168,6 +578,22 @@ pub async fn async_programming_best_practices() {
     }
 
     cancellable_operation().await;
+
+    println!("\n🔁 按 RetryPolicy 重试异步操作:");
+    let policy = RetryPolicy::new(4, Duration::from_millis(20), Backoff::ExponentialJitter);
+    let result: Result<String, String> = retry_async(&policy, |attempt| async move {
+        if attempt < 3 {
+            println!("  第 {} 次尝试失败，稍后重试", attempt);
+            Err(format!("第 {} 次尝试失败", attempt))
+        } else {
+            Ok(format!("第 {} 次尝试成功", attempt))
+        }
+    })
+    .await;
+    match result {
+        Ok(message) => println!("✅ {}", message),
+        Err(error) => println!("❌ 重试耗尽: {}", error),
+    }
 }
 
 /// 资源管理最佳实践
@@ -257,8 +683,6 @@ pub fn resource_management_best_practices() {
 pub fn performance_optimization_best_practices() {
     println!("⚡ 性能优化最佳实践：");
 
-    use std::mem::{align_of, size_of};
-
     // 1. 数据结构优化
     println!("\n1️⃣ 数据结构优化:");
 
@@ -281,17 +705,34 @@ pub fn performance_optimization_best_practices() {
         e: u8,  // 1字节
     }
 
+    let unoptimized = describe_layout::<UnoptimizedStruct>();
+    let optimized = describe_layout::<OptimizedStruct>();
     println!(
         "未优化结构体大小: {} 字节, 对齐: {} 字节",
-        size_of::<UnoptimizedStruct>(),
-        align_of::<UnoptimizedStruct>()
+        unoptimized.size, unoptimized.align
     );
     println!(
         "优化结构体大小: {} 字节, 对齐: {} 字节",
-        size_of::<OptimizedStruct>(),
-        align_of::<OptimizedStruct>()
+        optimized.size, optimized.align
     );
 
+    println!("常见标准库类型布局:");
+    let std_layouts = [
+        ("u8", describe_layout::<u8>()),
+        ("u64", describe_layout::<u64>()),
+        ("(u8, u64)", describe_layout::<(u8, u64)>()),
+        ("&str", describe_layout::<&str>()),
+        ("String", describe_layout::<String>()),
+        ("Vec<u8>", describe_layout::<Vec<u8>>()),
+        ("Option<Box<u8>>", describe_layout::<Option<Box<u8>>>()),
+    ];
+    for (name, layout) in std_layouts {
+        println!(
+            "  {:<16} 大小: {:>2} 字节, 对齐: {} 字节",
+            name, layout.size, layout.align
+        );
+    }
+
     // 2. 零成本抽象
     println!("\n2️⃣ 零成本抽象:");
 
@@ -339,6 +780,69 @@ pub fn performance_optimization_best_practices() {
     }
     let duration = start.elapsed();
     println!("行主序访问和: {}, 耗时: {:?}", sum, duration);
+
+    // 5. 延迟计算
+    println!("\n5️⃣ 延迟计算（只计算一次）:");
+
+    let primes_below_10k: Lazy<usize> = Lazy::new();
+    println!("访问前是否已计算: {}", primes_below_10k.get().is_some());
+    for round in 1..=3 {
+        let start = Instant::now();
+        let count = primes_below_10k.get_or_init(|| {
+            (2..10_000u32)
+                .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+                .count()
+        });
+        println!(
+            "第 {} 次访问: 1 万以内素数 {} 个, 耗时: {:?}",
+            round,
+            count,
+            start.elapsed()
+        );
+    }
+
+    // 6. 分段计时
+    println!("\n6️⃣ 分段计时:");
+
+    let mut stopwatch = Stopwatch::start();
+    let data: Vec<u64> = (1..=100_000).collect();
+    stopwatch.lap("生成数据");
+    let total: u64 = data.iter().sum();
+    stopwatch.lap("求和");
+    let mut sorted = data.clone();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    stopwatch.lap("逆序排序");
+
+    println!("求和结果: {}", total);
+    for (label, elapsed) in stopwatch.report() {
+        println!("  {:<8} 累计耗时: {:?}", label, elapsed);
+    }
+
+    // 7. 延迟分布
+    println!("\n7️⃣ 延迟分布（指数直方图）:");
+
+    let mut histogram = LatencyHistogram::new();
+    let mut samples_us = Vec::new();
+    for size in (1..=200).map(|i| i * 50) {
+        let start = Instant::now();
+        let mut values: Vec<u64> = (0..size).rev().collect();
+        values.sort_unstable();
+        let elapsed = start.elapsed();
+        histogram.record(elapsed);
+        samples_us.push(elapsed.as_secs_f64() * 1e6);
+    }
+    println!(
+        "{} 次排序: p50 ≤ {:?}, p99 ≤ {:?}",
+        histogram.count(),
+        histogram.percentile(50.0),
+        histogram.percentile(99.0)
+    );
+    if let (Some(p50), Some(p99)) = (
+        percentile(&mut samples_us, 50.0),
+        percentile(&mut samples_us, 99.0),
+    ) {
+        println!("精确百分位: p50 = {:.1}µs, p99 = {:.1}µs", p50, p99);
+    }
 }
 
 /// API设计最佳实践
@@ -396,9 +900,20 @@ pub fn api_design_best_practices() {
 
     println!("🛠️ 构建的配置: {:?}", config);
 
+    // 按 环境变量 → 配置文件 → 默认值 的优先级解析端口，命中后不再查询后续来源
+    let file_contents = "port = 8443";
+    let sources: [&dyn Fn() -> Option<u16>; 3] = [
+        &|| std::env::var("RUST_LEARN_PORT").ok()?.parse().ok(),
+        &|| file_contents.strip_prefix("port = ")?.parse().ok(),
+        &|| Some(8080),
+    ];
+    let port = first_some(sources.iter().map(|source| source()));
+    println!("🔎 解析出的端口: {:?}", port);
+
     // 2. 错误类型设计
     #[derive(Debug, thiserror::Error)]
     pub enum ApiError {
+        #[cfg(feature = "http")]
         #[error("网络错误: {source}")]
         Network {
             #[from]
@@ -3,10 +3,12 @@
 //! 这个模块演示了Rust的并发编程特性，包括线程、消息传递和共享状态。
 //! 采用了现代化的Rust 2021/2024最佳实践。
 
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc;
-use std::sync::{Arc, LockResult, RwLock};
+use std::sync::{Arc, Condvar, LockResult, Mutex, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 fn recover_lock<T>(result: LockResult<T>) -> T {
     match result {
@@ -28,6 +30,602 @@ fn join_and_report<T>(handle: thread::JoinHandle<T>, label: &str) -> Option<T> {
     }
 }
 
+/// [`join_timeout`] 未能取回线程结果的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinTimeout {
+    /// 超时前线程没有结束；线程仍在后台继续运行
+    TimedOut,
+    /// 线程在超时前发生了 panic
+    Panicked,
+}
+
+impl std::fmt::Display for JoinTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimedOut => write!(f, "等待线程结束超时"),
+            Self::Panicked => write!(f, "线程发生 panic"),
+        }
+    }
+}
+
+impl std::error::Error for JoinTimeout {}
+
+/// 最多等待 `timeout` 取回线程结果，线程卡住时不会让调用方一起阻塞
+///
+/// 标准库的 `join` 没有超时版本，这里由一个辅助线程代为 `join`，完成后通过通道通知调用方，
+/// 调用方用 `recv_timeout` 等待。超时后辅助线程与原线程都会被分离，仍在后台运行直到结束。
+pub fn join_timeout<T: Send + 'static>(
+    handle: thread::JoinHandle<T>,
+    timeout: Duration,
+) -> Result<T, JoinTimeout> {
+    if handle.is_finished() {
+        return handle.join().map_err(|_| JoinTimeout::Panicked);
+    }
+
+    let (done_tx, done_rx) = mpsc::channel();
+    thread::spawn(move || {
+        // 调用方已超时离开时接收端已关闭，发送失败可以忽略
+        let _ = done_tx.send(handle.join());
+    });
+
+    match done_rx.recv_timeout(timeout) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(_)) => Err(JoinTimeout::Panicked),
+        Err(_) => Err(JoinTimeout::TimedOut),
+    }
+}
+
+/// 令牌桶限流器
+///
+/// 桶中最多保存 `capacity` 个令牌，并按墙钟时间以 `refill_per_sec` 的速率补充。
+/// 内部状态由 `Mutex` 保护，可以在线程间共享（配合 `Arc`）。
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<TokenBucketState>,
+}
+
+struct TokenBucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// 创建一个装满令牌的桶
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity: f64::from(capacity),
+            refill_per_sec: refill_per_sec.max(0.0),
+            state: Mutex::new(TokenBucketState {
+                tokens: f64::from(capacity),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// 尝试一次性获取 `n` 个令牌，令牌不足时返回 `false` 且不消耗任何令牌
+    pub fn try_acquire(&self, n: u32) -> bool {
+        let mut state = recover_lock(self.state.lock());
+        self.refill(&mut state);
+
+        let requested = f64::from(n);
+        if state.tokens >= requested {
+            state.tokens -= requested;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// 当前可用令牌数（向下取整）
+    pub fn available(&self) -> u32 {
+        let mut state = recover_lock(self.state.lock());
+        self.refill(&mut state);
+        state.tokens as u32
+    }
+
+    fn refill(&self, state: &mut TokenBucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        state.last_refill = now;
+    }
+}
+
+/// 使用作用域线程并行映射，并保持输出顺序与输入一致
+///
+/// 输入按块切分给固定数量的线程（不超过可用并行度与元素个数），
+/// 每个线程按原顺序处理自己的块，最后按块顺序拼接结果。
+/// 借助 `std::thread::scope`，闭包可以直接借用外部数据而无需 `Arc`。
+pub fn par_map<T: Send, R: Send>(items: Vec<T>, f: impl Fn(T) -> R + Sync) -> Vec<R> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(items.len());
+    let chunk_size = items.len().div_ceil(workers);
+
+    let mut chunks: Vec<Vec<T>> = Vec::with_capacity(workers);
+    let mut remaining = items.into_iter();
+    loop {
+        let chunk: Vec<T> = remaining.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || chunk.into_iter().map(f).collect::<Vec<R>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+            })
+            .collect()
+    })
+}
+
+/// 基于 `Mutex` + `Condvar` 的计数信号量
+struct Semaphore {
+    permits: Mutex<usize>,
+    available: Condvar,
+}
+
+impl Semaphore {
+    fn new(permits: usize) -> Self {
+        Self {
+            permits: Mutex::new(permits),
+            available: Condvar::new(),
+        }
+    }
+
+    /// 阻塞直到拿到一个许可；许可随返回的守卫析构而归还
+    fn acquire(&self) -> SemaphorePermit<'_> {
+        let mut permits = recover_lock(self.permits.lock());
+        while *permits == 0 {
+            permits = recover_lock(self.available.wait(permits));
+        }
+        *permits -= 1;
+        SemaphorePermit { semaphore: self }
+    }
+}
+
+struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        *recover_lock(self.semaphore.permits.lock()) += 1;
+        self.semaphore.available.notify_one();
+    }
+}
+
+/// 并行映射，但同一时刻最多有 `max_concurrency` 个 `f` 在执行
+///
+/// 每个元素由一个作用域线程处理，线程启动前先从计数信号量获取许可，
+/// 因此无需线程池也能限制并发度；结果顺序与输入一致。
+/// `max_concurrency` 为 0 时按 1 处理。
+pub fn bounded_par_map<T: Send, R: Send>(
+    items: Vec<T>,
+    max_concurrency: usize,
+    f: impl Fn(T) -> R + Sync,
+) -> Vec<R> {
+    let semaphore = Semaphore::new(max_concurrency.max(1));
+    let f = &f;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .into_iter()
+            .map(|item| {
+                let permit = semaphore.acquire();
+                scope.spawn(move || {
+                    let _permit = permit;
+                    f(item)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+            })
+            .collect()
+    })
+}
+
+/// 使用 `Barrier` 分阶段推进的并行计算
+///
+/// 每个阶段中，第 `t` 个线程贡献 `上一阶段总和 + (t + 1)`，累加到该阶段的
+/// `AtomicU64` 中，然后在屏障处等待所有线程。因为读取上一阶段总和发生在
+/// 屏障之后，结果是确定的：返回值为各阶段的总和。
+pub fn phased_compute(threads: usize, phases: usize) -> Vec<u64> {
+    use std::sync::Barrier;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    let totals: Vec<AtomicU64> = (0..phases).map(|_| AtomicU64::new(0)).collect();
+    if threads == 0 {
+        return vec![0; phases];
+    }
+
+    let barrier = Barrier::new(threads);
+    thread::scope(|scope| {
+        for t in 0..threads {
+            let totals = &totals;
+            let barrier = &barrier;
+            scope.spawn(move || {
+                for phase in 0..phases {
+                    let previous = match phase {
+                        0 => 0,
+                        _ => totals[phase - 1].load(Ordering::SeqCst),
+                    };
+                    totals[phase].fetch_add(previous + t as u64 + 1, Ordering::SeqCst);
+                    barrier.wait();
+                }
+            });
+        }
+    });
+
+    totals.into_iter().map(|total| total.into_inner()).collect()
+}
+
+/// 发送给计数器 Actor 的消息
+#[derive(Debug)]
+pub enum Message {
+    /// 计数加一
+    Increment,
+    /// 通过附带的一次性通道返回当前值
+    Get(mpsc::Sender<i64>),
+    /// 停止 Actor 线程
+    Stop,
+}
+
+/// 基于 `mpsc` 的计数器 Actor
+///
+/// 状态只由 Actor 线程拥有，外部只能通过 [`ActorHandle`] 发送消息访问，
+/// 因此不需要任何锁。
+pub struct Actor {
+    state: i64,
+    receiver: mpsc::Receiver<Message>,
+}
+
+impl Actor {
+    /// 启动 Actor 线程，返回可克隆的句柄和线程句柄（线程结束时返回最终状态）
+    pub fn spawn(initial: i64) -> (ActorHandle, thread::JoinHandle<i64>) {
+        let (sender, receiver) = mpsc::channel();
+        let actor = Actor {
+            state: initial,
+            receiver,
+        };
+        let join_handle = thread::spawn(move || actor.run());
+        (ActorHandle { sender }, join_handle)
+    }
+
+    fn run(mut self) -> i64 {
+        while let Ok(message) = self.receiver.recv() {
+            match message {
+                Message::Increment => self.state += 1,
+                Message::Get(reply) => {
+                    let _ = reply.send(self.state);
+                }
+                Message::Stop => break,
+            }
+        }
+        self.state
+    }
+}
+
+/// 计数器 Actor 的句柄，可在线程间克隆共享
+#[derive(Debug, Clone)]
+pub struct ActorHandle {
+    sender: mpsc::Sender<Message>,
+}
+
+impl ActorHandle {
+    /// 请求计数加一；Actor 已停止时返回 `false`
+    pub fn increment(&self) -> bool {
+        self.sender.send(Message::Increment).is_ok()
+    }
+
+    /// 查询当前值
+    ///
+    /// # Panics
+    ///
+    /// Actor 已停止（收到 `Stop` 或线程已退出）时 panic；
+    /// 需要处理停止状态时先用 [`ActorHandle::increment`] 的返回值或线程句柄判断。
+    pub fn get(&self) -> i64 {
+        let (reply_sender, reply_receiver) = mpsc::channel();
+        self.sender
+            .send(Message::Get(reply_sender))
+            .expect("actor has stopped");
+        reply_receiver.recv().expect("actor has stopped")
+    }
+
+    /// 请求 Actor 停止
+    pub fn stop(&self) {
+        let _ = self.sender.send(Message::Stop);
+    }
+}
+
+/// 通过通道发起一次同步“请求/响应”调用
+///
+/// 为本次请求新建一个一次性回复通道，随请求一起发送给服务线程，然后阻塞等待回复。
+/// 服务线程已退出，或丢弃回复发送端而没有回复时，返回 `RecvError`。
+pub fn ask<Req, Resp>(
+    tx: &mpsc::Sender<(Req, mpsc::Sender<Resp>)>,
+    req: Req,
+) -> Result<Resp, mpsc::RecvError> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    // 发送失败时回复发送端随消息一起被丢弃，下面的 recv 会直接返回错误
+    let _ = tx.send((req, reply_tx));
+    reply_rx.recv()
+}
+
+/// 基于 `mpsc` 的线程间事件总线
+///
+/// 每次 `subscribe` 都会创建一个新的通道；`publish` 将消息克隆后发送给所有订阅者，
+/// 发送失败说明对应的接收端已被丢弃，此时会把该订阅者从列表中移除。
+pub struct EventBus<T: Clone + Send> {
+    subscribers: Mutex<Vec<mpsc::Sender<T>>>,
+}
+
+impl<T: Clone + Send> EventBus<T> {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 订阅事件，返回新的接收端
+    pub fn subscribe(&self) -> mpsc::Receiver<T> {
+        let (sender, receiver) = mpsc::channel();
+        recover_lock(self.subscribers.lock()).push(sender);
+        receiver
+    }
+
+    /// 向所有仍然存活的订阅者广播事件，并清理已关闭的订阅者
+    pub fn publish(&self, event: T) {
+        recover_lock(self.subscribers.lock())
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+
+    /// 当前记录的订阅者数量
+    pub fn subscriber_count(&self) -> usize {
+        recover_lock(self.subscribers.lock()).len()
+    }
+}
+
+impl<T: Clone + Send> Default for EventBus<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 协作式取消令牌
+///
+/// 内部共享一个 `Arc<AtomicBool>`：任一克隆或 [`child`](Self::child) 调用 `cancel` 后，
+/// 所有共享该标志的令牌都会观察到取消；工作线程需自行在循环中检查 `is_cancelled`。
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 发出取消信号，重复调用无副作用
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// 是否已经取消
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// 派生一个共享同一取消标志的令牌，交给子任务使用
+    pub fn child(&self) -> Self {
+        self.clone()
+    }
+
+    /// 返回一个守卫：守卫析构时若令牌已被取消，则执行 `on_cancel`
+    ///
+    /// 适合放在工作线程开头，用于在因取消而提前退出时做清理或记录。
+    pub fn cancelled_guard<F: FnOnce()>(&self, on_cancel: F) -> CancelledGuard<F> {
+        CancelledGuard {
+            token: self.child(),
+            on_cancel: Some(on_cancel),
+        }
+    }
+}
+
+/// [`CancellationToken::cancelled_guard`] 返回的守卫
+pub struct CancelledGuard<F: FnOnce()> {
+    token: CancellationToken,
+    on_cancel: Option<F>,
+}
+
+impl<F: FnOnce()> Drop for CancelledGuard<F> {
+    fn drop(&mut self) {
+        if !self.token.is_cancelled() {
+            return;
+        }
+        if let Some(on_cancel) = self.on_cancel.take() {
+            on_cancel();
+        }
+    }
+}
+
+/// 线程安全的计数器
+///
+/// 内部共享一个 `Arc<AtomicU64>`，克隆得到的是同一个计数器的另一个句柄；
+/// 只做计数、不用于同步其他数据，因此使用 `Relaxed` 顺序即可。
+#[derive(Debug, Clone, Default)]
+pub struct Counter {
+    value: Arc<AtomicU64>,
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加一
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// 加上 `n`
+    pub fn add(&self, n: u64) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// 当前计数
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// 按需创建资源的有界对象池
+///
+/// 最多创建 `max` 个实例：空闲列表为空且未达上限时调用工厂新建，
+/// 达到上限后 `get` 阻塞，直到有守卫被释放并把实例归还到池中。
+pub struct LazyPool<T> {
+    state: Mutex<LazyPoolState<T>>,
+    available: Condvar,
+    max: usize,
+    factory: Box<dyn Fn() -> T + Send + Sync>,
+}
+
+struct LazyPoolState<T> {
+    free: Vec<T>,
+    created: usize,
+}
+
+impl<T> LazyPool<T> {
+    /// 创建空池，实例在首次借出时才通过 `factory` 构造
+    ///
+    /// # Panics
+    ///
+    /// `max` 为 0 时 panic：这样的池永远无法借出实例，`get` 会一直阻塞。
+    pub fn new(max: usize, factory: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        assert!(max > 0, "LazyPool capacity must be at least 1");
+        Self {
+            state: Mutex::new(LazyPoolState {
+                free: Vec::new(),
+                created: 0,
+            }),
+            available: Condvar::new(),
+            max,
+            factory: Box::new(factory),
+        }
+    }
+
+    /// 借出一个实例；池已满且没有空闲实例时阻塞等待归还
+    pub fn get(&self) -> PoolGuard<'_, T> {
+        let mut state = recover_lock(self.state.lock());
+        loop {
+            if let Some(item) = state.free.pop() {
+                return PoolGuard::new(self, item);
+            }
+            if state.created < self.max {
+                state.created += 1;
+                // 工厂可能较慢（如建立连接），在锁外调用以免阻塞归还
+                drop(state);
+                let slot = CreationSlot { pool: self };
+                let item = (self.factory)();
+                std::mem::forget(slot);
+                return PoolGuard::new(self, item);
+            }
+            state = recover_lock(self.available.wait(state));
+        }
+    }
+
+    /// 已经创建的实例数量
+    pub fn created(&self) -> usize {
+        recover_lock(self.state.lock()).created
+    }
+
+    /// 当前空闲、可立即借出的实例数量
+    pub fn idle(&self) -> usize {
+        recover_lock(self.state.lock()).free.len()
+    }
+
+    fn give_back(&self, item: T) {
+        recover_lock(self.state.lock()).free.push(item);
+        self.available.notify_one();
+    }
+}
+
+/// 为新实例预留的名额；工厂 panic 时在展开过程中释放名额并唤醒一个等待者
+struct CreationSlot<'a, T> {
+    pool: &'a LazyPool<T>,
+}
+
+impl<T> Drop for CreationSlot<'_, T> {
+    fn drop(&mut self) {
+        recover_lock(self.pool.state.lock()).created -= 1;
+        self.pool.available.notify_one();
+    }
+}
+
+/// `LazyPool` 借出的实例，析构时自动归还到池中
+pub struct PoolGuard<'a, T> {
+    pool: &'a LazyPool<T>,
+    item: Option<T>,
+}
+
+impl<'a, T> PoolGuard<'a, T> {
+    fn new(pool: &'a LazyPool<T>, item: T) -> Self {
+        Self {
+            pool,
+            item: Some(item),
+        }
+    }
+}
+
+impl<T> Deref for PoolGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.item
+            .as_ref()
+            .expect("pool guard holds an item until dropped")
+    }
+}
+
+impl<T> DerefMut for PoolGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.item
+            .as_mut()
+            .expect("pool guard holds an item until dropped")
+    }
+}
+
+impl<T> Drop for PoolGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Some(item) = self.item.take() {
+            self.pool.give_back(item);
+        }
+    }
+}
+
 /// 现代化同步线程示例
 pub fn modern_sync_threads() {
     println!("🧵 现代化同步线程：");
@@ -40,8 +638,15 @@ pub fn modern_sync_threads() {
         "线程完成".to_string()
     });
 
-    if let Some(msg) = join_and_report(handle, "同步线程示例") {
-        println!("✅ {}", msg);
+    match join_timeout(handle, Duration::from_secs(2)) {
+        Ok(msg) => println!("✅ {}", msg),
+        Err(error) => eprintln!("❌ 同步线程示例: {}", error),
+    }
+
+    // 卡住的线程不会拖住主线程：超时后直接返回，线程在后台自行结束
+    let slow = thread::spawn(|| thread::sleep(Duration::from_millis(300)));
+    if let Err(error) = join_timeout(slow, Duration::from_millis(50)) {
+        println!("⏰ 慢线程: {}，主线程不再等待", error);
     }
 
     println!("主线程继续执行");
@@ -81,6 +686,65 @@ pub fn modern_message_passing() {
 
     let _ = join_and_report(producer, "消息生产者");
     let _ = join_and_report(consumer, "消息消费者");
+
+    // Actor 模型：状态归单个线程所有，其他线程只发送消息
+    let (actor, actor_thread) = Actor::spawn(0);
+    let senders: Vec<_> = (0..3)
+        .map(|_| {
+            let actor = actor.clone();
+            thread::spawn(move || {
+                for _ in 0..5 {
+                    actor.increment();
+                }
+            })
+        })
+        .collect();
+    for (index, sender) in senders.into_iter().enumerate() {
+        let _ = join_and_report(sender, &format!("Actor 消息发送线程 {}", index));
+    }
+
+    println!("🎭 Actor 当前计数: {}", actor.get());
+    actor.stop();
+    if let Some(final_state) = join_and_report(actor_thread, "计数器 Actor") {
+        println!("🎭 Actor 停止时的最终状态: {}", final_state);
+    }
+
+    // 请求/响应：服务线程循环处理请求，每个请求自带回复通道
+    let (request_tx, request_rx) = mpsc::channel::<(String, mpsc::Sender<usize>)>();
+    let server = thread::spawn(move || {
+        for (word, reply) in request_rx {
+            let _ = reply.send(word.chars().count());
+        }
+    });
+    for word in ["hello", "通道", "rpc"] {
+        match ask(&request_tx, word.to_string()) {
+            Ok(len) => println!("📞 ask({:?}) -> {} 个字符", word, len),
+            Err(error) => eprintln!("⚠️ 请求 {:?} 失败: {}", word, error),
+        }
+    }
+    drop(request_tx);
+    let _ = join_and_report(server, "请求/响应服务线程");
+
+    // 事件总线：一次发布，多个订阅者各自收到一份克隆
+    let bus = Arc::new(EventBus::new());
+    let listeners: Vec<_> = (0..2)
+        .map(|id| {
+            let receiver = bus.subscribe();
+            thread::spawn(move || {
+                for event in receiver {
+                    println!("📡 订阅者{} 收到事件: {}", id, event);
+                }
+            })
+        })
+        .collect();
+
+    bus.publish("部署开始".to_string());
+    bus.publish("部署完成".to_string());
+    drop(bus);
+
+    for (id, listener) in listeners.into_iter().enumerate() {
+        let _ = join_and_report(listener, &format!("事件订阅者 {}", id));
+    }
 }
 
 /// 现代化共享状态管理
@@ -147,6 +811,12 @@ pub fn modern_synchronization() {
         let _ = join_and_report(handle, &format!("同步机制线程 {}", index));
     }
 
+    // 分阶段计算：每个阶段都依赖上一阶段完整的结果
+    println!(
+        "🧮 分阶段计算（4 线程 × 3 阶段）: {:?}",
+        phased_compute(4, 3)
+    );
+
     // 使用Once配合原子类型表达一次性初始化，避免static mut的误导
     static INITIALIZED: AtomicBool = AtomicBool::new(false);
     static INIT_ONCE: Once = Once::new();
@@ -234,8 +904,9 @@ pub fn modern_work_pool() {
     use std::sync::{Arc, Mutex, mpsc};
 
     // 创建一个共享的工作队列
-    let work_queue = Arc::new(Mutex::new(vec![1, 2, 3, 4, 5, 6]));
+    let work_queue = Arc::new(Mutex::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]));
     let (result_sender, result_receiver) = mpsc::channel();
+    let shutdown = CancellationToken::new();
 
     // 创建工作线程池
     let mut workers = Vec::new();
@@ -243,28 +914,34 @@ pub fn modern_work_pool() {
     for id in 0..3 {
         let work_queue = Arc::clone(&work_queue);
         let result_sender = result_sender.clone();
-
-        let worker = thread::spawn(move || loop {
-            let work = {
-                let mut queue = recover_lock(work_queue.lock());
-                queue.pop()
-            };
-
-            match work {
-                Some(work_item) => {
-                    println!("👷 工作者 {} 处理任务: {}", id, work_item);
-                    thread::sleep(Duration::from_millis(100));
-
-                    let result = format!("工作者 {} 完成任务: {}", id, work_item);
-                    if result_sender.send(result).is_err() {
-                        eprintln!("⚠️ 结果接收端已关闭，工作者 {} 提前结束", id);
+        let shutdown = shutdown.child();
+
+        let worker = thread::spawn(move || {
+            let _guard =
+                shutdown.cancelled_guard(|| println!("🛑 工作者 {} 收到关闭信号后退出", id));
+
+            while !shutdown.is_cancelled() {
+                let work = {
+                    let mut queue = recover_lock(work_queue.lock());
+                    queue.pop()
+                };
+
+                match work {
+                    Some(work_item) => {
+                        println!("👷 工作者 {} 处理任务: {}", id, work_item);
+                        thread::sleep(Duration::from_millis(100));
+
+                        let result = format!("工作者 {} 完成任务: {}", id, work_item);
+                        if result_sender.send(result).is_err() {
+                            eprintln!("⚠️ 结果接收端已关闭，工作者 {} 提前结束", id);
+                            break;
+                        }
+                    }
+                    None => {
+                        println!("👷 工作者 {} 退出，队列为空", id);
                         break;
                     }
                 }
-                None => {
-                    println!("👷 工作者 {} 退出，队列为空", id);
-                    break;
-                }
             }
         });
         workers.push(worker);
@@ -272,59 +949,42 @@ pub fn modern_work_pool() {
 
     drop(result_sender);
 
+    // 收到足够结果后发出关闭信号，仍在处理中的任务会正常完成
+    let mut received = 0;
     while let Ok(result) = result_receiver.recv() {
         println!("📊 {}", result);
+        received += 1;
+        if received == 6 && !shutdown.is_cancelled() {
+            println!("📣 已收到 {} 个结果，通知工作池关闭", received);
+            shutdown.cancel();
+        }
     }
 
     for (index, worker) in workers.into_iter().enumerate() {
         let _ = join_and_report(worker, &format!("工作池线程 {}", index));
     }
+
+    let remaining = recover_lock(work_queue.lock()).len();
+    println!("📦 关闭后队列中剩余未处理任务: {}", remaining);
 }
 
 /// 演示真实Web服务器并发处理
 pub fn web_server_concurrent_handling() {
     println!("🌐 Web服务器并发请求处理：");
 
-    use std::sync::{Arc, Mutex};
+    use std::sync::Arc;
 
-    // 模拟请求统计
-    #[derive(Clone)]
+    // 模拟请求统计：克隆后各字段仍指向同一组计数器
+    #[derive(Clone, Default)]
     struct RequestStats {
-        total: Arc<Mutex<u32>>,
-        completed: Arc<Mutex<u32>>,
-        failed: Arc<Mutex<u32>>,
-    }
-
-    impl RequestStats {
-        fn new() -> Self {
-            Self {
-                total: Arc::new(Mutex::new(0)),
-                completed: Arc::new(Mutex::new(0)),
-                failed: Arc::new(Mutex::new(0)),
-            }
-        }
-
-        fn increment(counter: &Arc<Mutex<u32>>) {
-            let mut value = recover_lock(counter.lock());
-            *value += 1;
-        }
-
-        fn total(&self) -> u32 {
-            *recover_lock(self.total.lock())
-        }
-
-        fn completed(&self) -> u32 {
-            *recover_lock(self.completed.lock())
-        }
-
-        fn failed(&self) -> u32 {
-            *recover_lock(self.failed.lock())
-        }
+        total: Counter,
+        completed: Counter,
+        failed: Counter,
     }
 
     // 模拟HTTP请求处理
     fn handle_request(id: u32, stats: &RequestStats) {
-        RequestStats::increment(&stats.total);
+        stats.total.inc();
 
         let processing_time = match id % 4 {
             0 => 50,  // 快速请求
@@ -336,15 +996,15 @@ pub fn web_server_concurrent_handling() {
         thread::sleep(Duration::from_millis(processing_time));
 
         if id % 4 == 3 {
-            RequestStats::increment(&stats.failed);
+            stats.failed.inc();
             println!("❌ 请求 {} 失败", id);
         } else {
-            RequestStats::increment(&stats.completed);
+            stats.completed.inc();
             println!("✅ 请求 {} 成功", id);
         }
     }
 
-    let stats = RequestStats::new();
+    let stats = RequestStats::default();
     let mut handles = vec![];
 
     // 模拟并发请求
@@ -363,9 +1023,43 @@ pub fn web_server_concurrent_handling() {
     }
 
     println!("📊 请求统计:");
-    println!("   总请求: {}", stats.total());
-    println!("   成功: {}", stats.completed());
-    println!("   失败: {}", stats.failed());
+    println!("   总请求: {}", stats.total.get());
+    println!("   成功: {}", stats.completed.get());
+    println!("   失败: {}", stats.failed.get());
+
+    // 使用作用域线程并行计算每个请求的模拟耗时，结果顺序与请求顺序一致
+    let latencies = par_map((0..10u32).collect(), |id| match id % 4 {
+        0 => 50,
+        1 => 200,
+        2 => 500,
+        _ => 100,
+    });
+    println!("⏱️ 按请求顺序的模拟耗时(ms): {:?}", latencies);
+
+    // 用信号量限制同时访问下游服务的请求数，结果仍按请求顺序返回
+    let responses = bounded_par_map((0..6u32).collect(), 2, |id| {
+        thread::sleep(Duration::from_millis(20));
+        format!("响应#{}", id)
+    });
+    println!("🎛️ 最多 2 个并发下游调用: {:?}", responses);
+
+    // 使用令牌桶对突发请求限流
+    let limiter = Arc::new(TokenBucket::new(3, 10.0));
+    let burst: Vec<_> = (0..5)
+        .map(|i| {
+            let limiter = Arc::clone(&limiter);
+            thread::spawn(move || (i, limiter.try_acquire(1)))
+        })
+        .collect();
+
+    println!("🚦 令牌桶限流（容量 3，每秒补充 10 个）:");
+    for (index, handle) in burst.into_iter().enumerate() {
+        let label = format!("限流请求线程 {}", index);
+        if let Some((id, accepted)) = join_and_report(handle, &label) {
+            let verdict = if accepted { "放行" } else { "被限流" };
+            println!("   请求 {} {}", id, verdict);
+        }
+    }
 }
 
 /// 演示数据库连接池
@@ -484,6 +1178,27 @@ pub fn database_connection_pool() {
     }
 
     println!("📊 连接池查询完成，所有查询线程都已收尾");
+
+    // 懒加载版本：连接在首次借出时才创建，数量不超过上限
+    let next_id = std::sync::atomic::AtomicU32::new(0);
+    let lazy_pool = LazyPool::new(2, move || {
+        DbConnection::new(next_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst))
+    });
+    thread::scope(|scope| {
+        for i in 0..4 {
+            let lazy_pool = &lazy_pool;
+            scope.spawn(move || {
+                let mut conn = lazy_pool.get();
+                println!("懒加载池: 线程 {} 使用连接 {}", i, conn.id);
+                conn.execute_query("SELECT 1");
+            });
+        }
+    });
+    println!(
+        "📊 懒加载池共创建 {} 个连接，空闲 {} 个",
+        lazy_pool.created(),
+        lazy_pool.idle()
+    );
 }
 
 /// 运行所有并发编程示例
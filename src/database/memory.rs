@@ -0,0 +1,186 @@
+//! 不依赖 SQLx 的内存数据库，无论是否启用 `database` feature 都会编译。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use super::User;
+
+/// 内存数据库错误类型，不依赖 SQLx。
+#[derive(Debug, Error)]
+pub enum MemoryDbError {
+    #[error("数据校验失败: {0}")]
+    Validation(String),
+}
+
+/// 不依赖 SQLx 的内存数据库
+///
+/// 提供与 SQLx 版 `DatabaseManager` 相同的用户 CRUD 异步接口，数据保存在
+/// `RwLock<HashMap<i64, User>>` 中，无需任何数据库连接即可离线演示和测试。
+/// 用户名与邮箱的唯一性约束与 SQLite 表结构保持一致。
+#[derive(Debug)]
+pub struct MemoryDb {
+    users: RwLock<HashMap<i64, User>>,
+    next_id: AtomicI64,
+}
+
+impl Default for MemoryDb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryDb {
+    /// 创建空的内存数据库，ID 从 1 开始自增
+    pub fn new() -> Self {
+        Self {
+            users: RwLock::new(HashMap::new()),
+            next_id: AtomicI64::new(1),
+        }
+    }
+
+    /// 创建用户，用户名或邮箱重复时返回校验错误
+    pub async fn create_user(&self, username: &str, email: &str) -> Result<User, MemoryDbError> {
+        let mut users = self.users.write().await;
+        Self::check_unique(&users, username, email)?;
+
+        let user = User {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            username: username.to_string(),
+            email: email.to_string(),
+            created_at: Some(chrono::Utc::now().naive_utc()),
+            is_active: true,
+        };
+        users.insert(user.id, user.clone());
+        Ok(user)
+    }
+
+    /// 事务式批量创建用户
+    ///
+    /// 先写入暂存区并逐个校验唯一性（包括批次内部），全部成功才一次性提交；
+    /// 任意一条失败都会丢弃整个批次，已有数据与 ID 计数器保持不变。
+    pub async fn bulk_create_atomic(
+        &self,
+        users: &[(String, String)],
+    ) -> Result<Vec<User>, MemoryDbError> {
+        let mut live = self.users.write().await;
+        let first_id = self.next_id.load(Ordering::Relaxed);
+        let mut staging = live.clone();
+        let mut created = Vec::with_capacity(users.len());
+
+        for (offset, (username, email)) in (0..).zip(users) {
+            Self::check_unique(&staging, username, email)?;
+
+            let user = User {
+                id: first_id + offset,
+                username: username.clone(),
+                email: email.clone(),
+                created_at: Some(chrono::Utc::now().naive_utc()),
+                is_active: true,
+            };
+            staging.insert(user.id, user.clone());
+            created.push(user);
+        }
+
+        *live = staging;
+        self.next_id
+            .store(first_id + created.len() as i64, Ordering::Relaxed);
+        Ok(created)
+    }
+
+    /// 根据 ID 获取用户
+    pub async fn get_user(&self, id: i64) -> Result<Option<User>, MemoryDbError> {
+        Ok(self.users.read().await.get(&id).cloned())
+    }
+
+    /// 获取所有用户，排序与 SQL 版本一致：创建时间倒序，其次 ID 倒序
+    pub async fn get_all_users(&self) -> Result<Vec<User>, MemoryDbError> {
+        let mut users: Vec<User> = self.users.read().await.values().cloned().collect();
+        users.sort_by_key(|user| std::cmp::Reverse((user.created_at, user.id)));
+        Ok(users)
+    }
+
+    /// 删除用户，返回是否确实删除了记录
+    pub async fn delete_user(&self, id: i64) -> Result<bool, MemoryDbError> {
+        Ok(self.users.write().await.remove(&id).is_some())
+    }
+
+    /// 设置用户的激活状态，返回用户是否存在
+    pub async fn set_active(&self, id: i64, is_active: bool) -> Result<bool, MemoryDbError> {
+        match self.users.write().await.get_mut(&id) {
+            Some(user) => {
+                user.is_active = is_active;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 按 ID 升序分页查询用户，`active_only` 为真时只返回激活用户
+    pub async fn list_users(&self, offset: usize, limit: usize, active_only: bool) -> Vec<User> {
+        let mut users: Vec<User> = self
+            .users
+            .read()
+            .await
+            .values()
+            .filter(|user| !active_only || user.is_active)
+            .cloned()
+            .collect();
+        users.sort_by_key(|user| user.id);
+        users.into_iter().skip(offset).take(limit).collect()
+    }
+
+    /// 用户总数
+    pub async fn count_users(&self) -> usize {
+        self.users.read().await.len()
+    }
+
+    fn check_unique(
+        users: &HashMap<i64, User>,
+        username: &str,
+        email: &str,
+    ) -> Result<(), MemoryDbError> {
+        if users.values().any(|user| user.username == username) {
+            return Err(MemoryDbError::Validation(format!(
+                "用户名已存在: {}",
+                username
+            )));
+        }
+
+        if users.values().any(|user| user.email == email) {
+            return Err(MemoryDbError::Validation(format!(
+                "邮箱已被注册: {}",
+                email
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// 内存数据库演示
+pub async fn demonstrate_memory_db() {
+    println!("🧠 内存数据库演示（无需 SQLx 连接）:");
+
+    let db = MemoryDb::new();
+    for (username, email) in [
+        ("张三", "zhangsan@example.com"),
+        ("李四", "lisi@example.com"),
+    ] {
+        match db.create_user(username, email).await {
+            Ok(user) => println!("  ✅ 创建用户: {} (ID: {})", user.username, user.id),
+            Err(error) => println!("  ❌ 创建用户失败: {}", error),
+        }
+    }
+
+    if let Err(error) = db.create_user("张三", "another@example.com").await {
+        println!("  ❌ 重复用户名被拒绝: {}", error);
+    }
+
+    println!("  📋 当前用户数: {}", db.count_users().await);
+    for user in db.list_users(0, 10, true).await {
+        println!("  👤 {} (ID: {})", user.username, user.id);
+    }
+}
@@ -5,6 +5,69 @@
 //!
 //! 因此它虽然仍可通过 [`rust_learn`](src/lib.rs) 访问以兼容现有调用与测试，
 //! 但在库侧仅作为弱化展示的说明性模块保留，不应被理解为与其它可验证、可复用源码等价的稳定公开能力。
+//! 例外是 [`detect_features`] 与 [`render_tree`]：前者在运行时报告当前构建的编译配置，
+//! 后者把依赖层级渲染成 `cargo tree` 风格的文本，两者都可被测试验证。
+
+/// 当前构建的编译配置快照。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureReport {
+    /// 是否启用了 `debug_assertions`（通常对应 debug 构建）
+    pub debug_assertions: bool,
+    /// 目标操作系统，与 `std::env::consts::OS` 一致
+    pub os: &'static str,
+    /// 目标 CPU 架构，与 `std::env::consts::ARCH` 一致
+    pub arch: &'static str,
+    /// 目标平台家族，如 `unix`、`windows`
+    pub family: &'static str,
+    /// 指针宽度（位）
+    pub pointer_width: u32,
+}
+
+/// 在运行时读取编译期确定的目标平台与构建配置。
+pub fn detect_features() -> FeatureReport {
+    FeatureReport {
+        debug_assertions: cfg!(debug_assertions),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        family: std::env::consts::FAMILY,
+        pointer_width: usize::BITS,
+    }
+}
+
+/// 将简单的邻接描述渲染为 `cargo tree` 风格的 ASCII 树。
+///
+/// `children` 中每一项为 `(节点, 子节点列表)`，未出现的节点视为叶子；
+/// 每行以换行结尾。出现环时，重复的祖先节点只显示名称而不再展开。
+pub fn render_tree(root: &str, children: &[(&str, Vec<&str>)]) -> String {
+    let mut output = format!("{root}\n");
+    let mut ancestors = vec![root];
+    render_children(root, children, "", &mut ancestors, &mut output);
+    output
+}
+
+fn render_children<'a>(
+    node: &str,
+    children: &[(&'a str, Vec<&'a str>)],
+    prefix: &str,
+    ancestors: &mut Vec<&'a str>,
+    output: &mut String,
+) {
+    let Some((_, kids)) = children.iter().find(|(name, _)| *name == node) else {
+        return;
+    };
+
+    for (index, &child) in kids.iter().enumerate() {
+        let is_last = index + 1 == kids.len();
+        let (branch, indent) = if is_last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        output.push_str(&format!("{prefix}{branch}{child}\n"));
+
+        if !ancestors.contains(&child) {
+            ancestors.push(child);
+            render_children(child, children, &format!("{prefix}{indent}"), ancestors, output);
+            ancestors.pop();
+        }
+    }
+}
 
 /// 现代化 Cargo 使用指南。
 fn modern_cargo_usage() {
@@ -309,6 +372,20 @@ pub fn run_ecosystem_examples() {
     
     modern_testing_strategies();
     
+    let features = detect_features();
+    println!("\n🖥️ 当前构建配置:");
+    println!("  目标平台: {} / {} ({})", features.os, features.arch, features.family);
+    println!("  指针宽度: {} 位", features.pointer_width);
+    println!("  debug_assertions: {}", features.debug_assertions);
+
+    println!("\n🌳 依赖树示例:");
+    let dependencies = [
+        ("rust_learn", vec!["serde", "tokio", "clap"]),
+        ("serde", vec!["serde_derive"]),
+        ("tokio", vec!["mio", "bytes"]),
+    ];
+    print!("{}", render_tree("rust_learn", &dependencies));
+    
     println!("\n✅ 所有现代化生态系统和工具示例运行完成！");
     println!("\n💡 建议:");
     println!("  - 定期运行 cargo update 更新依赖");
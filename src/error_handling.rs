@@ -17,15 +17,22 @@ mod infrastructure;
 mod io_config;
 
 pub use domain::{
+    TimingRecord,
     business_validation_error_handling,
     modern_error_logging,
     modern_error_recovery,
     modern_error_types,
+    retry,
+    timed,
+    timed_with_sink,
 };
 pub use fundamentals::{
+    ParseRangeError,
+    ResultExt,
     modern_panic_handling,
     modern_question_mark_patterns,
     modern_result_handling,
+    parse_in_range,
 };
 pub use infrastructure::{
     external_service_error_handling,
@@ -6,8 +6,69 @@
 //! - 日志与重试等恢复策略
 
 use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use super::fundamentals::{log_demo_error, log_demo_message, AppError};
+use crate::best_practices::{Backoff, RetryPolicy};
+
+/// 按 [`RetryPolicy`] 同步重试操作。
+///
+/// `operation` 会收到当前尝试次数（从 1 开始）；全部失败时返回最后一次的错误。
+/// `max_attempts` 为 0 时仍会执行一次。
+pub fn retry<T, E, F>(policy: &RetryPolicy, mut operation: F) -> Result<T, E>
+where
+    F: FnMut(u32) -> Result<T, E>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        match operation(attempt) {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt >= max_attempts => return Err(error),
+            Err(_) => {
+                thread::sleep(policy.delay_for(attempt));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// [`timed`] 产生的一次计时记录。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingRecord {
+    pub label: String,
+    pub duration: Duration,
+    pub ok: bool,
+}
+
+/// 计时执行返回 `Result` 的操作，输出标签、耗时与成功/失败结果后原样返回。
+pub fn timed<T, E>(label: &str, op: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    timed_with_sink(label, op, |record| {
+        let outcome = if record.ok { "ok" } else { "err" };
+        log_demo_message(
+            &record.label,
+            &format!("耗时 {:?}，结果: {}", record.duration, outcome),
+        );
+    })
+}
+
+/// 与 [`timed`] 相同，但把计时记录交给 `sink`，便于测试捕获或接入其他日志系统。
+pub fn timed_with_sink<T, E>(
+    label: &str,
+    op: impl FnOnce() -> Result<T, E>,
+    sink: impl FnOnce(TimingRecord),
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = op();
+    sink(TimingRecord {
+        label: label.to_string(),
+        duration: started.elapsed(),
+        ok: result.is_ok(),
+    });
+    result
+}
 
 /// 演示现代错误类型设计。
 pub fn modern_error_types() {
@@ -82,33 +143,26 @@ pub fn modern_error_recovery() {
         }
     }
 
-    fn with_retry<T, F>(max_retries: usize, operation_name: &str, operation: F) -> Result<T, RetryError>
+    fn with_retry<T, F>(policy: &RetryPolicy, operation_name: &str, operation: F) -> Result<T, RetryError>
     where
         F: Fn() -> Result<T, RetryError>,
     {
-        let mut last_error = RetryError::PermanentDenied;
-
-        for attempt in 1..=max_retries {
-            match operation() {
-                Ok(result) => return Ok(result),
-                Err(error) => {
-                    last_error = error;
-                    if attempt < max_retries {
-                        log_demo_message(
-                            operation_name,
-                            &format!("准备第 {} 次尝试，上次失败: {}", attempt + 1, error),
-                        );
-                    }
+        retry(policy, |attempt| {
+            operation().inspect_err(|error| {
+                if attempt < policy.max_attempts {
+                    log_demo_message(
+                        operation_name,
+                        &format!("准备第 {} 次尝试，上次失败: {}", attempt + 1, error),
+                    );
                 }
-            }
-        }
-
-        Err(last_error)
+            })
+        })
     }
 
+    let policy = RetryPolicy::new(3, Duration::from_millis(10), Backoff::Exponential);
     for id in [1, 4, 6, 8] {
         let operation_name = format!("重试任务 #{id}");
-        match with_retry(3, &operation_name, || unreliable_operation(id)) {
+        match with_retry(&policy, &operation_name, || unreliable_operation(id)) {
             Ok(result) => println!("✅ 操作结果: {}", result),
             Err(error) => {
                 log_demo_error(&operation_name, &error);
@@ -151,7 +205,8 @@ pub fn modern_error_logging() {
     ];
 
     for failure in failures {
-        log_demo_error(failure.operation, &failure.error);
+        let _ = timed(failure.operation, || Err::<(), _>(&failure.error))
+            .inspect_err(|error| log_demo_error(failure.operation, error));
         println!("⚠️ {} 操作失败，已使用统一示例日志口径输出", failure.operation);
     }
 }
@@ -11,6 +11,7 @@ use std::error::Error;
 use std::fmt;
 use std::fs::File;
 use std::io::{self, Read};
+use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// 用于演示 `Result` 的基础数学错误。
@@ -141,6 +142,75 @@ pub(crate) fn log_demo_message(operation: &str, message: &str) {
     println!("📝 [{}] {}", operation, message);
 }
 
+/// [`parse_in_range`] 的错误：区分“根本不是数字”与“是数字但越界”。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    /// 输入无法解析为目标类型。
+    Invalid { input: String },
+    /// 输入可以解析，但不在 `[lo, hi]` 闭区间内。
+    OutOfRange { input: String },
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { input } => write!(f, "'{}' 不是合法的数值", input),
+            Self::OutOfRange { input } => write!(f, "'{}' 超出允许范围", input),
+        }
+    }
+}
+
+impl Error for ParseRangeError {}
+
+/// 解析 `s`（忽略首尾空白）并检查其落在 `[lo, hi]` 闭区间内。
+///
+/// 解析失败返回 [`ParseRangeError::Invalid`]，越界（包括浮点 NaN 等无法与边界比较的值）
+/// 返回 [`ParseRangeError::OutOfRange`]，调用方可以据此给出不同的提示。
+pub fn parse_in_range<T: FromStr + PartialOrd>(
+    s: &str,
+    lo: T,
+    hi: T,
+) -> Result<T, ParseRangeError> {
+    let input = s.trim();
+    let value: T = input.parse().map_err(|_| ParseRangeError::Invalid {
+        input: input.to_string(),
+    })?;
+    // 写成取反的形式，使 NaN 这类无法比较的值也被当作越界拒绝
+    if !(lo <= value && value <= hi) {
+        return Err(ParseRangeError::OutOfRange {
+            input: input.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+/// 在链式调用中“旁观” `Result` 的扩展方法，适合插入日志而不改变值。
+///
+/// 与标准库的 `inspect`/`inspect_err` 语义相同，这里显式实现以演示扩展 trait 的写法。
+pub trait ResultExt<T, E>: Sized {
+    /// 结果为 `Ok` 时以引用调用 `f`，随后原样返回。
+    fn tap_ok(self, f: impl FnOnce(&T)) -> Self;
+
+    /// 结果为 `Err` 时以引用调用 `f`，随后原样返回。
+    fn tap_err(self, f: impl FnOnce(&E)) -> Self;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E> {
+    fn tap_ok(self, f: impl FnOnce(&T)) -> Self {
+        if let Ok(value) = &self {
+            f(value);
+        }
+        self
+    }
+
+    fn tap_err(self, f: impl FnOnce(&E)) -> Self {
+        if let Err(error) = &self {
+            f(error);
+        }
+        self
+    }
+}
+
 fn unique_demo_path(label: &str) -> std::path::PathBuf {
     let unique_id = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -207,6 +277,19 @@ pub fn modern_result_handling() {
     };
 
     println!("安全除法结果: {}", safe_result);
+
+    let quarter = divide(10.0, 4.0)
+        .tap_ok(|value| log_demo_message("链式日志", &format!("除法得到 {value}")))
+        .tap_err(|error| log_demo_error("链式日志", error));
+    println!("tap 后结果保持不变: {:?}", quarter.ok());
+
+    for raw in ["8080", "70000", "port"] {
+        match parse_in_range::<u32>(raw, 1, 65_535) {
+            Ok(port) => println!("✅ 端口: {}", port),
+            Err(error @ ParseRangeError::OutOfRange { .. }) => log_demo_error("端口范围", &error),
+            Err(error @ ParseRangeError::Invalid { .. }) => log_demo_error("端口格式", &error),
+        }
+    }
 }
 
 /// 演示 `?` 操作符与错误转换链。
@@ -6,8 +6,15 @@
 //!
 //! 其中：
 //! - `projects` 是独立实战项目专题入口，保留为库模块，但不纳入主学习 CLI。
-//! - `database` 是带运行环境要求的 SQLite + SQLx 教学示例，保留为库模块，
-//!   但同样不纳入默认 CLI 主路径。
+//! - `database` 是 SQLite + SQLx 教学示例，其中 SQLx 部分仅在启用 `database` feature（默认开启）时编译；
+//!   不依赖 SQLx 的 `database::MemoryDb` 始终可用。
+//!
+//! 网络与数据库依赖分别由 `http`、`database` 两个默认 feature 控制，
+//! 只需要基础主题时可用 `--no-default-features` 构建，避免引入 reqwest 与 sqlx。
+//!
+//! 启用 `no-color` feature 或调用 [`set_plain_output`] 可关闭彩色与装饰性输出，适合 CI 日志。
+
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub mod basics;
 pub mod ownership;
@@ -27,3 +34,24 @@ pub mod best_practices;
 pub mod pitfalls;
 pub mod projects;
 pub mod database;
+
+static PLAIN_OUTPUT: AtomicBool = AtomicBool::new(cfg!(feature = "no-color"));
+
+/// 切换纯文本输出模式。
+///
+/// 开启时全局关闭 `colored` 的颜色输出，并设置进程级的纯文本标记，
+/// 各模块（包括在工作线程中运行的模块）可通过 [`plain_output`] 判断是否跳过 emoji 等装饰性内容；
+/// 关闭时恢复 `colored` 默认的终端检测。
+pub fn set_plain_output(plain: bool) {
+    PLAIN_OUTPUT.store(plain, Ordering::Relaxed);
+    if plain {
+        colored::control::set_override(false);
+    } else {
+        colored::control::unset_override();
+    }
+}
+
+/// 当前是否处于纯文本输出模式。
+pub fn plain_output() -> bool {
+    PLAIN_OUTPUT.load(Ordering::Relaxed)
+}
@@ -5,6 +5,9 @@
 
 #![allow(dead_code)]
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
 /// 现代化声明宏示例
 macro_rules! say_hello {
     () => {
@@ -150,7 +153,9 @@ pub fn modern_custom_macros() {
 }
 
 /// 现代化派生宏示例
-#[derive(Debug, Clone, PartialEq)]
+///
+/// 同时派生 serde 的 `Serialize`/`Deserialize`，宏创建的点可以直接持久化。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
@@ -162,6 +167,12 @@ impl Point {
     }
 }
 
+impl From<(f64, f64)> for Point {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self { x, y }
+    }
+}
+
 impl std::fmt::Display for Point {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
@@ -189,6 +200,13 @@ pub fn modern_procedural_macros_demo() {
     println!("点3: {}", p3);
     println!("点4: {}", p4);
     println!("点3到点4距离: {:.2}", p3.distance(&p4));
+
+    // 元组转换与 serde 序列化
+    let p5 = Point::from((3.0, 4.0));
+    match serde_json::to_string(&p5) {
+        Ok(json) => println!("点5 JSON: {}", json),
+        Err(error) => println!("点5 序列化失败: {}", error),
+    }
 }
 
 /// 现代化宏卫生性和作用域
@@ -221,6 +239,76 @@ macro_rules! data_structure {
     };
 }
 
+/// 生成结构体以及每个字段的读取/写入方法
+///
+/// 字段保持私有，只能通过生成的 `new` 与访问器读写；`macro_rules!` 本身无法拼接标识符，
+/// 因此每个字段在 `=>` 之后显式给出读取与写入方法的名字。
+///
+/// ```
+/// rust_learn::accessors! {
+///     #[derive(Debug)]
+///     pub struct Config {
+///         host: String => (get_host, set_host),
+///         port: u16 => (get_port, set_port),
+///     }
+/// }
+///
+/// let mut config = Config::new("localhost".to_string(), 8080);
+/// config.set_port(443);
+/// assert_eq!(*config.get_port(), 443);
+/// assert_eq!(config.get_host(), "localhost");
+/// ```
+#[macro_export]
+macro_rules! accessors {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $field:ident: $ty:ty => ($getter:ident, $setter:ident) ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $field: $ty, )*
+        }
+
+        impl $name {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($( $field: $ty ),*) -> Self {
+                Self { $( $field ),* }
+            }
+
+            $(
+                pub fn $getter(&self) -> &$ty {
+                    &self.$field
+                }
+
+                pub fn $setter(&mut self, value: $ty) {
+                    self.$field = value;
+                }
+            )*
+        }
+    };
+}
+
+/// 模式匹配时取出绑定值，匹配失败返回 `None`，相当于返回 `Option` 的 `matches!`
+///
+/// 支持可选的 `if` 守卫：`match_variant!(shape, Shape::Circle(r) if r > 0.0 => r)`。
+///
+/// ```
+/// let value: Result<u8, &str> = Ok(7);
+/// assert_eq!(rust_learn::match_variant!(value, Ok(n) => n * 2), Some(14));
+/// assert_eq!(rust_learn::match_variant!(value, Err(e) => e), None);
+/// ```
+#[macro_export]
+macro_rules! match_variant {
+    ($value:expr, $pattern:pat $(if $guard:expr)? => $extract:expr $(,)?) => {
+        match $value {
+            $pattern $(if $guard)? => ::core::option::Option::Some($extract),
+            _ => ::core::option::Option::None,
+        }
+    };
+}
+
 /// 现代化宏卫生性演示
 pub fn modern_macro_hygiene() {
     println!("🧹 现代化宏卫生性：");
@@ -263,6 +351,19 @@ pub fn modern_macro_hygiene() {
 
     println!("状态: {:?}", status);
     println!("待处理: {:?}", pending_status);
+
+    // 访问器宏：为每个字段生成 get_/set_ 方法
+    crate::accessors! {
+        #[derive(Debug)]
+        struct ServerConfig {
+            host: String => (get_host, set_host),
+            port: u16 => (get_port, set_port),
+        }
+    }
+
+    let mut server = ServerConfig::new("localhost".to_string(), 8080);
+    server.set_port(443);
+    println!("访问器: {}:{}", server.get_host(), server.get_port());
 }
 
 /// 现代化重复模式宏
@@ -547,22 +648,161 @@ pub fn configuration_dsl() {
         prod_config.server.host, prod_config.server.port, prod_config.server.workers
     );
 
+    // 从单行文本覆盖服务器配置：名称, 主机, 端口, 工作线程数
+    let override_line = " staging , 10.0.0.5,4000 ,  4 ";
+    let mut fields = crate::basics::split_trim(override_line, ',');
+    let profile = fields.next().unwrap_or_default();
+    let staging_server = match (fields.next(), fields.next(), fields.next()) {
+        (Some(host), Some(port), Some(workers)) => {
+            port.parse()
+                .ok()
+                .zip(workers.parse().ok())
+                .map(|(port, workers)| ServerConfig {
+                    host: host.to_string(),
+                    port,
+                    workers,
+                })
+        }
+        _ => None,
+    };
+    match staging_server {
+        Some(server) => println!(
+            "✅ {} 环境覆盖: {}:{} ({} 工作线程)",
+            profile, server.host, server.port, server.workers
+        ),
+        None => println!("⚠️ 覆盖行格式无效: {:?}", override_line),
+    }
+
     println!("📊 配置管理DSL演示完成");
 }
 
+/// API路由DSL使用的HTTP方法
+///
+/// 可通过 `str::parse` 从请求行解析（不区分大小写），`Display` 输出大写方法名。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    GET,
+    POST,
+    PUT,
+    PATCH,
+    DELETE,
+    HEAD,
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::GET => "GET",
+            HttpMethod::POST => "POST",
+            HttpMethod::PUT => "PUT",
+            HttpMethod::PATCH => "PATCH",
+            HttpMethod::DELETE => "DELETE",
+            HttpMethod::HEAD => "HEAD",
+        }
+    }
+}
+
+impl std::str::FromStr for HttpMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "GET" => Ok(HttpMethod::GET),
+            "POST" => Ok(HttpMethod::POST),
+            "PUT" => Ok(HttpMethod::PUT),
+            "PATCH" => Ok(HttpMethod::PATCH),
+            "DELETE" => Ok(HttpMethod::DELETE),
+            "HEAD" => Ok(HttpMethod::HEAD),
+            _ => Err(format!("Unknown HTTP method: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 路由匹配结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathMatch {
+    pub handler: String,
+    /// 捕获的参数：`:name` 段以 `name` 为键，结尾的 `*` 段以 `*` 为键
+    pub params: HashMap<String, String>,
+}
+
+impl PathMatch {
+    /// 结尾 `*` 段捕获的剩余路径
+    pub fn tail(&self) -> Option<&str> {
+        self.params.get("*").map(String::as_str)
+    }
+}
+
+/// 按方法和路径模式分发请求的路由表
+///
+/// 路径模式按 `/` 分段：`:name` 匹配任意单段并捕获，
+/// 位于末尾的 `*` 匹配剩余的全部路径（可以为空），其余段需要字面相等。
+/// 按注册顺序匹配，先注册的路由优先。
+#[derive(Debug, Clone, Default)]
+pub struct Router {
+    routes: Vec<(HttpMethod, String, String)>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册路由
+    pub fn route(&mut self, method: HttpMethod, pattern: &str, handler: &str) -> &mut Self {
+        self.routes
+            .push((method, pattern.to_string(), handler.to_string()));
+        self
+    }
+
+    /// 查找与请求匹配的第一条路由
+    pub fn resolve(&self, method: HttpMethod, path: &str) -> Option<PathMatch> {
+        self.routes
+            .iter()
+            .filter(|(route_method, _, _)| *route_method == method)
+            .find_map(|(_, pattern, handler)| {
+                Self::match_path(pattern, path).map(|params| PathMatch {
+                    handler: handler.clone(),
+                    params,
+                })
+            })
+    }
+
+    fn match_path(pattern: &str, path: &str) -> Option<HashMap<String, String>> {
+        let pattern: Vec<&str> = pattern.trim_matches('/').split('/').collect();
+        let path: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let mut params = HashMap::new();
+
+        for (i, segment) in pattern.iter().enumerate() {
+            if *segment == "*" && i == pattern.len() - 1 {
+                let tail = path.get(i..).unwrap_or_default().join("/");
+                params.insert("*".to_string(), tail);
+                return Some(params);
+            }
+            let actual = path.get(i)?;
+            match segment.strip_prefix(':') {
+                Some(name) => {
+                    params.insert(name.to_string(), actual.to_string());
+                }
+                None if segment == actual => {}
+                None => return None,
+            }
+        }
+
+        (pattern.len() == path.len()).then_some(params)
+    }
+}
+
 /// 演示API路由DSL
 pub fn api_routing_dsl() {
     println!("🛣️ API路由DSL：");
 
-    // HTTP方法枚举
-    #[derive(Debug, Clone)]
-    pub enum HttpMethod {
-        GET,
-        POST,
-        PUT,
-        DELETE,
-    }
-
     // 路由结构体
     #[derive(Debug)]
     pub struct Route {
@@ -581,17 +821,7 @@ pub fn api_routing_dsl() {
         }
 
         pub fn render(&self) -> String {
-            format!(
-                "{} {} -> {}",
-                match self.method {
-                    HttpMethod::GET => "GET",
-                    HttpMethod::POST => "POST",
-                    HttpMethod::PUT => "PUT",
-                    HttpMethod::DELETE => "DELETE",
-                },
-                self.path,
-                self.handler
-            )
+            format!("{} {} -> {}", self.method, self.path, self.handler)
         }
     }
 
@@ -614,12 +844,24 @@ pub fn api_routing_dsl() {
         };
     }
 
+    macro_rules! patch_route {
+        ($path:expr, $handler:expr) => {
+            Route::new(HttpMethod::PATCH, $path, $handler)
+        };
+    }
+
     macro_rules! delete_route {
         ($path:expr, $handler:expr) => {
             Route::new(HttpMethod::DELETE, $path, $handler)
         };
     }
 
+    macro_rules! head_route {
+        ($path:expr, $handler:expr) => {
+            Route::new(HttpMethod::HEAD, $path, $handler)
+        };
+    }
+
     macro_rules! routes {
         ($($route:expr),* $(,)?) => {{
             vec![$($route),*]
@@ -634,8 +876,10 @@ pub fn api_routing_dsl() {
         get_route!("/api/users/:id", "get_user_by_id"),
         post_route!("/api/users", "create_user"),
         put_route!("/api/users/:id", "update_user"),
+        patch_route!("/api/users/:id", "patch_user"),
         delete_route!("/api/users/:id", "delete_user"),
         get_route!("/api/health", "health_check"),
+        head_route!("/api/health", "health_probe"),
     ];
 
     println!("✅ 生成的路由:");
@@ -643,6 +887,35 @@ pub fn api_routing_dsl() {
         println!("   {}: {}", i + 1, route.render());
     }
 
+    // 按真实请求行中的方法匹配路由
+    let request_line = "patch /api/users/:id";
+    if let Some((method, path)) = request_line.split_once(' ') {
+        match method.parse::<HttpMethod>() {
+            Ok(method) => {
+                let matched = routes
+                    .iter()
+                    .find(|route| route.method == method && route.path == path);
+                match matched {
+                    Some(route) => println!("🎯 请求 {:?} 匹配: {}", request_line, route.render()),
+                    None => println!("❓ 请求 {:?} 没有匹配的路由", request_line),
+                }
+            }
+            Err(e) => println!("❌ {}", e),
+        }
+    }
+
+    // 带参数和通配段的路由表
+    let mut router = Router::new();
+    router
+        .route(HttpMethod::GET, "/api/users/:id", "get_user_by_id")
+        .route(HttpMethod::GET, "/files/*", "serve_file");
+    for path in ["/api/users/42", "/files/docs/guide.md", "/missing"] {
+        match router.resolve(HttpMethod::GET, path) {
+            Some(found) => println!("🧭 {} -> {} {:?}", path, found.handler, found.params),
+            None => println!("🧭 {} -> 404", path),
+        }
+    }
+
     println!("📊 API路由DSL演示完成");
 }
 
@@ -768,6 +1041,19 @@ pub fn complex_macro_patterns() {
         identify_pattern!(sum_greater_than: [5, 10, 20], 30)
     );
     println!("包含6: {}", identify_pattern!(contains: [1, 2, 3, 6], 6));
+
+    // 提取枚举变体中的值，不必写完整的 match
+    #[derive(Debug)]
+    enum Message {
+        Text(String),
+        Ping,
+    }
+    let messages = [Message::Text("hello".to_string()), Message::Ping];
+    let texts: Vec<&str> = messages
+        .iter()
+        .filter_map(|message| crate::match_variant!(message, Message::Text(text) => text.as_str()))
+        .collect();
+    println!("文本消息: {:?}", texts);
 }
 
 /// 演示内联函数宏
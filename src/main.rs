@@ -2,12 +2,18 @@
 //!
 //! 这个程序演示了如何运行当前已接入 CLI 的学习模块。
 //! crate 使用 Rust 2024 Edition；CLI 注册表覆盖所有稳定接入的学习主题，
-//! `projects` 保持为独立实战项目入口，`database` 已作为库模块开放，
-//! 但仍暂不纳入主学习 CLI，避免将数据库运行环境要求引入默认主路径。
+//! `projects` 保持为独立实战项目入口，不纳入主学习 CLI。
+//! `database` 仅在启用 `database` feature（默认开启）时注册；关闭该 feature 时，
+//! 请求该模块会给出“构建时未启用”的提示，而不是报告未知模块。
 //! `ecosystem` 目前保留为说明性导览模块：仍可从 CLI 运行与从库侧兼容访问，
 //! 但不应被视为与其它可验证源码等价的稳定工程 API。
 
+use serde::Deserialize;
 use std::env;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
 use std::time::{Duration, Instant};
 
 /// CLI 学习阶段，用于组织帮助输出和推荐学习路径。
@@ -144,6 +150,13 @@ const MODULE_REGISTRY: &[ModuleInfo] = &[
         stage: LearningStage::PracticeTracks,
         run_function: rust_learn::popular_libraries::run_popular_libraries_examples,
     },
+    #[cfg(feature = "database")]
+    ModuleInfo {
+        name: "database",
+        description: "SQLite + SQLx 数据库集成（内存数据库，无需外部服务）",
+        stage: LearningStage::PracticeTracks,
+        run_function: rust_learn::database::run_database_examples_blocking,
+    },
     ModuleInfo {
         name: "security",
         description: "安全编程实践与输入验证",
@@ -166,21 +179,79 @@ const NON_CLI_MODULES: &[ModuleStatus] = &[
         reading_path: "src/projects/mod.rs -> src/projects/task_manager/mod.rs",
         suggestion: "如果你想继续主线学习，建议改为运行 `cargo run -- popular_libraries` 或 `cargo run -- best_practices`；如果想看项目化示例，请直接阅读对应源码。",
     },
+    #[cfg(not(feature = "database"))]
     ModuleStatus {
         name: "database",
-        summary: "数据库模块已统一为 SQLite + SQLx 教学示例，并作为库模块开放；但当前仍不纳入主学习 CLI。",
+        summary: "当前构建未启用 `database` feature，数据库模块（SQLite + SQLx）未编译进程序。",
         reading_path: "src/database.rs",
-        suggestion: "如需阅读数据库主题，可直接查看源码；若需要稳定 CLI 主线学习，建议继续使用 `cargo run -- ecosystem` 或 `cargo run -- popular_libraries`。",
+        suggestion: "使用 `cargo run --features database -- database` 重新构建后运行；或继续使用 `cargo run -- popular_libraries`。",
     },
 ];
 
+/// 进程退出码：全部成功。
+const EXIT_SUCCESS: i32 = 0;
+/// 进程退出码：模块执行失败或超时。
+const EXIT_MODULE_FAILURE: i32 = 1;
+/// 进程退出码：命令行参数错误。
+const EXIT_USAGE_ERROR: i32 = 2;
+
+/// `run_all_examples` 的执行汇总，成功与失败路径都会携带。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RunSummary {
+    total: usize,
+    succeeded: usize,
+    failed: Vec<&'static str>,
+    elapsed: Duration,
+}
+
+impl RunSummary {
+    fn all_succeeded(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// 未成功（失败或超时）的模块数量。
+    fn failed_count(&self) -> usize {
+        self.failed.len()
+    }
+
+    /// 将汇总映射为进程退出码：全部成功为 0，否则为 1。
+    fn exit_code(&self) -> i32 {
+        if self.all_succeeded() {
+            EXIT_SUCCESS
+        } else {
+            EXIT_MODULE_FAILURE
+        }
+    }
+}
+
 /// CLI 错误类型。
 #[derive(Debug, PartialEq, Eq)]
 enum AppError {
     UnknownModule(String),
     UnavailableModule(&'static ModuleStatus),
     TooManyArguments,
+    /// 未知选项或选项取值不合法，如 `--timeout-ms soon`。
+    InvalidFlag(String),
+    /// `--config` 文件无法读取、解析或校验失败。
+    ConfigError(String),
     ModuleExecutionFailed(String),
+    ModuleTimedOut(String),
+    ModulesFailed(RunSummary),
+}
+
+impl AppError {
+    /// 参数类错误返回 2，执行类错误返回 1。
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::UnknownModule(_)
+            | Self::UnavailableModule(_)
+            | Self::TooManyArguments
+            | Self::InvalidFlag(_)
+            | Self::ConfigError(_) => EXIT_USAGE_ERROR,
+            Self::ModuleExecutionFailed(_) | Self::ModuleTimedOut(_) => EXIT_MODULE_FAILURE,
+            Self::ModulesFailed(summary) => summary.exit_code(),
+        }
+    }
 }
 
 impl std::fmt::Display for AppError {
@@ -193,9 +264,21 @@ impl std::fmt::Display for AppError {
             Self::TooManyArguments => {
                 write!(f, "参数过多；仅支持一个模块名，或使用 help / --help / --performance")
             }
+            Self::InvalidFlag(reason) => write!(f, "无效的命令行选项: {}", reason),
+            Self::ConfigError(reason) => write!(f, "配置文件错误: {}", reason),
             Self::ModuleExecutionFailed(module) => {
                 write!(f, "模块执行失败: {}", module)
             }
+            Self::ModuleTimedOut(module) => {
+                write!(f, "模块执行超时: {}", module)
+            }
+            Self::ModulesFailed(summary) => write!(
+                f,
+                "{}/{} 个模块未成功: {}",
+                summary.failed_count(),
+                summary.total,
+                summary.failed.join(", ")
+            ),
         }
     }
 }
@@ -247,11 +330,74 @@ fn suggested_modules(input: &str) -> Vec<&'static str> {
     matches
 }
 
+/// 按量级选择 µs / ms / s 单位，统一保留两位小数。
 fn format_duration(duration: Duration) -> String {
-    if duration.as_secs_f64() >= 1.0 {
-        format!("{:.2}s", duration.as_secs_f64())
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else if secs >= 0.001 {
+        format!("{:.2}ms", secs * 1_000.0)
     } else {
-        format!("{:.2}ms", duration.as_secs_f64() * 1_000.0)
+        format!("{:.2}µs", secs * 1_000_000.0)
+    }
+}
+
+/// 进度条内部宽度（不含方括号）。
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// 渲染 `[=====>    ] 5/11` 形式的进度条文本。
+fn render_progress_bar(current: usize, total: usize, width: usize) -> String {
+    let current = current.min(total);
+    let filled = (current * width).checked_div(total).unwrap_or(width);
+
+    let bar = if filled >= width {
+        "=".repeat(width)
+    } else {
+        format!("{}>{}", "=".repeat(filled), " ".repeat(width - filled - 1))
+    };
+
+    format!("[{}] {}/{}", bar, current, total)
+}
+
+/// `run_all_examples` 使用的整体进度指示器。
+///
+/// 仅当标准输出是终端时才通过 `\r` 原地刷新，管道输出保持干净。
+struct Progress {
+    total: usize,
+    enabled: bool,
+}
+
+impl Progress {
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            enabled: std::io::stdout().is_terminal(),
+        }
+    }
+
+    fn update(&self, current: usize) {
+        if !self.enabled {
+            return;
+        }
+
+        print!("\r{}", render_progress_bar(current, self.total, PROGRESS_BAR_WIDTH));
+        let _ = std::io::stdout().flush();
+    }
+
+    fn clear(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        let line_width = render_progress_bar(self.total, self.total, PROGRESS_BAR_WIDTH).len();
+        print!("\r{}\r", " ".repeat(line_width));
+        let _ = std::io::stdout().flush();
+    }
+
+    fn finish(&self) {
+        if self.enabled {
+            println!();
+        }
     }
 }
 
@@ -265,15 +411,78 @@ fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
     }
 }
 
-fn execute_module(module: &ModuleInfo) -> Result<Duration, AppError> {
+/// 带超时运行的结果。
+#[derive(Debug, PartialEq, Eq)]
+enum TimedRun {
+    Completed,
+    Panicked(String),
+    TimedOut,
+}
+
+fn run_catching_panic<F>(task: F) -> TimedRun
+where
+    F: FnOnce(),
+{
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(task)) {
+        Ok(()) => TimedRun::Completed,
+        Err(payload) => TimedRun::Panicked(panic_message(payload)),
+    }
+}
+
+/// 在独立线程中运行任务，并最多等待 `timeout`。
+///
+/// 工作线程在结束前通过通道回报结果；超时后调用方不再等待，
+/// 挂起的线程会被分离，让 CLI 可以继续执行后续模块。
+fn run_with_timeout<F>(task: F, timeout: Duration) -> TimedRun
+where
+    F: FnOnce() + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let _ = sender.send(run_catching_panic(task));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(outcome) => {
+            let _ = handle.join();
+            outcome
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => TimedRun::TimedOut,
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            TimedRun::Panicked("工作线程未回报结果即退出".to_string())
+        }
+    }
+}
+
+/// 执行单个模块，并在 `module` span 中记录模块名与耗时。
+///
+/// 未安装订阅器时 span 不产生任何输出；`--verbose` 会安装订阅器，
+/// 便于定位耗时异常的模块。
+fn execute_module(module: &ModuleInfo, timeout: Option<Duration>) -> Result<Duration, AppError> {
+    let span = tracing::info_span!(
+        "module",
+        name = module.name,
+        duration_ms = tracing::field::Empty
+    );
+    let _entered = span.enter();
+
     let start_time = Instant::now();
-    std::panic::catch_unwind(std::panic::AssertUnwindSafe(module.run_function)).map_err(
-        |payload| {
-            let message = panic_message(payload);
-            AppError::ModuleExecutionFailed(format!("{}（panic: {}）", module.name, message))
-        },
-    )?;
-    Ok(start_time.elapsed())
+    let outcome = match timeout {
+        Some(limit) => run_with_timeout(module.run_function, limit),
+        None => run_catching_panic(module.run_function),
+    };
+    let elapsed = start_time.elapsed();
+    span.record("duration_ms", elapsed.as_millis() as u64);
+    tracing::info!(duration_ms = elapsed.as_millis() as u64, "模块执行结束");
+
+    match outcome {
+        TimedRun::Completed => Ok(elapsed),
+        TimedRun::Panicked(message) => Err(AppError::ModuleExecutionFailed(format!(
+            "{}（panic: {}）",
+            module.name, message
+        ))),
+        TimedRun::TimedOut => Err(AppError::ModuleTimedOut(module.name.to_string())),
+    }
 }
 
 fn print_learning_path_overview() {
@@ -344,23 +553,42 @@ fn print_error_guidance(error: &AppError) {
             eprintln!("   - 下一步建议: {}", module.suggestion);
             eprintln!("   - 使用 `cargo run -- --help` 可查看主学习 CLI 当前支持的模块");
         }
-        AppError::TooManyArguments => {
+        AppError::TooManyArguments | AppError::InvalidFlag(_) => {
             eprintln!("💡 支持的调用方式:");
             eprintln!("   - `cargo run`");
             eprintln!("   - `cargo run -- <module>`");
             eprintln!("   - `cargo run -- help` 或 `cargo run -- --help`");
             eprintln!("   - `cargo run -- --performance`");
+            eprintln!("   - `cargo run -- [<module>] --timeout-ms <毫秒>`");
+            eprintln!("   - `cargo run -- --config <文件>`（不能与模块名同时使用）");
+            eprintln!("   - `--timeout-ms` 需要正整数毫秒数，例如 5000");
+        }
+        AppError::ConfigError(_) => {
+            eprintln!("💡 配置文件格式示例:");
+            eprintln!(r#"   - {{ "modules": ["basics", "types"], "repeat": 2 }}"#);
+            eprintln!("   - `repeat` 可省略（默认 1），`modules` 至少包含一个模块");
         }
         AppError::ModuleExecutionFailed(module) => {
             eprintln!("💡 下一步建议:");
             eprintln!("   - 可先单独重试 `cargo run -- {}`，聚焦查看该模块输出", module);
             eprintln!("   - 若只是查看学习路径，可使用 `cargo run -- --help`");
         }
+        AppError::ModuleTimedOut(module) => {
+            eprintln!("💡 下一步建议:");
+            eprintln!("   - 模块 '{}' 可能在等待网络或其它外部资源", module);
+            eprintln!("   - 可尝试调大 `--timeout-ms`，或去掉该参数后单独运行该模块");
+        }
+        AppError::ModulesFailed(summary) => {
+            eprintln!("💡 下一步建议:");
+            for module in &summary.failed {
+                eprintln!("   - 单独重试 `cargo run -- {}`，聚焦查看该模块输出", module);
+            }
+        }
     }
 }
 
 /// 运行所有已接入 CLI 的模块示例。
-fn run_all_examples() -> Result<(), AppError> {
+fn run_all_examples(timeout: Option<Duration>) -> Result<RunSummary, AppError> {
     println!("🚀 启动 Rust 学习项目");
     println!("📦 Rust Edition: 2024");
     println!(
@@ -375,8 +603,10 @@ fn run_all_examples() -> Result<(), AppError> {
     let total_modules = MODULE_REGISTRY.len();
     let mut success_count = 0;
     let mut failed_modules = Vec::new();
+    let progress = Progress::new(total_modules);
 
     for (index, module) in MODULE_REGISTRY.iter().enumerate() {
+        progress.clear();
         print!(
             "[{}/{}] 执行模块: {} [{}] - {} ...",
             index + 1,
@@ -386,7 +616,7 @@ fn run_all_examples() -> Result<(), AppError> {
             module.description
         );
 
-        match execute_module(module) {
+        match execute_module(module, timeout) {
             Ok(duration) => {
                 println!(" ✅ 完成 ({})", format_duration(duration));
                 success_count += 1;
@@ -396,29 +626,41 @@ fn run_all_examples() -> Result<(), AppError> {
                 eprintln!("⚠️ 模块 '{}' 执行失败", module.name);
                 failed_modules.push(module.name);
             }
+            Err(AppError::ModuleTimedOut(_)) => {
+                println!(" ⏱️ 超时");
+                eprintln!("⚠️ 模块 '{}' 超出时间预算，继续执行下一个模块", module.name);
+                failed_modules.push(module.name);
+            }
             Err(other) => return Err(other),
         }
 
+        progress.update(index + 1);
         std::thread::sleep(Duration::from_millis(100));
     }
+    progress.finish();
 
-    let total_duration = start_time.elapsed();
-    let average_duration = Duration::from_secs_f64(total_duration.as_secs_f64() / total_modules as f64);
+    let summary = RunSummary {
+        total: total_modules,
+        succeeded: success_count,
+        failed: failed_modules,
+        elapsed: start_time.elapsed(),
+    };
+    let average_duration =
+        Duration::from_secs_f64(summary.elapsed.as_secs_f64() / summary.total as f64);
 
     println!();
     println!("📊 执行统计:");
-    println!("   ✅ 成功模块: {}/{}", success_count, total_modules);
-    println!("   ⏱️ 总执行时间: {}", format_duration(total_duration));
+    println!("   ✅ 成功模块: {}/{}", summary.succeeded, summary.total);
+    println!("   ⏱️ 总执行时间: {}", format_duration(summary.elapsed));
     println!("   📈 平均模块时间: {}", format_duration(average_duration));
 
-    if failed_modules.is_empty() {
+    if summary.all_succeeded() {
         println!("\n🎉 所有已接入模块执行成功，CLI 演示完成！");
         println!("💡 可继续使用 `cargo run -- <module>` 单独复习某一主题。");
-        Ok(())
+        Ok(summary)
     } else {
-        let failed = failed_modules.join(", ");
-        eprintln!("\n⚠️ 以下模块执行失败: {}", failed);
-        Err(AppError::ModuleExecutionFailed(failed))
+        eprintln!("\n⚠️ 以下模块执行失败: {}", summary.failed.join(", "));
+        Err(AppError::ModulesFailed(summary))
     }
 }
 
@@ -435,7 +677,7 @@ fn resolve_module_request(module_name: &str) -> Result<&'static ModuleInfo, AppE
 }
 
 /// 运行指定模块示例。
-fn run_specific_example(module_name: &str) -> Result<(), AppError> {
+fn run_specific_example(module_name: &str, timeout: Option<Duration>) -> Result<(), AppError> {
     let module = resolve_module_request(module_name)?;
 
     println!("🎯 执行模块: {} - {}", module.name, module.description);
@@ -443,13 +685,70 @@ fn run_specific_example(module_name: &str) -> Result<(), AppError> {
     println!("📖 学习目标: {}", module.stage.focus());
     println!();
 
-    let duration = execute_module(module)?;
+    let duration = execute_module(module, timeout)?;
     println!("\n✅ {} 模块执行完成！耗时: {}", module.name, format_duration(duration));
     print_post_module_guidance(module);
 
     Ok(())
 }
 
+/// `--config` 指定的脚本化运行配置。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunConfig {
+    modules: Vec<String>,
+    #[serde(default = "RunConfig::default_repeat")]
+    repeat: u32,
+}
+
+impl RunConfig {
+    fn default_repeat() -> u32 {
+        1
+    }
+}
+
+/// 解析并校验运行配置；模块名在执行前统一校验，避免运行到一半才发现拼写错误。
+fn parse_run_config(json: &str) -> Result<RunConfig, AppError> {
+    let config: RunConfig =
+        serde_json::from_str(json).map_err(|error| AppError::ConfigError(error.to_string()))?;
+
+    if config.modules.is_empty() {
+        return Err(AppError::ConfigError("modules 不能为空".to_string()));
+    }
+    if config.repeat == 0 {
+        return Err(AppError::ConfigError("repeat 必须为正整数".to_string()));
+    }
+    for module in &config.modules {
+        resolve_module_request(module)?;
+    }
+
+    Ok(config)
+}
+
+fn load_run_config(path: &Path) -> Result<RunConfig, AppError> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|error| AppError::ConfigError(format!("{}: {}", path.display(), error)))?;
+    parse_run_config(&json)
+}
+
+/// 按配置顺序运行模块，整组重复 `repeat` 次；任一模块失败即停止。
+fn run_configured_examples(config: &RunConfig, timeout: Option<Duration>) -> Result<(), AppError> {
+    for round in 1..=config.repeat {
+        println!(
+            "🔁 第 {}/{} 轮: {}",
+            round,
+            config.repeat,
+            config.modules.join(" → ")
+        );
+        for module in &config.modules {
+            run_specific_example(module, timeout)?;
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
 /// 输出 CLI 帮助信息。
 fn print_usage() {
     println!("📖 Rust 学习项目 CLI 使用说明");
@@ -459,6 +758,9 @@ fn print_usage() {
     println!("  cargo run -- <module>          - 运行指定学习模块");
     println!("  cargo run -- help              - 显示此帮助信息（等价于 --help）");
     println!("  cargo run -- --performance     - 显示性能与学习阶段概览");
+    println!("  cargo run -- --timeout-ms <N>  - 为每个模块设置 N 毫秒的执行时间预算（可与模块名组合）");
+    println!("  cargo run -- --verbose         - 启用 tracing 输出，记录每个模块的执行耗时（可简写为 -v）");
+    println!("  cargo run -- --config <FILE>   - 按 JSON 配置文件中的顺序与轮数运行模块");
     println!();
 
     print_learning_path_overview();
@@ -503,6 +805,24 @@ struct Args {
     module: Option<String>,
     show_help: bool,
     show_performance: bool,
+    timeout: Option<Duration>,
+    verbose: bool,
+    config: Option<PathBuf>,
+}
+
+fn parse_timeout_ms(value: &str) -> Result<Duration, AppError> {
+    match value.parse::<u64>() {
+        Ok(millis) if millis > 0 => Ok(Duration::from_millis(millis)),
+        _ => Err(AppError::InvalidFlag(format!(
+            "--timeout-ms 需要正整数毫秒数，收到: {}",
+            value
+        ))),
+    }
+}
+
+/// 以位置参数形式处理的模式选项（帮助与性能概览）。
+fn is_mode_flag(arg: &str) -> bool {
+    matches!(arg, "--help" | "-h" | "--performance" | "-p")
 }
 
 fn parse_args_from<I, S>(args: I) -> Result<Args, AppError>
@@ -510,45 +830,80 @@ where
     I: IntoIterator<Item = S>,
     S: Into<String>,
 {
-    let args: Vec<String> = args.into_iter().map(Into::into).collect();
+    let mut remaining = args.into_iter().map(Into::into).skip(1);
+    let mut positional: Vec<String> = Vec::new();
+    let mut timeout = None;
+    let mut verbose = false;
+    let mut config = None;
+
+    while let Some(arg) = remaining.next() {
+        if arg == "--verbose" || arg == "-v" {
+            verbose = true;
+        } else if arg == "--timeout-ms" {
+            let value = remaining
+                .next()
+                .ok_or_else(|| AppError::InvalidFlag("--timeout-ms 缺少取值".to_string()))?;
+            timeout = Some(parse_timeout_ms(&value)?);
+        } else if let Some(value) = arg.strip_prefix("--timeout-ms=") {
+            timeout = Some(parse_timeout_ms(value)?);
+        } else if arg == "--config" {
+            let value = remaining
+                .next()
+                .ok_or_else(|| AppError::InvalidFlag("--config 缺少配置文件路径".to_string()))?;
+            config = Some(PathBuf::from(value));
+        } else if let Some(value) = arg.strip_prefix("--config=") {
+            config = Some(PathBuf::from(value));
+        } else if arg.starts_with('-') && !is_mode_flag(&arg) {
+            return Err(AppError::InvalidFlag(format!("未知选项 {}", arg)));
+        } else {
+            positional.push(arg);
+        }
+    }
 
-    if args.len() > 2 {
+    if positional.len() > 1 {
         return Err(AppError::TooManyArguments);
     }
 
-    if args.len() == 1 {
-        return Ok(Args {
-            module: None,
-            show_help: false,
-            show_performance: false,
-        });
+    let mut args = Args {
+        module: None,
+        show_help: false,
+        show_performance: false,
+        timeout,
+        verbose,
+        config,
+    };
+
+    match positional.pop().as_deref() {
+        None => {}
+        Some("--help" | "-h" | "help") => args.show_help = true,
+        Some("--performance" | "-p" | "performance") => args.show_performance = true,
+        Some(other) => args.module = Some(other.to_string()),
     }
 
-    match args[1].as_str() {
-        "--help" | "-h" | "help" => Ok(Args {
-            module: None,
-            show_help: true,
-            show_performance: false,
-        }),
-        "--performance" | "-p" | "performance" => Ok(Args {
-            module: None,
-            show_help: false,
-            show_performance: true,
-        }),
-        other => Ok(Args {
-            module: Some(other.to_string()),
-            show_help: false,
-            show_performance: false,
-        }),
+    if args.module.is_some() && args.config.is_some() {
+        return Err(AppError::TooManyArguments);
     }
+
+    Ok(args)
 }
 
 fn parse_args() -> Result<Args, AppError> {
     parse_args_from(env::args())
 }
 
+/// 启用 `no-color` feature 时在启动阶段关闭彩色输出。
+///
+/// 纯文本标记的默认值已随 feature 打开，这里负责同步 `colored` 的全局开关。
+fn apply_output_features() {
+    if cfg!(feature = "no-color") {
+        rust_learn::set_plain_output(true);
+    }
+}
+
 /// 程序主入口。
 fn main() {
+    apply_output_features();
+
     let args = match parse_args() {
         Ok(args) => args,
         Err(error) => {
@@ -556,7 +911,7 @@ fn main() {
             print_error_guidance(&error);
             println!();
             print_usage();
-            std::process::exit(1);
+            std::process::exit(error.exit_code());
         }
     };
 
@@ -570,20 +925,29 @@ fn main() {
         return;
     }
 
-    let result = match args.module {
-        Some(ref module) => run_specific_example(module),
-        None => run_all_examples(),
+    if args.verbose {
+        rust_learn::popular_libraries::init_tracing_once();
+    }
+
+    let result = match (&args.module, &args.config) {
+        (Some(module), _) => run_specific_example(module, args.timeout).map(|()| EXIT_SUCCESS),
+        (None, Some(path)) => load_run_config(path)
+            .and_then(|config| run_configured_examples(&config, args.timeout))
+            .map(|()| EXIT_SUCCESS),
+        (None, None) => run_all_examples(args.timeout).map(|summary| summary.exit_code()),
     };
 
     match result {
-        Ok(()) => {
-            println!("✅ 程序执行成功");
-            std::process::exit(0);
+        Ok(exit_code) => {
+            if exit_code == EXIT_SUCCESS {
+                println!("✅ 程序执行成功");
+            }
+            std::process::exit(exit_code);
         }
         Err(error) => {
             eprintln!("❌ 程序执行失败: {}", error);
             print_error_guidance(&error);
-            std::process::exit(1);
+            std::process::exit(error.exit_code());
         }
     }
 }
@@ -606,6 +970,9 @@ mod tests {
                 module: None,
                 show_help: false,
                 show_performance: false,
+                timeout: None,
+                verbose: false,
+                config: None,
             },
             "empty invocation should keep CLI in run-all mode"
         );
@@ -623,6 +990,9 @@ mod tests {
                     module: None,
                     show_help: true,
                     show_performance: false,
+                    timeout: None,
+                    verbose: false,
+                    config: None,
                 },
                 "alias {alias} should route to help output"
             );
@@ -641,6 +1011,9 @@ mod tests {
                     module: None,
                     show_help: false,
                     show_performance: true,
+                    timeout: None,
+                    verbose: false,
+                    config: None,
                 },
                 "alias {alias} should route to performance overview"
             );
@@ -658,6 +1031,9 @@ mod tests {
                 module: Some("basics".to_string()),
                 show_help: false,
                 show_performance: false,
+                timeout: None,
+                verbose: false,
+                config: None,
             },
             "non-flag arguments should be preserved as module names"
         );
@@ -675,26 +1051,343 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_args_accepts_timeout_flag_alongside_module() {
+        let args = parse_args_from(cli_args(&["rust_learn", "--timeout-ms", "250", "basics"]))
+            .expect("timeout flag with module should parse");
+
+        assert_eq!(
+            args,
+            Args {
+                module: Some("basics".to_string()),
+                show_help: false,
+                show_performance: false,
+                timeout: Some(Duration::from_millis(250)),
+                verbose: false,
+                config: None,
+            },
+            "--timeout-ms should set a per-module budget without consuming the module name"
+        );
+
+        let error = parse_args_from(cli_args(&["rust_learn", "--timeout-ms", "soon"]))
+            .expect_err("non-numeric timeout should be rejected");
+        assert_eq!(
+            error,
+            AppError::InvalidFlag("--timeout-ms 需要正整数毫秒数，收到: soon".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_args_accepts_config_flag() {
+        let args = parse_args_from(cli_args(&["rust_learn", "--config", "tasks.json"]))
+            .expect("config flag should parse");
+        assert_eq!(args.config, Some(PathBuf::from("tasks.json")));
+        assert_eq!(args.module, None);
+
+        let args = parse_args_from(cli_args(&["rust_learn", "--config=tasks.json"]))
+            .expect("inline config flag should parse");
+        assert_eq!(args.config, Some(PathBuf::from("tasks.json")));
+
+        let error = parse_args_from(cli_args(&["rust_learn", "--config=tasks.json", "basics"]))
+            .expect_err("config and module name should not be combined");
+        assert_eq!(error, AppError::TooManyArguments);
+    }
+
+    #[test]
+    fn parse_args_reports_malformed_flags_as_invalid_flag() {
+        let reject = |argv: &[&str]| {
+            parse_args_from(cli_args(argv)).expect_err("malformed flag should be rejected")
+        };
+        let invalid_flag = |reason: &str| AppError::InvalidFlag(reason.to_string());
+
+        assert_eq!(
+            reject(&["rust_learn", "--timeout-ms"]),
+            invalid_flag("--timeout-ms 缺少取值")
+        );
+        assert_eq!(
+            reject(&["rust_learn", "--timeout-ms=0"]),
+            invalid_flag("--timeout-ms 需要正整数毫秒数，收到: 0")
+        );
+        assert_eq!(
+            reject(&["rust_learn", "--config"]),
+            invalid_flag("--config 缺少配置文件路径")
+        );
+
+        let unknown = reject(&["rust_learn", "basics", "--colour"]);
+        assert_eq!(unknown, invalid_flag("未知选项 --colour"));
+        assert_eq!(unknown.exit_code(), EXIT_USAGE_ERROR);
+    }
+
+    #[test]
+    fn flag_and_config_errors_display_their_reason() {
+        assert_eq!(
+            AppError::InvalidFlag("未知选项 --colour".to_string()).to_string(),
+            "无效的命令行选项: 未知选项 --colour"
+        );
+        assert_eq!(
+            AppError::ConfigError("repeat 必须为正整数".to_string()).to_string(),
+            "配置文件错误: repeat 必须为正整数"
+        );
+    }
+
+    #[test]
+    fn parse_run_config_reads_modules_and_repeat() {
+        let config = parse_run_config(r#"{ "modules": ["basics", "types"], "repeat": 2 }"#)
+            .expect("valid config should parse");
+        assert_eq!(
+            config,
+            RunConfig {
+                modules: vec!["basics".to_string(), "types".to_string()],
+                repeat: 2,
+            }
+        );
+
+        let config =
+            parse_run_config(r#"{ "modules": ["ownership"] }"#).expect("repeat is optional");
+        assert_eq!(config.repeat, 1);
+
+        assert!(matches!(
+            parse_run_config(r#"{ "modules": ["basics"], "repeat": 0 }"#),
+            Err(AppError::ConfigError(_))
+        ));
+        assert!(matches!(
+            parse_run_config("not json"),
+            Err(AppError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn parse_run_config_rejects_unknown_modules_up_front() {
+        let error = parse_run_config(r#"{ "modules": ["basics", "not_a_real_module"] }"#)
+            .expect_err("unknown module should be rejected before running anything");
+
+        assert_eq!(
+            error,
+            AppError::UnknownModule("not_a_real_module".to_string())
+        );
+        assert_eq!(error.exit_code(), EXIT_USAGE_ERROR);
+    }
+
+    #[test]
+    fn parse_args_accepts_verbose_flag_in_any_position() {
+        let args = parse_args_from(cli_args(&["rust_learn", "basics", "--verbose"]))
+            .expect("verbose flag after module should parse");
+        assert!(args.verbose, "--verbose should enable tracing output");
+        assert_eq!(args.module.as_deref(), Some("basics"));
+
+        let args = parse_args_from(cli_args(&["rust_learn", "-v"])).expect("short alias should parse");
+        assert!(args.verbose, "-v should be an alias of --verbose");
+        assert_eq!(args.module, None, "verbose flag must not be treated as a module name");
+    }
+
+    #[test]
+    fn execute_module_records_one_span_per_module() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing_subscriber::Layer;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+
+        #[derive(Default)]
+        struct SpanFields {
+            name: Option<String>,
+            duration_ms: Option<u64>,
+        }
+
+        impl Visit for SpanFields {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "name" {
+                    self.name = Some(value.to_string());
+                }
+            }
+
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                if field.name() == "duration_ms" {
+                    self.duration_ms = Some(value);
+                }
+            }
+
+            fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+        }
+
+        #[derive(Clone, Default)]
+        struct RecordingLayer {
+            spans: Arc<Mutex<Vec<(Id, SpanFields)>>>,
+        }
+
+        impl<S: tracing::Subscriber> Layer<S> for RecordingLayer {
+            fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+                if attrs.metadata().name() == "module" {
+                    let mut fields = SpanFields::default();
+                    attrs.record(&mut fields);
+                    self.spans.lock().unwrap().push((id.clone(), fields));
+                }
+            }
+
+            fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+                let mut spans = self.spans.lock().unwrap();
+                if let Some((_, fields)) = spans.iter_mut().find(|(span_id, _)| span_id == id) {
+                    values.record(fields);
+                }
+            }
+        }
+
+        fn noop() {}
+
+        let modules = [
+            ModuleInfo {
+                name: "first",
+                description: "测试模块一",
+                stage: LearningStage::Foundation,
+                run_function: noop,
+            },
+            ModuleInfo {
+                name: "second",
+                description: "测试模块二",
+                stage: LearningStage::PracticeTracks,
+                run_function: noop,
+            },
+        ];
+
+        let layer = RecordingLayer::default();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            for module in &modules {
+                execute_module(module, None).expect("noop module should succeed");
+            }
+        });
+
+        let spans = layer.spans.lock().unwrap();
+        let names: Vec<_> = spans.iter().map(|(_, fields)| fields.name.as_deref()).collect();
+        assert_eq!(
+            names,
+            vec![Some("first"), Some("second")],
+            "each module run should open exactly one named span"
+        );
+        assert!(
+            spans.iter().all(|(_, fields)| fields.duration_ms.is_some()),
+            "each module span should carry its duration as a structured field"
+        );
+    }
+
+    #[test]
+    fn run_with_timeout_reports_completion_and_overrun() {
+        let quick = run_with_timeout(|| {}, Duration::from_secs(5));
+        assert_eq!(quick, TimedRun::Completed, "fast closures should complete within budget");
+
+        let slow = run_with_timeout(
+            || std::thread::sleep(Duration::from_millis(500)),
+            Duration::from_millis(20),
+        );
+        assert_eq!(slow, TimedRun::TimedOut, "sleeping closures should overrun a short budget");
+    }
+
+    #[test]
+    fn run_summary_maps_to_exit_codes() {
+        let all_ok = RunSummary {
+            total: 3,
+            succeeded: 3,
+            failed: Vec::new(),
+            elapsed: Duration::from_millis(30),
+        };
+        assert_eq!(all_ok.failed_count(), 0);
+        assert_eq!(all_ok.exit_code(), EXIT_SUCCESS, "fully successful runs should exit with 0");
+
+        let partial = RunSummary {
+            total: 3,
+            succeeded: 2,
+            failed: vec!["concurrency"],
+            elapsed: Duration::from_millis(30),
+        };
+        assert_eq!(partial.failed_count(), 1);
+        assert_eq!(partial.exit_code(), EXIT_MODULE_FAILURE, "any failed module should exit with 1");
+        assert_eq!(
+            AppError::ModulesFailed(partial).exit_code(),
+            EXIT_MODULE_FAILURE,
+            "the error carrying the summary should reuse its exit code"
+        );
+        assert_eq!(
+            AppError::TooManyArguments.exit_code(),
+            EXIT_USAGE_ERROR,
+            "argument errors should exit with 2"
+        );
+    }
+
+    #[test]
+    fn render_progress_bar_handles_empty_half_and_full_progress() {
+        assert_eq!(
+            render_progress_bar(0, 10, 10),
+            "[>         ] 0/10",
+            "0% progress should render only the arrow head"
+        );
+        assert_eq!(
+            render_progress_bar(5, 10, 10),
+            "[=====>    ] 5/10",
+            "50% progress should fill half of the bar"
+        );
+        assert_eq!(
+            render_progress_bar(10, 10, 10),
+            "[==========] 10/10",
+            "100% progress should fill the whole bar without an arrow head"
+        );
+    }
+
+    #[test]
+    fn format_duration_picks_unit_by_magnitude() {
+        assert_eq!(format_duration(Duration::from_micros(500)), "500.00µs");
+        assert_eq!(format_duration(Duration::from_micros(12_340)), "12.34ms");
+        assert_eq!(format_duration(Duration::from_millis(3_200)), "3.20s");
+    }
+
     #[test]
     fn resolve_module_request_distinguishes_non_cli_modules() {
         let projects_status = find_non_cli_module("projects").expect("projects should be registered");
-        let database_status = find_non_cli_module("database").expect("database should be registered");
 
         let projects_error =
             resolve_module_request("projects").expect_err("projects should be blocked from main CLI");
-        let database_error =
-            resolve_module_request("database").expect_err("database should be blocked from main CLI");
 
         assert_eq!(
             projects_error,
             AppError::UnavailableModule(projects_status),
             "projects should be reported as unavailable instead of unknown"
         );
+    }
+
+    #[cfg(feature = "database")]
+    #[test]
+    fn resolve_module_request_runs_database_when_feature_enabled() {
+        let module = resolve_module_request("database").expect("database feature registers the module");
+
+        assert_eq!(module.stage, LearningStage::PracticeTracks);
+        assert!(find_non_cli_module("database").is_none());
+    }
+
+    #[cfg(not(feature = "database"))]
+    #[test]
+    fn resolve_module_request_reports_database_built_without_feature() {
+        let database_status = find_non_cli_module("database").expect("database should be registered");
+
+        let database_error =
+            resolve_module_request("database").expect_err("database should be blocked without the feature");
+
         assert_eq!(
             database_error,
             AppError::UnavailableModule(database_status),
             "database should be reported as unavailable instead of unknown"
         );
+        assert!(database_status.summary.contains("`database` feature"));
+    }
+
+    #[test]
+    fn module_registry_length_matches_enabled_features() {
+        let expected = 15 + usize::from(cfg!(feature = "database"));
+
+        assert_eq!(MODULE_REGISTRY.len(), expected);
+        assert_eq!(
+            NON_CLI_MODULES.len(),
+            1 + usize::from(!cfg!(feature = "database")),
+            "database should move between the registry and the non-CLI list"
+        );
     }
 
     #[test]
@@ -708,4 +1401,13 @@ mod tests {
             "unknown modules should remain distinguishable from disabled modules"
         );
     }
+
+    #[cfg(feature = "no-color")]
+    #[test]
+    fn test_no_color_feature_renders_plain_text() {
+        use colored::Colorize;
+
+        apply_output_features();
+        assert_eq!("x".red().to_string(), "x");
+    }
 }
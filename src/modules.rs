@@ -15,18 +15,45 @@ pub mod farm {
         #[derive(Debug)]
         pub struct CropManager {
             crops: HashMap<String, usize>,
+            /// 所有作物的总数量上限，`None` 表示不限制
+            limit: Option<usize>,
         }
         
         impl CropManager {
+            /// 创建不限总量的作物管理器
             pub fn new() -> Self {
                 Self {
                     crops: HashMap::new(),
+                    limit: None,
                 }
             }
             
-            pub fn plant(&mut self, crop: &str, quantity: usize) {
+            /// 创建总数量不超过 `max` 的作物管理器，常配合 `modern_exports::MAX_CROPS` 使用
+            pub fn with_limit(max: usize) -> Self {
+                Self {
+                    crops: HashMap::new(),
+                    limit: Some(max),
+                }
+            }
+            
+            /// 种植作物；会使总数量超过上限时拒绝并保持原状
+            pub fn plant(&mut self, crop: &str, quantity: usize) -> Result<(), String> {
+                let new_total = self.total_quantity().saturating_add(quantity);
+                if let Some(limit) = self.limit.filter(|limit| new_total > *limit) {
+                    return Err(format!(
+                        "种植 {} x {} 后总数 {} 将超过上限 {}",
+                        crop, quantity, new_total, limit
+                    ));
+                }
+                
                 *self.crops.entry(crop.to_string()).or_insert(0) += quantity;
                 println!("🌱 种植作物: {} x {}", crop, quantity);
+                Ok(())
+            }
+            
+            /// 当前所有作物的总数量
+            pub fn total_quantity(&self) -> usize {
+                self.crops.values().sum()
             }
             
             pub fn harvest(&mut self, crop: &str, quantity: usize) -> Option<usize> {
@@ -64,8 +91,11 @@ pub mod farm {
             }
             
             pub fn manage_vegetables(manager: &mut CropManager) {
-                manager.plant("tomato", 50);
-                manager.plant("carrot", 30);
+                for (crop, quantity) in [("tomato", 50), ("carrot", 30)] {
+                    if let Err(error) = manager.plant(crop, quantity) {
+                        println!("⚠️ {}", error);
+                    }
+                }
                 manager.harvest("tomato", 10);
             }
         }
@@ -90,6 +120,8 @@ pub mod farm {
     }
     
     pub mod animals {
+        use std::time::{Duration, Instant};
+        
         /// 现代化动物管理
         #[derive(Debug, Clone)]
         pub struct Animal {
@@ -97,6 +129,8 @@ pub mod farm {
             pub species: String,
             pub age: u8,
             pub fed: bool,
+            /// 最近一次喂食的时间，从未喂食时为 `None`
+            pub last_fed: Option<Instant>,
         }
         
         impl Animal {
@@ -106,20 +140,80 @@ pub mod farm {
                     species: species.to_string(),
                     age,
                     fed: false,
+                    last_fed: None,
                 }
             }
             
             pub fn feed(&mut self) {
+                self.feed_at(Instant::now());
+            }
+            
+            /// 以指定时间记录喂食，便于测试注入时钟
+            pub fn feed_at(&mut self, at: Instant) {
                 self.fed = true;
+                self.last_fed = Some(at);
                 println!("🐕 喂食动物: {} ({})", self.name, self.species);
             }
             
+            /// 在 `now` 时刻距上次喂食是否已超过 `interval`；从未喂食视为饥饿
+            pub fn is_hungry_at(&self, interval: Duration, now: Instant) -> bool {
+                self.last_fed
+                    .is_none_or(|last_fed| now.saturating_duration_since(last_fed) >= interval)
+            }
+            
             pub fn get_info(&self) -> String {
                 format!("{} 是 {}，年龄 {} 岁，{}已喂食",
                         self.name, self.species, self.age,
                         if self.fed { "" } else { "尚未" })
             }
         }
+        
+        /// 集中饲养多只动物的畜棚
+        #[derive(Debug, Clone, Default)]
+        pub struct Barn {
+            animals: Vec<Animal>,
+        }
+        
+        impl Barn {
+            pub fn new() -> Self {
+                Self::default()
+            }
+            
+            pub fn add(&mut self, animal: Animal) {
+                self.animals.push(animal);
+            }
+            
+            pub fn animals(&self) -> &[Animal] {
+                &self.animals
+            }
+            
+            /// 喂食所有动物，返回此前尚未喂过的动物数量
+            pub fn feed_all(&mut self) -> usize {
+                self.feed_all_at(Instant::now())
+            }
+            
+            /// 以指定时间喂食所有动物，返回此前尚未喂过的动物数量
+            pub fn feed_all_at(&mut self, now: Instant) -> usize {
+                let newly_fed = self.animals.iter().filter(|animal| !animal.fed).count();
+                for animal in &mut self.animals {
+                    animal.feed_at(now);
+                }
+                newly_fed
+            }
+            
+            /// 距上次喂食已超过 `interval` 的动物
+            pub fn hungry(&self, interval: Duration) -> Vec<&Animal> {
+                self.hungry_at(interval, Instant::now())
+            }
+            
+            /// 以指定的当前时间判断哪些动物已饥饿
+            pub fn hungry_at(&self, interval: Duration, now: Instant) -> Vec<&Animal> {
+                self.animals
+                    .iter()
+                    .filter(|animal| animal.is_hungry_at(interval, now))
+                    .collect()
+            }
+        }
     }
     
     /// 全局作物数据（类似const泛型）
@@ -159,6 +253,13 @@ pub fn modern_use_patterns() {
     dog.feed();
     println!("🐕 {}", dog.get_info());
     
+    // 畜棚批量喂食
+    let mut barn = farm::animals::Barn::new();
+    barn.add(dog);
+    barn.add(Animal::new("Daisy", "奶牛", 4));
+    println!("🏠 本次新喂食 {} 只动物", barn.feed_all());
+    println!("🏠 一小时内需要再喂的动物: {}", barn.hungry(std::time::Duration::from_secs(3600)).len());
+    
     // 演示绝对路径和相对路径
     crate::modules::farm::crops::vegetables::plant_tomato(); // 绝对路径
     farm::crops::fruits::plant_apple(); // 相对路径
@@ -245,6 +346,57 @@ pub fn platform_specific() {
     println!("🌐 运行在其他平台");
 }
 
+/// 农场组合体 - 将CropManager和Animal组合在一起
+#[derive(Debug)]
+pub struct Farm {
+    crop_manager: farm::crops::CropManager,
+    animals: Vec<farm::animals::Animal>,
+    name: String,
+}
+
+impl Farm {
+    pub fn new(name: &str) -> Self {
+        println!("🏡 创建农场: {}", name);
+        Self {
+            crop_manager: farm::crops::CropManager::with_limit(modern_exports::MAX_CROPS),
+            animals: Vec::new(),
+            name: name.to_string(),
+        }
+    }
+    
+    pub fn add_animal(&mut self, animal: farm::animals::Animal) {
+        println!("➕ 添加动物: {}", animal.name);
+        self.animals.push(animal);
+    }
+    
+    /// 农场中的所有动物
+    pub fn animals(&self) -> &[farm::animals::Animal] {
+        &self.animals
+    }
+    
+    /// 按名称移除第一只匹配的动物
+    pub fn remove_animal(&mut self, name: &str) -> Option<farm::animals::Animal> {
+        let index = self.animals.iter().position(|animal| animal.name == name)?;
+        let animal = self.animals.remove(index);
+        println!("➖ 移除动物: {}", animal.name);
+        Some(animal)
+    }
+    
+    /// 喂食农场中的所有动物
+    pub fn feed_all(&mut self) {
+        for animal in &mut self.animals {
+            animal.feed();
+        }
+    }
+    
+    pub fn farm_status(&self) -> String {
+        format!("农场 '{}' - 作物: {}, 动物数量: {}",
+                self.name,
+                self.crop_manager.get_status(),
+                self.animals.len())
+    }
+}
+
 /// 现代化模块使用策略
 pub fn modular_design_patterns() {
     println!("🎯 现代化模块设计模式：");
@@ -253,46 +405,22 @@ pub fn modular_design_patterns() {
     use farm::animals::Animal;
     
     // 1. 组合模式 - 将CropManager和Animal结合
-    #[derive(Debug)]
-    pub struct Farm {
-        crop_manager: CropManager,
-        animals: Vec<Animal>,
-        name: String,
-    }
+    let mut farm = Farm::new("现代化家庭农场");
     
-    impl Farm {
-        pub fn new(name: &str) -> Self {
-            println!("🏡 创建农场: {}", name);
-            Self {
-                crop_manager: CropManager::new(),
-                animals: Vec::new(),
-                name: name.to_string(),
-            }
-        }
-        
-        pub fn add_animal(&mut self, animal: Animal) {
-            self.animals.push(animal);
-            println!("➕ 添加动物: {}", self.animals.last().unwrap().name);
-        }
-        
-        pub fn farm_status(&self) -> String {
-            format!("农场 '{}' - 作物: {}, 动物数量: {}",
-                    self.name,
-                    self.crop_manager.get_status(),
-                    self.animals.len())
+    // 添加作物（总量受 MAX_CROPS 限制，超出时会被拒绝）
+    for (crop, quantity) in [("小麦", 100), ("玉米", 80), ("大豆", modern_exports::MAX_CROPS)] {
+        if let Err(error) = farm.crop_manager.plant(crop, quantity) {
+            println!("⚠️ {}", error);
         }
     }
     
-    // 使用组合模式
-    let mut farm = Farm::new("现代化家庭农场");
-    
-    // 添加作物
-    farm.crop_manager.plant("小麦", 100);
-    farm.crop_manager.plant("玉米", 80);
-    
     // 添加动物
     farm.add_animal(Animal::new("Max", "牧羊犬", 5));
     farm.add_animal(Animal::new("Bella", "奶牛", 3));
+    farm.add_animal(Animal::new("Dolly", "绵羊", 2));
+    
+    farm.feed_all();
+    farm.remove_animal("Dolly");
     
     println!("📊 {}", farm.farm_status());
     
@@ -317,178 +445,295 @@ pub fn modular_design_patterns() {
     println!("🔧 {}", farm.animals.operate());
 }
 
-/// 演示企业级项目组织结构
-pub fn enterprise_project_structure() {
-    println!("🏢 企业级项目组织结构：");
+/// 通用仓库接口：按 `Id` 存取 `Entity`，与具体存储方式无关
+///
+/// 领域专用的仓库（如按邮箱查找用户）可以在内部组合一个实现了本 trait 的类型。
+pub trait Repository<Id, Entity> {
+    /// 按 ID 获取实体的副本
+    fn get(&self, id: &Id) -> Option<Entity>;
     
-    // 模拟电商平台项目结构
-    pub mod ecommerce {
-        use std::collections::HashMap;
-        use chrono::Utc;
-        
-        /// 用户实体
-        #[derive(Debug, Clone)]
-        pub struct User {
-            pub id: u64,
-            pub username: String,
-            pub email: String,
-            pub created_at: chrono::DateTime<chrono::Utc>,
-        }
-        
-        /// 产品实体
-        #[derive(Debug, Clone)]
-        pub struct Product {
-            pub id: u64,
-            pub name: String,
-            pub price: f64,
-            pub category: String,
-            pub stock: u32,
+    /// 所有实体的副本，顺序由实现决定
+    fn all(&self) -> Vec<Entity>;
+    
+    /// 以 `id` 保存实体，已存在时覆盖并返回旧值
+    fn save(&mut self, id: Id, entity: Entity) -> Option<Entity>;
+    
+    /// 删除并返回实体，不存在时返回 `None`
+    fn delete(&mut self, id: &Id) -> Option<Entity>;
+}
+
+/// 基于 `HashMap` 的通用内存仓库
+#[derive(Debug, Clone)]
+pub struct MemoryRepo<Id, Entity> {
+    items: std::collections::HashMap<Id, Entity>,
+}
+
+impl<Id, Entity> MemoryRepo<Id, Entity> {
+    pub fn new() -> Self {
+        Self {
+            items: std::collections::HashMap::new(),
         }
-        
-        /// 用户仓库
-        pub trait UserRepository {
-            fn find_by_id(&self, id: u64) -> Option<User>;
-            fn find_by_email(&self, email: &str) -> Option<User>;
-            fn save(&mut self, user: User) -> Result<User, String>;
+    }
+    
+    /// 返回第一个满足 `predicate` 的实体副本
+    ///
+    /// 按引用遍历存储，只克隆命中的实体；多个实体满足条件时返回哪一个由遍历顺序决定。
+    pub fn find(&self, predicate: impl Fn(&Entity) -> bool) -> Option<Entity>
+    where
+        Entity: Clone,
+    {
+        self.items.values().find(|entity| predicate(entity)).cloned()
+    }
+}
+
+impl<Id, Entity> Default for MemoryRepo<Id, Entity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id, Entity> Repository<Id, Entity> for MemoryRepo<Id, Entity>
+where
+    Id: std::hash::Hash + Eq + Clone,
+    Entity: Clone,
+{
+    fn get(&self, id: &Id) -> Option<Entity> {
+        self.items.get(id).cloned()
+    }
+    
+    fn all(&self) -> Vec<Entity> {
+        self.items.values().cloned().collect()
+    }
+    
+    fn save(&mut self, id: Id, entity: Entity) -> Option<Entity> {
+        self.items.insert(id, entity)
+    }
+    
+    fn delete(&mut self, id: &Id) -> Option<Entity> {
+        self.items.remove(id)
+    }
+}
+
+/// 企业级电商平台项目结构示例
+pub mod enterprise {
+    use chrono::Utc;
+    use serde::{Deserialize, Serialize};
+    use super::{MemoryRepo, Repository};
+    
+    /// 用户实体
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct User {
+        pub id: u64,
+        pub username: String,
+        pub email: String,
+        pub created_at: chrono::DateTime<chrono::Utc>,
+    }
+    
+    /// 产品实体
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Product {
+        pub id: u64,
+        pub name: String,
+        pub price: f64,
+        pub category: String,
+        pub stock: u32,
+    }
+    
+    /// 用户仓库
+    pub trait UserRepository {
+        fn find_by_id(&self, id: u64) -> Option<User>;
+        fn find_by_email(&self, email: &str) -> Option<User>;
+        fn save(&mut self, user: User) -> Result<User, String>;
+    }
+    
+    /// 产品仓库
+    pub trait ProductRepository {
+        fn find_by_id(&self, id: u64) -> Option<Product>;
+        fn find_all(&self) -> Vec<Product>;
+        fn save(&mut self, product: Product) -> Result<Product, String>;
+    }
+    
+    /// 内存用户仓库实现，存储委托给通用的 [`MemoryRepo`]
+    pub struct InMemoryUserRepository {
+        users: MemoryRepo<u64, User>,
+        next_id: u64,
+    }
+    
+    impl InMemoryUserRepository {
+        pub fn new() -> Self {
+            Self {
+                users: MemoryRepo::new(),
+                next_id: 1,
+            }
         }
-        
-        /// 产品仓库
-        pub trait ProductRepository {
-            fn find_by_id(&self, id: u64) -> Option<Product>;
-            fn save(&mut self, product: Product) -> Result<Product, String>;
+    }
+    
+    impl Default for InMemoryUserRepository {
+        fn default() -> Self {
+            Self::new()
         }
-        
-        /// 内存用户仓库实现
-        pub struct InMemoryUserRepository {
-            users: HashMap<u64, User>,
-            next_id: u64,
+    }
+    
+    impl UserRepository for InMemoryUserRepository {
+        fn find_by_id(&self, id: u64) -> Option<User> {
+            self.users.get(&id)
         }
         
-        impl InMemoryUserRepository {
-            pub fn new() -> Self {
-                Self {
-                    users: HashMap::new(),
-                    next_id: 1,
-                }
-            }
+        fn find_by_email(&self, email: &str) -> Option<User> {
+            self.users.find(|u| u.email == email)
         }
         
-        impl UserRepository for InMemoryUserRepository {
-            fn find_by_id(&self, id: u64) -> Option<User> {
-                self.users.get(&id).cloned()
-            }
-            
-            fn find_by_email(&self, email: &str) -> Option<User> {
-                self.users.values().find(|u| u.email == email).cloned()
+        fn save(&mut self, mut user: User) -> Result<User, String> {
+            if user.id == 0 {
+                user.id = self.next_id;
+                self.next_id += 1;
             }
             
-            fn save(&mut self, mut user: User) -> Result<User, String> {
-                if user.id == 0 {
-                    user.id = self.next_id;
-                    self.next_id += 1;
-                }
-                
-                user.created_at = Utc::now();
-                self.users.insert(user.id, user.clone());
-                Ok(user)
+            user.created_at = Utc::now();
+            self.users.save(user.id, user.clone());
+            Ok(user)
+        }
+    }
+    
+    /// 内存产品仓库实现，存储委托给通用的 [`MemoryRepo`]
+    pub struct InMemoryProductRepository {
+        products: MemoryRepo<u64, Product>,
+        next_id: u64,
+    }
+    
+    impl InMemoryProductRepository {
+        pub fn new() -> Self {
+            Self {
+                products: MemoryRepo::new(),
+                next_id: 1,
             }
         }
-        
-        /// 内存产品仓库实现
-        pub struct InMemoryProductRepository {
-            products: HashMap<u64, Product>,
-            next_id: u64,
+    }
+    
+    impl Default for InMemoryProductRepository {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+    
+    impl ProductRepository for InMemoryProductRepository {
+        fn find_by_id(&self, id: u64) -> Option<Product> {
+            self.products.get(&id)
         }
         
-        impl InMemoryProductRepository {
-            pub fn new() -> Self {
-                Self {
-                    products: HashMap::new(),
-                    next_id: 1,
-                }
-            }
+        fn find_all(&self) -> Vec<Product> {
+            let mut products = self.products.all();
+            products.sort_by_key(|p| p.id);
+            products
         }
         
-        impl ProductRepository for InMemoryProductRepository {
-            fn find_by_id(&self, id: u64) -> Option<Product> {
-                self.products.get(&id).cloned()
+        fn save(&mut self, mut product: Product) -> Result<Product, String> {
+            if product.id == 0 {
+                product.id = self.next_id;
+                self.next_id += 1;
             }
             
-            fn save(&mut self, mut product: Product) -> Result<Product, String> {
-                if product.id == 0 {
-                    product.id = self.next_id;
-                    self.next_id += 1;
-                }
-                
-                self.products.insert(product.id, product.clone());
-                Ok(product)
-            }
+            self.products.save(product.id, product.clone());
+            Ok(product)
         }
-        
-        /// 用户服务
-        pub struct UserService<R> {
-            repository: R,
+    }
+    
+    /// 用户服务
+    pub struct UserService<R> {
+        repository: R,
+    }
+    
+    impl<R: UserRepository> UserService<R> {
+        pub fn new(repository: R) -> Self {
+            Self { repository }
         }
         
-        impl<R: UserRepository> UserService<R> {
-            pub fn new(repository: R) -> Self {
-                Self { repository }
+        pub fn create_user(&mut self, username: String, email: String) -> Result<User, String> {
+            if self.repository.find_by_email(&email).is_some() {
+                return Err("邮箱已存在".to_string());
             }
             
-            pub fn create_user(&mut self, username: String, email: String) -> Result<User, String> {
-                if self.repository.find_by_email(&email).is_some() {
-                    return Err("邮箱已存在".to_string());
-                }
-                
-                let user = User {
-                    id: 0,
-                    username,
-                    email,
-                    created_at: Utc::now(),
-                };
-                
-                self.repository.save(user)
-            }
+            let user = User {
+                id: 0,
+                username,
+                email,
+                created_at: Utc::now(),
+            };
+            
+            self.repository.save(user)
         }
-        
-        /// 产品服务
-        pub struct ProductService<R> {
-            repository: R,
+    }
+    
+    /// 产品服务
+    pub struct ProductService<R> {
+        repository: R,
+    }
+    
+    impl<R: ProductRepository> ProductService<R> {
+        pub fn new(repository: R) -> Self {
+            Self { repository }
         }
         
-        impl<R: ProductRepository> ProductService<R> {
-            pub fn new(repository: R) -> Self {
-                Self { repository }
+        pub fn create_product(&mut self, name: String, price: f64, category: String, stock: u32) -> Result<Product, String> {
+            if price <= 0.0 {
+                return Err("价格必须大于0".to_string());
             }
             
-            pub fn create_product(&mut self, name: String, price: f64, category: String, stock: u32) -> Result<Product, String> {
-                if price <= 0.0 {
-                    return Err("价格必须大于0".to_string());
-                }
-                
-                let product = Product {
-                    id: 0,
-                    name,
-                    price,
-                    category,
-                    stock,
-                };
-                
-                self.repository.save(product)
+            let product = Product {
+                id: 0,
+                name,
+                price,
+                category,
+                stock,
+            };
+            
+            self.repository.save(product)
+        }
+        
+        /// 购买产品并扣减库存，库存不足或产品不存在时返回错误
+        pub fn purchase(&mut self, product_id: u64, qty: u32) -> Result<(), String> {
+            let mut product = self
+                .repository
+                .find_by_id(product_id)
+                .ok_or_else(|| format!("产品不存在: {}", product_id))?;
+            
+            if product.stock < qty {
+                return Err(format!(
+                    "库存不足: {} 剩余 {}，请求 {}",
+                    product.name, product.stock, qty
+                ));
             }
+            
+            product.stock -= qty;
+            self.repository.save(product)?;
+            Ok(())
+        }
+        
+        /// 查找价格在 `[min, max]` 闭区间内的产品，按ID排序
+        pub fn find_in_price_range(&self, min: f64, max: f64) -> Vec<Product> {
+            self.repository
+                .find_all()
+                .into_iter()
+                .filter(|p| p.price >= min && p.price <= max)
+                .collect()
         }
     }
+}
+
+/// 演示企业级项目组织结构
+pub fn enterprise_project_structure() {
+    println!("🏢 企业级项目组织结构：");
     
     // 演示企业级项目使用
     println!("🏗️ 演示电商平台项目结构:");
     
     // 创建仓库层
-    let user_repo = ecommerce::InMemoryUserRepository::new();
-    let product_repo = ecommerce::InMemoryProductRepository::new();
+    let user_repo = enterprise::InMemoryUserRepository::new();
+    let product_repo = enterprise::InMemoryProductRepository::new();
     
     // 创建服务层
-    let mut user_service = ecommerce::UserService::new(user_repo);
-    let mut product_service = ecommerce::ProductService::new(product_repo);
+    let mut user_service = enterprise::UserService::new(user_repo);
+    let mut product_service = enterprise::ProductService::new(product_repo);
     
     // 模拟用户注册
     match user_service.create_user("张三".to_string(), "zhangsan@example.com".to_string()) {
@@ -507,6 +752,22 @@ pub fn enterprise_project_structure() {
         Err(e) => println!("❌ 产品创建失败: {}", e),
     }
     
+    let _ = product_service.create_product("AirPods".to_string(), 1299.0, "耳机".to_string(), 200);
+    match product_service.purchase(1, 60) {
+        Ok(()) => println!("✅ 购买成功"),
+        Err(e) => println!("❌ 购买失败: {}", e),
+    }
+    
+    let affordable = product_service.find_in_price_range(0.0, 2000.0);
+    println!("💰 2000元以内的产品: {:?}", affordable.iter().map(|p| &p.name).collect::<Vec<_>>());
+    
+    // 通用仓库同样适用于非数字 ID，例如按 SKU 存放库存
+    let mut inventory: MemoryRepo<String, u32> = MemoryRepo::new();
+    inventory.save("SKU-001".to_string(), 12);
+    inventory.save("SKU-002".to_string(), 0);
+    inventory.delete(&"SKU-002".to_string());
+    println!("📦 SKU-001 库存: {:?}，剩余条目: {}", inventory.get(&"SKU-001".to_string()), inventory.all().len());
+    
     println!("📊 企业级项目结构演示完成");
 }
 
@@ -313,6 +313,38 @@ pub fn memory_leak_pitfalls() {
     println!("大数据已释放");
 }
 
+/// 构建并丢弃一棵 [`TreeNode`](crate::advanced_types::TreeNode) 树，返回被释放的节点数
+///
+/// 子节点由 `Rc` 持有、父节点由 `Weak` 指向，不存在引用循环，
+/// 因此丢弃根节点后所有节点都会被释放，返回值等于节点总数。
+pub fn demonstrate_weak_tree() -> usize {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use crate::advanced_types::TreeNode;
+
+    struct Counted(Rc<Cell<usize>>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    println!("🌳 Weak 父指针树的释放检查:");
+
+    let drops = Rc::new(Cell::new(0));
+    let root = TreeNode::new(Counted(Rc::clone(&drops)));
+    for _ in 0..2 {
+        let branch = TreeNode::add_child(&root, Counted(Rc::clone(&drops)));
+        TreeNode::add_child(&branch, Counted(Rc::clone(&drops)));
+    }
+
+    drop(root);
+    println!("丢弃根节点后释放了 {} 个节点（共 5 个），没有泄漏", drops.get());
+    drops.get()
+}
+
 /// 错误处理陷阱
 pub fn error_handling_pitfalls() {
     println!("🚨 错误处理陷阱：");
@@ -568,6 +600,9 @@ pub fn run_pitfalls_examples() {
     memory_leak_pitfalls();
     println!();
 
+    demonstrate_weak_tree();
+    println!();
+
     error_handling_pitfalls();
     println!();
 
@@ -9,7 +9,6 @@ use std::sync::OnceLock;
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use clap::Parser;
-use reqwest;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use thiserror::Error;
@@ -17,6 +16,12 @@ use tracing::{debug, error, info, trace, warn};
 use tracing_subscriber::{self, EnvFilter};
 use uuid::Uuid;
 
+#[cfg(feature = "http")]
+mod http;
+
+#[cfg(feature = "http")]
+pub use http::{demonstrate_http_requests, get_json_retry, get_text_limited};
+
 /// 现代化结构体定义（使用Serde特性）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -35,6 +40,87 @@ pub struct UserPreferences {
     pub notifications: bool,
 }
 
+impl User {
+    /// 创建用户构建器
+    pub fn builder() -> UserBuilder {
+        UserBuilder::default()
+    }
+}
+
+/// `User` 构建器，偏好设置未指定时使用默认值
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    id: u32,
+    name: String,
+    email: String,
+    theme: String,
+    language: String,
+    notifications: bool,
+}
+
+impl Default for UserBuilder {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: String::new(),
+            email: String::new(),
+            theme: "light".to_string(),
+            language: "zh-CN".to_string(),
+            notifications: true,
+        }
+    }
+}
+
+impl UserBuilder {
+    pub fn id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = email.to_string();
+        self
+    }
+
+    pub fn theme(mut self, theme: &str) -> Self {
+        self.theme = theme.to_string();
+        self
+    }
+
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
+    pub fn notifications(mut self, enabled: bool) -> Self {
+        self.notifications = enabled;
+        self
+    }
+
+    /// 校验邮箱（复用 `security::validate_email`）并构建用户，`created_at` 取当前时间
+    pub fn build(self) -> std::result::Result<User, LibraryError> {
+        let email =
+            crate::security::validate_email(&self.email).map_err(LibraryError::InvalidUser)?;
+
+        Ok(User {
+            id: self.id,
+            name: self.name,
+            email,
+            created_at: Utc::now(),
+            preferences: UserPreferences {
+                theme: self.theme,
+                language: self.language,
+                notifications: self.notifications,
+            },
+        })
+    }
+}
+
 /// 命令行参数结构体
 #[derive(Parser, Debug)]
 #[command(name = "rust-popular-libs")]
@@ -54,6 +140,56 @@ pub struct CliArgs {
     pub url: Option<String>,
 }
 
+impl CliArgs {
+    /// 创建参数构建器，便于在测试或嵌入场景中不经过 argv 构造参数
+    pub fn builder() -> CliArgsBuilder {
+        CliArgsBuilder::default()
+    }
+}
+
+/// `CliArgs` 构建器，未指定操作时默认为 `Serialize`
+#[derive(Debug, Clone)]
+pub struct CliArgsBuilder {
+    operation: Operation,
+    verbose: bool,
+    url: Option<String>,
+}
+
+impl Default for CliArgsBuilder {
+    fn default() -> Self {
+        Self {
+            operation: Operation::Serialize,
+            verbose: false,
+            url: None,
+        }
+    }
+}
+
+impl CliArgsBuilder {
+    pub fn operation(mut self, operation: Operation) -> Self {
+        self.operation = operation;
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    pub fn build(self) -> CliArgs {
+        CliArgs {
+            operation: self.operation,
+            verbose: self.verbose,
+            url: self.url,
+        }
+    }
+}
+
 /// 操作类型枚举
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum Operation {
@@ -77,6 +213,7 @@ pub enum LibraryError {
     #[error("序列化错误: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[cfg(feature = "http")]
     #[error("网络请求错误: {0}")]
     Network(#[from] reqwest::Error),
 
@@ -88,6 +225,12 @@ pub enum LibraryError {
 
     #[error("API返回错误状态码: {0}")]
     HttpStatus(u16),
+
+    #[error("无效的用户数据: {0}")]
+    InvalidUser(String),
+
+    #[error("响应体超过 {limit} 字节上限")]
+    ResponseTooLarge { limit: usize },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -96,6 +239,11 @@ enum TracingInitState {
     ExternalSubscriber,
 }
 
+/// 未启用 `http` 特性时，HTTP 演示入口给出的提示
+#[cfg(not(feature = "http"))]
+const HTTP_DISABLED_NOTICE: &str =
+    "当前构建未启用 `http` 特性，HTTP 请求演示不可用；请使用 `--features http` 重新构建";
+
 static TRACING_INIT_STATE: OnceLock<TracingInitState> = OnceLock::new();
 
 fn ensure_tracing_initialized(verbose: bool) -> TracingInitState {
@@ -131,60 +279,26 @@ fn ensure_tracing_initialized(verbose: bool) -> TracingInitState {
     state
 }
 
-fn truncate_for_output(content: &str, max_chars: usize) -> String {
-    let trimmed = content.trim();
-    let mut truncated = trimmed.chars().take(max_chars).collect::<String>();
-    if trimmed.chars().count() > max_chars {
-        truncated.push_str("...");
-    }
-    truncated
-}
-
-async fn ensure_success_response(
-    response: reqwest::Response,
-    operation: &str,
-) -> Result<reqwest::Response> {
-    let status = response.status();
-    if status.is_success() {
-        return Ok(response);
-    }
-
-    let body = response
-        .text()
-        .await
-        .unwrap_or_else(|error| format!("<读取错误响应体失败: {}>", error));
-    let body_snippet = truncate_for_output(&body, 200);
-    let visible_body = if body_snippet.is_empty() {
-        "<空响应体>".to_string()
-    } else {
-        body_snippet
-    };
-
-    error!(operation, status = %status, body = %visible_body, "HTTP 请求失败");
-    Err(anyhow!(
-        "{} 返回非成功状态 {}，响应片段: {}",
-        operation,
-        status,
-        visible_body
-    ))
+/// 以普通日志配置安装全局 tracing 订阅器，可在任意模块中重复调用。
+///
+/// 订阅器最多安装一次；若外部已安装订阅器则沿用现有配置，不会 panic。
+pub fn init_tracing_once() {
+    let _ = ensure_tracing_initialized(false);
 }
 
-/// 演示现代化Serde序列化
-pub fn demonstrate_serde_serialization() -> Result<()> {
+/// 演示现代化Serde序列化，返回示例用户的 JSON 字符串
+pub fn demonstrate_serde_serialization() -> Result<String> {
     println!("🔄 演示现代化Serde序列化:");
 
     // 创建用户数据
-    let user = User {
-        id: 1,
-        name: "张三".to_string(),
-        email: "zhangsan@example.com".to_string(),
-        created_at: Utc::now(),
-        preferences: UserPreferences {
-            theme: "dark".to_string(),
-            language: "zh-CN".to_string(),
-            notifications: true,
-        },
-    };
+    let user = User::builder()
+        .id(1)
+        .name("张三")
+        .email("zhangsan@example.com")
+        .theme("dark")
+        .language("zh-CN")
+        .notifications(true)
+        .build()?;
 
     // 序列化为JSON
     let json_string = serde_json::to_string_pretty(&user).context("序列化用户数据失败")?;
@@ -218,11 +332,11 @@ pub fn demonstrate_serde_serialization() -> Result<()> {
     println!("\n📊 复杂结构序列化:");
     println!("{}", serde_json::to_string_pretty(&user_data)?);
 
-    Ok(())
+    Ok(json_string)
 }
 
-/// 演示现代化Serde反序列化
-pub fn demonstrate_serde_deserialization() -> Result<()> {
+/// 演示现代化Serde反序列化，返回解析出的用户
+pub fn demonstrate_serde_deserialization() -> Result<User> {
     println!("🔄 演示现代化Serde反序列化:");
 
     // JSON字符串
@@ -257,63 +371,11 @@ pub fn demonstrate_serde_deserialization() -> Result<()> {
         }
     );
 
-    Ok(())
-}
-
-/// 演示现代化HTTP请求
-pub async fn demonstrate_http_requests() -> Result<()> {
-    println!("🌐 演示现代化HTTP请求:");
-
-    let client = reqwest::Client::new();
-
-    // GET请求示例
-    println!("📡 发送GET请求...");
-    let response = client
-        .get("https://httpbin.org/get")
-        .header("User-Agent", "Rust Popular Libraries Demo")
-        .send()
-        .await
-        .context("发送GET请求失败")?;
-    let response = ensure_success_response(response, "GET https://httpbin.org/get").await?;
-    let status = response.status();
-    let data: serde_json::Value = response
-        .json()
-        .await
-        .context("解析GET响应JSON失败")?;
-    println!("✅ GET请求成功:");
-    println!("  状态码: {}", status);
-    println!("  响应: {}", serde_json::to_string_pretty(&data)?);
-
-    // POST请求示例
-    println!("\n📤 发送POST请求...");
-    let post_data = json!({
-        "name": "测试用户",
-        "email": "test@example.com",
-        "timestamp": Utc::now()
-    });
+    Ok(user)
+}
 
-    let post_response = client
-        .post("https://httpbin.org/post")
-        .json(&post_data)
-        .send()
-        .await
-        .context("发送POST请求失败")?;
-    let post_response =
-        ensure_success_response(post_response, "POST https://httpbin.org/post").await?;
-    let post_status = post_response.status();
-    let post_result: serde_json::Value = post_response
-        .json()
-        .await
-        .context("解析POST响应JSON失败")?;
-    println!("✅ POST请求成功:");
-    println!("  状态码: {}", post_status);
-    println!("  响应: {}", serde_json::to_string_pretty(&post_result)?);
-
-    Ok(())
-}
-
-/// 演示现代化错误处理
-pub fn demonstrate_error_handling() -> Result<()> {
+/// 演示现代化错误处理，读取成功时返回文件内容
+pub fn demonstrate_error_handling() -> Result<String> {
     println!("🚨 演示现代化错误处理:");
 
     // 使用Anyhow进行错误处理
@@ -342,11 +404,11 @@ pub fn demonstrate_error_handling() -> Result<()> {
 
     println!("📄 文件内容: {}", file_content);
 
-    Ok(())
+    Ok(file_content)
 }
 
-/// 演示现代化命令行解析
-pub fn demonstrate_cli_parsing(args: &CliArgs) {
+/// 演示现代化命令行解析，返回解析出的操作
+pub fn demonstrate_cli_parsing(args: &CliArgs) -> Operation {
     println!("⚡ 演示现代化命令行解析:");
     println!("  操作: {:?}", args.operation);
     println!("  详细模式: {}", args.verbose);
@@ -369,6 +431,8 @@ pub fn demonstrate_cli_parsing(args: &CliArgs) {
         }
         _ => println!("📋 其他操作"),
     }
+
+    args.operation.clone()
 }
 
 /// 演示现代化日志记录。
@@ -429,7 +493,7 @@ pub fn demonstrate_tracing(verbose: bool) {
 }
 
 /// 演示UUID和日期时间操作
-pub fn demonstrate_datetime_uuid() {
+pub fn demonstrate_datetime_uuid() -> Uuid {
     println!("🕐 演示日期时间和UUID:");
 
     // UUID生成
@@ -458,10 +522,144 @@ pub fn demonstrate_datetime_uuid() {
     // 时间计算
     let duration = tomorrow - yesterday;
     println!("⏰ 时间间隔: {} 小时", duration.num_hours());
+
+    user_uuid
+}
+
+/// 按 JSON Pointer 路径读取嵌套值，路径不存在时返回 `None`
+///
+/// 在 [`serde_json::Value::pointer`] 之上放宽了写法：前导 `/` 可以省略，
+/// 因此 `"users/0/name"` 与 `"/users/0/name"` 等价；空路径返回根值本身。
+pub fn json_get<'a>(value: &'a serde_json::Value, pointer: &str) -> Option<&'a serde_json::Value> {
+    if pointer.is_empty() || pointer.starts_with('/') {
+        value.pointer(pointer)
+    } else {
+        value.pointer(&format!("/{}", pointer))
+    }
+}
+
+/// 按路径读取字符串值，值不存在或不是字符串时返回 `None`
+pub fn json_get_str<'a>(value: &'a serde_json::Value, pointer: &str) -> Option<&'a str> {
+    json_get(value, pointer).and_then(serde_json::Value::as_str)
+}
+
+/// 按路径读取无符号整数值，值不存在或不是非负整数时返回 `None`
+pub fn json_get_u64(value: &serde_json::Value, pointer: &str) -> Option<u64> {
+    json_get(value, pointer).and_then(serde_json::Value::as_u64)
+}
+
+/// [`json_diff`] 报告的一处差异，`pointer` 为 JSON Pointer 路径（根为 `""`）
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonDiff {
+    /// 仅存在于新值中的键或数组元素
+    Added {
+        pointer: String,
+        value: serde_json::Value,
+    },
+    /// 仅存在于旧值中的键或数组元素
+    Removed {
+        pointer: String,
+        value: serde_json::Value,
+    },
+    /// 两边都存在但取值不同（类型不同也视为变化）
+    Changed {
+        pointer: String,
+        from: serde_json::Value,
+        to: serde_json::Value,
+    },
+}
+
+/// 比较两个 JSON 值，返回从 `a` 到 `b` 的结构化差异
+///
+/// 对象按键递归比较，数组按下标逐个比较；其余情况只要不相等就报告为 `Changed`。
+/// 路径中的 `~` 与 `/` 按 RFC 6901 转义，可直接用于 [`json_get`]。
+pub fn json_diff(a: &serde_json::Value, b: &serde_json::Value) -> Vec<JsonDiff> {
+    let mut diffs = Vec::new();
+    collect_json_diff(a, b, String::new(), &mut diffs);
+    diffs
 }
 
-/// 运行热门库演示
-pub async fn run_popular_libraries_demo(args: &CliArgs) -> Result<()> {
+/// 在父路径后追加一段按 RFC 6901 转义的引用片段
+fn child_pointer(parent: &str, token: &str) -> String {
+    format!("{}/{}", parent, token.replace('~', "~0").replace('/', "~1"))
+}
+
+fn collect_json_diff(
+    a: &serde_json::Value,
+    b: &serde_json::Value,
+    pointer: String,
+    diffs: &mut Vec<JsonDiff>,
+) {
+    use serde_json::Value;
+
+    match (a, b) {
+        (Value::Object(left), Value::Object(right)) => {
+            for (key, left_value) in left {
+                let child = child_pointer(&pointer, key);
+                match right.get(key) {
+                    Some(right_value) => collect_json_diff(left_value, right_value, child, diffs),
+                    None => diffs.push(JsonDiff::Removed {
+                        pointer: child,
+                        value: left_value.clone(),
+                    }),
+                }
+            }
+            for (key, right_value) in right {
+                if !left.contains_key(key) {
+                    diffs.push(JsonDiff::Added {
+                        pointer: child_pointer(&pointer, key),
+                        value: right_value.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let child = child_pointer(&pointer, &index.to_string());
+                match (left.get(index), right.get(index)) {
+                    (Some(left_value), Some(right_value)) => {
+                        collect_json_diff(left_value, right_value, child, diffs)
+                    }
+                    (Some(left_value), None) => diffs.push(JsonDiff::Removed {
+                        pointer: child,
+                        value: left_value.clone(),
+                    }),
+                    (None, Some(right_value)) => diffs.push(JsonDiff::Added {
+                        pointer: child,
+                        value: right_value.clone(),
+                    }),
+                    (None, None) => unreachable!("下标小于两数组长度的最大值"),
+                }
+            }
+        }
+        _ if a != b => diffs.push(JsonDiff::Changed {
+            pointer,
+            from: a.clone(),
+            to: b.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// [`run_popular_libraries_demo`] 中各操作分支的执行结果
+#[derive(Debug, Clone)]
+pub enum OperationOutput {
+    /// 序列化得到的用户 JSON
+    Serialized(String),
+    /// 反序列化得到的用户
+    Deserialized(User),
+    /// HTTP 响应体
+    HttpBody(String),
+    /// 命令行解析得到的操作
+    CliParsed(Operation),
+    /// 错误处理演示中成功读取的文件内容
+    ErrorsHandled(String),
+    /// 日志演示中生成的用户 UUID
+    Traced(Uuid),
+}
+
+/// 运行热门库演示，返回所选操作的结构化结果
+pub async fn run_popular_libraries_demo(args: &CliArgs) -> Result<OperationOutput> {
     println!("🎯 === Rust热门库演示 ===");
     println!();
 
@@ -469,61 +667,33 @@ pub async fn run_popular_libraries_demo(args: &CliArgs) -> Result<()> {
         let _ = ensure_tracing_initialized(args.verbose);
     }
 
-    match args.operation {
-        Operation::Serialize => {
-            demonstrate_serde_serialization()?;
-        }
+    let output = match args.operation {
+        Operation::Serialize => OperationOutput::Serialized(demonstrate_serde_serialization()?),
         Operation::Deserialize => {
-            demonstrate_serde_deserialization()?;
+            OperationOutput::Deserialized(demonstrate_serde_deserialization()?)
         }
+        #[cfg(feature = "http")]
         Operation::Http => {
             if let Some(ref url) = args.url {
-                demonstrate_http_requests_with_url(url).await?;
+                OperationOutput::HttpBody(http::demonstrate_http_requests_with_url(url).await?)
             } else {
                 return Err(anyhow!("HTTP操作需要URL参数"));
             }
         }
-        Operation::Cli => {
-            demonstrate_cli_parsing(args);
-        }
-        Operation::Errors => {
-            demonstrate_error_handling()?;
+        #[cfg(not(feature = "http"))]
+        Operation::Http => {
+            return Err(anyhow!("{}", HTTP_DISABLED_NOTICE));
         }
+        Operation::Cli => OperationOutput::CliParsed(demonstrate_cli_parsing(args)),
+        Operation::Errors => OperationOutput::ErrorsHandled(demonstrate_error_handling()?),
         Operation::Tracing => {
             demonstrate_tracing(args.verbose);
-            demonstrate_datetime_uuid();
+            OperationOutput::Traced(demonstrate_datetime_uuid())
         }
-    }
+    };
 
     println!("\n✅ 热门库演示完成！");
-    Ok(())
-}
-
-/// 带URL的HTTP请求演示。
-///
-/// 对非 2xx 响应会返回错误，而不是仅打印后继续伪装成成功。
-async fn demonstrate_http_requests_with_url(url: &str) -> Result<()> {
-    println!("🌐 演示HTTP请求到: {}", url);
-
-    let client = reqwest::Client::new();
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .with_context(|| format!("发送请求失败: {}", url))?;
-    let response = ensure_success_response(response, &format!("GET {}", url)).await?;
-
-    let text = response
-        .text()
-        .await
-        .with_context(|| format!("读取响应体失败: {}", url))?;
-    println!("✅ 响应内容 (前200字符):");
-    println!("{}", truncate_for_output(&text, 200));
-    if text.chars().count() > 200 {
-        println!("... (内容已截断)");
-    }
-
-    Ok(())
+    Ok(output)
 }
 
 /// 演示所有库的完整示例
@@ -586,6 +756,9 @@ pub fn run_popular_libraries_examples() {
     println!("════════════════════════════════════════");
     demonstrate_tracing(true);
 
+    #[cfg(not(feature = "http"))]
+    println!("\nℹ️ {}", HTTP_DISABLED_NOTICE);
+
     println!("\n✅ 所有热门库示例演示完成！");
 }
 
@@ -618,14 +791,19 @@ fn demonstrate_json_processing() {
     if let Some(arr) = user_list.as_array() {
         println!("\n👥 用户列表访问:");
         for (idx, user) in arr.iter().enumerate() {
-            if let Some(name) = user.get("name").and_then(|v| v.as_str()) {
-                if let Some(age) = user.get("age").and_then(|v| v.as_u64()) {
-                    println!("  [{}] {} - 年龄: {}", idx + 1, name, age);
-                }
+            let name = json_get_str(user, "name");
+            let age = json_get_u64(user, "age");
+            if let (Some(name), Some(age)) = (name, age) {
+                println!("  [{}] {} - 年龄: {}", idx + 1, name, age);
             }
         }
     }
 
+    // 按路径直接读取嵌套值
+    if let Some(skill) = json_get_str(&user_list, "1/skills/0") {
+        println!("\n🔎 路径 1/skills/0 -> {}", skill);
+    }
+
     // JSON修改
     let mut modified_json = user_list.clone();
     if let Some(obj) = modified_json.get_mut(0).and_then(|v| v.as_object_mut()) {
@@ -634,4 +812,9 @@ fn demonstrate_json_processing() {
 
     println!("\n✏️ 修改后的JSON:");
     println!("{}", serde_json::to_string_pretty(&modified_json).unwrap());
+
+    println!("\n🧾 修改前后的结构化差异:");
+    for diff in json_diff(&user_list, &modified_json) {
+        println!("  {:?}", diff);
+    }
 }
@@ -0,0 +1,214 @@
+//! HTTP 网络演示与带保护的请求辅助函数。
+//!
+//! 仅在启用 `http` 特性（默认开启）时编译，关闭后不再依赖 reqwest。
+
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tracing::{error, warn};
+
+use super::LibraryError;
+use crate::best_practices::RetryPolicy;
+
+/// 演示请求允许读取的最大响应体字节数
+const MAX_DEMO_RESPONSE_BYTES: usize = 1024 * 1024;
+
+fn truncate_for_output(content: &str, max_chars: usize) -> String {
+    let trimmed = content.trim();
+    let mut truncated = trimmed.chars().take(max_chars).collect::<String>();
+    if trimmed.chars().count() > max_chars {
+        truncated.push_str("...");
+    }
+    truncated
+}
+
+async fn ensure_success_response(
+    response: reqwest::Response,
+    operation: &str,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|error| format!("<读取错误响应体失败: {}>", error));
+    let body_snippet = truncate_for_output(&body, 200);
+    let visible_body = if body_snippet.is_empty() {
+        "<空响应体>".to_string()
+    } else {
+        body_snippet
+    };
+
+    error!(operation, status = %status, body = %visible_body, "HTTP 请求失败");
+    Err(anyhow!(
+        "{} 返回非成功状态 {}，响应片段: {}",
+        operation,
+        status,
+        visible_body
+    ))
+}
+
+/// 演示现代化HTTP请求
+pub async fn demonstrate_http_requests() -> Result<()> {
+    println!("🌐 演示现代化HTTP请求:");
+
+    let client = reqwest::Client::new();
+
+    // GET请求示例
+    println!("📡 发送GET请求...");
+    let response = client
+        .get("https://httpbin.org/get")
+        .header("User-Agent", "Rust Popular Libraries Demo")
+        .send()
+        .await
+        .context("发送GET请求失败")?;
+    let response = ensure_success_response(response, "GET https://httpbin.org/get").await?;
+    let status = response.status();
+    let data: serde_json::Value = response
+        .json()
+        .await
+        .context("解析GET响应JSON失败")?;
+    println!("✅ GET请求成功:");
+    println!("  状态码: {}", status);
+    println!("  响应: {}", serde_json::to_string_pretty(&data)?);
+
+    // POST请求示例
+    println!("\n📤 发送POST请求...");
+    let post_data = json!({
+        "name": "测试用户",
+        "email": "test@example.com",
+        "timestamp": Utc::now()
+    });
+
+    let post_response = client
+        .post("https://httpbin.org/post")
+        .json(&post_data)
+        .send()
+        .await
+        .context("发送POST请求失败")?;
+    let post_response =
+        ensure_success_response(post_response, "POST https://httpbin.org/post").await?;
+    let post_status = post_response.status();
+    let post_result: serde_json::Value = post_response
+        .json()
+        .await
+        .context("解析POST响应JSON失败")?;
+    println!("✅ POST请求成功:");
+    println!("  状态码: {}", post_status);
+    println!("  响应: {}", serde_json::to_string_pretty(&post_result)?);
+
+    Ok(())
+}
+
+/// 按 [`RetryPolicy`] 重试的 JSON GET 请求
+///
+/// 网络错误与 5xx 响应会在退避后重试；4xx 属于客户端错误，立即返回
+/// [`LibraryError::HttpStatus`]。响应体无法解析为 `T` 时同样不会重试。
+pub async fn get_json_retry<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+    policy: RetryPolicy,
+) -> std::result::Result<T, LibraryError> {
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        let error = match get_body_once(client, url).await {
+            Ok(body) => return Ok(serde_json::from_slice(&body)?),
+            Err(error) => error,
+        };
+        let retryable = match &error {
+            LibraryError::Network(_) => true,
+            LibraryError::HttpStatus(status) => *status >= 500,
+            _ => false,
+        };
+        if !retryable || attempt >= max_attempts {
+            return Err(error);
+        }
+
+        warn!(url, attempt, error = %error, "GET 请求失败，准备重试");
+        tokio::time::sleep(policy.delay_for(attempt)).await;
+        attempt += 1;
+    }
+}
+
+async fn get_body_once(
+    client: &reqwest::Client,
+    url: &str,
+) -> std::result::Result<Vec<u8>, LibraryError> {
+    let response = client.get(url).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(LibraryError::HttpStatus(status.as_u16()));
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// 读取文本响应体，累计超过 `max_bytes` 字节时立即停止下载
+///
+/// 若响应声明的 `Content-Length` 已超限，则不读取响应体直接返回
+/// [`LibraryError::ResponseTooLarge`]；非 2xx 响应返回 [`LibraryError::HttpStatus`]。
+pub async fn get_text_limited(
+    client: &reqwest::Client,
+    url: &str,
+    max_bytes: usize,
+) -> std::result::Result<String, LibraryError> {
+    let response = client.get(url).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(LibraryError::HttpStatus(status.as_u16()));
+    }
+    read_text_limited(response, max_bytes).await
+}
+
+async fn read_text_limited(
+    mut response: reqwest::Response,
+    max_bytes: usize,
+) -> std::result::Result<String, LibraryError> {
+    let too_large = LibraryError::ResponseTooLarge { limit: max_bytes };
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(too_large);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(too_large);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// 带URL的HTTP请求演示。
+///
+/// 对非 2xx 响应会返回错误，而不是仅打印后继续伪装成成功；成功时返回完整响应体。
+pub(super) async fn demonstrate_http_requests_with_url(url: &str) -> Result<String> {
+    println!("🌐 演示HTTP请求到: {}", url);
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("发送请求失败: {}", url))?;
+    let response = ensure_success_response(response, &format!("GET {}", url)).await?;
+
+    let text = read_text_limited(response, MAX_DEMO_RESPONSE_BYTES)
+        .await
+        .with_context(|| format!("读取响应体失败: {}", url))?;
+    println!("✅ 响应内容 (前200字符):");
+    println!("{}", truncate_for_output(&text, 200));
+    if text.chars().count() > 200 {
+        println!("... (内容已截断)");
+    }
+
+    Ok(text)
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use super::TaskManager;
+use super::model::{ColorTheme, Priority, Status, Task};
+use super::storage::TaskStore;
+
+/// 任务管理器命令行定义
+#[derive(Parser, Debug)]
+#[command(name = "task-manager")]
+#[command(about = "命令行任务管理器")]
+pub struct TaskCli {
+    #[command(subcommand)]
+    pub command: TaskCommand,
+}
+
+/// 任务管理器支持的子命令
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum TaskCommand {
+    /// 添加任务
+    Add {
+        #[arg(long)]
+        title: String,
+        #[arg(long, default_value = "medium")]
+        priority: Priority,
+        /// 可重复传入多个标签
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// 列出任务，可按状态过滤
+    List {
+        #[arg(long)]
+        status: Option<Status>,
+    },
+    /// 完成任务
+    Complete { id: u64 },
+    /// 删除任务
+    Delete { id: u64 },
+    /// 显示统计信息
+    Stats,
+}
+
+/// 执行一条命令，返回需要展示给用户的输出文本。
+pub fn run_cli<S: TaskStore>(args: TaskCli, manager: &mut TaskManager<S>) -> Result<String> {
+    match args.command {
+        TaskCommand::Add {
+            title,
+            priority,
+            tags,
+        } => {
+            let id = manager.add_task(Task::new(0, title.as_str(), priority).with_tags(tags))?;
+            Ok(format!("Added task #{}: {}", id, title))
+        }
+        TaskCommand::List { status } => {
+            let theme = ColorTheme::default();
+            let lines = manager
+                .list_tasks(status)
+                .into_iter()
+                .map(|task| task.display(&theme))
+                .collect::<Vec<_>>();
+            Ok(lines.join("\n"))
+        }
+        TaskCommand::Complete { id } => {
+            manager.complete_task(id)?;
+            Ok(format!("Completed task #{}", id))
+        }
+        TaskCommand::Delete { id } => {
+            let task = manager.delete_task(id)?;
+            Ok(format!("Deleted task #{}: {}", id, task.title()))
+        }
+        TaskCommand::Stats => {
+            let stats = manager.get_statistics();
+            Ok(format!(
+                "Total: {}, Completed: {}, Pending: {}, In progress: {}, Urgent: {}",
+                stats.total, stats.completed, stats.pending, stats.in_progress, stats.urgent
+            ))
+        }
+    }
+}
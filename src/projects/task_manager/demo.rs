@@ -1,6 +1,6 @@
 use colored::Colorize;
 
-use super::{Priority, Task, TaskManager};
+use super::{ColorTheme, Priority, Task, TaskManager};
 
 /// 运行任务管理器演示
 pub fn run_task_manager_demo() -> anyhow::Result<()> {
@@ -8,6 +8,7 @@ pub fn run_task_manager_demo() -> anyhow::Result<()> {
     println!("{}", "═══════════════════════════════════════".dimmed());
 
     let mut manager = TaskManager::new()?;
+    let theme = ColorTheme::default();
 
     // 添加示例任务
     println!("\n{}", "Adding sample tasks...".cyan());
@@ -42,7 +43,7 @@ pub fn run_task_manager_demo() -> anyhow::Result<()> {
     // 列出所有任务
     println!("\n{}", "All Tasks:".bold().underline());
     for task in manager.list_tasks(None) {
-        println!("  {}", task.display());
+        println!("  {}", task.display(&theme));
     }
 
     // 显示统计
@@ -51,7 +52,18 @@ pub fn run_task_manager_demo() -> anyhow::Result<()> {
     // 搜索任务
     println!("\n{}", "Search results for 'rust':".bold().underline());
     for task in manager.search_tasks("rust") {
-        println!("  {}", task.display());
+        println!("  {}", task.display(&theme));
+    }
+
+    // 模糊搜索：标题拼错一个字母也能找到
+    println!(
+        "\n{}",
+        "Fuzzy results for 'Updte documentation':"
+            .bold()
+            .underline()
+    );
+    for task in manager.fuzzy_search("Updte documentation", 2) {
+        println!("  {}", task.display(&theme));
     }
 
     // 清理
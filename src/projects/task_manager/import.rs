@@ -0,0 +1,130 @@
+use anyhow::{Result, bail};
+use chrono::{DateTime, Local};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use super::model::{Priority, Status, Task};
+use super::storage::PersistState;
+
+/// 存储文件信封（[`PersistState`]）的顶层字段。
+const ENVELOPE_FIELDS: [&str; 3] = ["version", "next_id", "tasks"];
+
+/// 导入结果汇总
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// 成功导入的记录数
+    pub imported: usize,
+    /// 因校验失败被跳过的记录数
+    pub skipped: usize,
+    /// `(记录序号, 错误说明)`，同一条记录的多个无效字段分别列出
+    pub errors: Vec<(usize, String)>,
+}
+
+/// 把导入文件拆成记录列表，支持任务数组、旧版 `{ id: task }` 对象，
+/// 以及 `TaskManager::save` 写出的 `{ version, next_id, tasks }` 信封。
+///
+/// 与存储层的解析规则一致：出现任一信封字段即按信封处理，不允许再混入任务 ID。
+pub(crate) fn split_records(json: &str) -> Result<Vec<Value>> {
+    match serde_json::from_str(json)? {
+        Value::Object(fields) if is_envelope(&fields) => envelope_records(fields),
+        Value::Array(records) => Ok(records),
+        Value::Object(records) => Ok(records.into_iter().map(|(_, record)| record).collect()),
+        _ => bail!("Import file must contain a JSON array or object of tasks"),
+    }
+}
+
+fn is_envelope(fields: &Map<String, Value>) -> bool {
+    ENVELOPE_FIELDS.iter().any(|key| fields.contains_key(*key))
+}
+
+fn envelope_records(mut fields: Map<String, Value>) -> Result<Vec<Value>> {
+    if let Some(key) = fields
+        .keys()
+        .find(|key| !ENVELOPE_FIELDS.contains(&key.as_str()))
+    {
+        bail!("Import file mixes envelope fields with task id `{key}`");
+    }
+
+    let version = fields.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version > u64::from(PersistState::CURRENT_VERSION) {
+        bail!("Unsupported task file version {version}");
+    }
+
+    match fields.remove("tasks") {
+        Some(Value::Object(tasks)) => Ok(tasks.into_iter().map(|(_, task)| task).collect()),
+        Some(_) => bail!("Envelope field `tasks` must be an object of tasks"),
+        None => bail!("Envelope is missing the `tasks` field"),
+    }
+}
+
+/// 逐字段校验单条记录，全部字段有效时构造任务，否则返回所有无效字段的说明。
+pub(crate) fn parse_record(record: &Value) -> std::result::Result<Task, Vec<String>> {
+    let Some(fields) = record.as_object() else {
+        return Err(vec!["record: expected a JSON object".to_string()]);
+    };
+    let mut errors = Vec::new();
+
+    let title = match fields.get("title") {
+        Some(Value::String(title)) if !title.trim().is_empty() => Some(title.clone()),
+        Some(Value::String(_)) => {
+            errors.push("title: must not be empty".to_string());
+            None
+        }
+        Some(other) => {
+            errors.push(format!("title: expected a string, got {other}"));
+            None
+        }
+        None => {
+            errors.push("title: missing".to_string());
+            None
+        }
+    };
+
+    let priority = parse_field(fields, "priority", Priority::Medium, &mut errors);
+    let status = parse_field(fields, "status", Status::Pending, &mut errors);
+    let description = parse_field::<Option<String>>(fields, "description", None, &mut errors);
+    let tags = parse_field(fields, "tags", Vec::<String>::new(), &mut errors);
+    let due_date = parse_field::<Option<DateTime<Local>>>(fields, "due_date", None, &mut errors);
+
+    match (title, priority, status, description, tags, due_date) {
+        (
+            Some(title),
+            Some(priority),
+            Some(status),
+            Some(description),
+            Some(tags),
+            Some(due_date),
+        ) => {
+            let mut task = Task::new(0, title, priority).with_tags(tags);
+            task.set_description(description);
+            task.set_due_date(due_date);
+            match status {
+                Status::Pending => {}
+                Status::InProgress => task.start(),
+                Status::Completed => task.complete(),
+                Status::Cancelled => task.cancel(),
+            }
+            Ok(task)
+        }
+        _ => Err(errors),
+    }
+}
+
+/// 解析可选字段：缺失或为 `null` 时使用默认值，无效时记录错误并返回 `None`。
+fn parse_field<T: DeserializeOwned>(
+    fields: &Map<String, Value>,
+    name: &str,
+    default: T,
+    errors: &mut Vec<String>,
+) -> Option<T> {
+    match fields.get(name) {
+        None | Some(Value::Null) => Some(default),
+        Some(value) => match T::deserialize(value) {
+            Ok(parsed) => Some(parsed),
+            Err(error) => {
+                errors.push(format!("{name}: {error}"));
+                None
+            }
+        },
+    }
+}
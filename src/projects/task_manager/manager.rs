@@ -1,25 +1,49 @@
 use anyhow::{Context, Result};
-use std::collections::HashMap;
-use std::path::Path;
+use chrono::Datelike;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
+use super::import::{self, ImportReport};
 use super::model::{Priority, Status, Task};
-use super::stats::TaskStatistics;
-use super::storage::{TaskLoadOutcome, TaskStorage, TaskStorageConfig};
+use super::stats::{TaskStatistics, priority_index};
+use super::storage::{
+    InMemoryStore, JsonFileStore, PersistState, TaskLoadError, TaskStorage, TaskStorageConfig,
+    TaskStore, next_task_id,
+};
+use crate::basics::{IdGenerator, icontains, levenshtein};
 
 /// 任务管理器初始化时的数据来源状态。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TaskManagerLoadState {
     InitializedEmpty,
     LoadedFromStorage,
+    /// 原任务文件无法解析，已备份为 `*.corrupt.<时间戳>` 并以空状态启动。
+    RecoveredFromCorruptFile,
+}
+
+/// 两个任务管理器快照之间的差异，ID 均按升序排列。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskDiff {
+    pub added: Vec<u64>,
+    pub removed: Vec<u64>,
+    pub modified: Vec<u64>,
+}
+
+impl TaskDiff {
+    /// 两个快照是否完全一致
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
 }
 
 /// 受控的任务更新句柄。
-pub struct TaskUpdateHandle<'a> {
-    manager: &'a mut TaskManager,
+pub struct TaskUpdateHandle<'a, S: TaskStore = JsonFileStore> {
+    manager: &'a mut TaskManager<S>,
     id: u64,
 }
 
-impl<'a> TaskUpdateHandle<'a> {
+impl<'a, S: TaskStore> TaskUpdateHandle<'a, S> {
     /// 将任务标记为进行中。
     pub fn start(&mut self) -> Result<()> {
         self.manager.start_task(self.id)
@@ -37,11 +61,18 @@ impl<'a> TaskUpdateHandle<'a> {
 }
 
 /// 任务存储管理器
-pub struct TaskManager {
+///
+/// 存储后端通过 [`TaskStore`] 注入，默认使用 JSON 文件。
+pub struct TaskManager<S: TaskStore = JsonFileStore> {
     tasks: HashMap<u64, Task>,
-    next_id: u64,
-    storage: TaskStorage,
+    ids: IdGenerator,
+    storage: S,
     load_state: TaskManagerLoadState,
+    /// 关闭后修改只标记为脏，直到 `flush` 或析构时才写入存储。
+    auto_save: bool,
+    dirty: bool,
+    /// 配置后，被删除的任务会先以 JSON 行追加到该文件。
+    archive_path: Option<PathBuf>,
 }
 
 impl TaskManager {
@@ -52,8 +83,7 @@ impl TaskManager {
 
     /// 通过可注入配置创建任务管理器。
     pub fn with_config(config: TaskStorageConfig) -> Result<Self> {
-        let storage = TaskStorage::new(config)?;
-        Self::with_storage(storage)
+        Self::with_json_storage(TaskStorage::new(config)?)
     }
 
     /// 通过自定义文件路径创建任务管理器，适合测试或临时示例。
@@ -61,25 +91,105 @@ impl TaskManager {
         Self::with_config(TaskStorageConfig::from_path(path))
     }
 
-    /// 通过已构造的存储实例创建任务管理器。
-    pub fn with_storage(storage: TaskStorage) -> Result<Self> {
-        let (tasks, load_state) = match storage.load_tasks() {
-            Ok(TaskLoadOutcome::NotFound) => (HashMap::new(), TaskManagerLoadState::InitializedEmpty),
-            Ok(TaskLoadOutcome::Loaded(tasks)) => (tasks, TaskManagerLoadState::LoadedFromStorage),
-            Err(error) => {
-                return Err(anyhow::Error::new(error)
-                    .context("Failed to initialize task manager from storage"));
+    /// 使用自定义文件路径创建任务管理器，并为加载预分配 `capacity` 个任务的空间。
+    pub fn with_capacity(path: impl Into<std::path::PathBuf>, capacity: usize) -> Result<Self> {
+        let storage = TaskStorage::new(TaskStorageConfig::from_path(path))?;
+        Self::with_json_storage(storage.with_capacity_hint(capacity))
+    }
+
+    /// 当前存储文件路径。
+    pub fn storage_path(&self) -> &Path {
+        self.storage.path()
+    }
+
+    /// 从 JSON 文件初始化；文件损坏时先备份再以空状态启动，
+    /// 避免随后的第一次保存覆盖可能仍可人工恢复的数据。
+    fn with_json_storage(storage: TaskStorage) -> Result<Self> {
+        match storage.load_persisted() {
+            Err(error) if is_parse_error(&error) => {
+                let backup_path = storage.back_up_corrupt_file()?;
+                tracing::warn!(
+                    path = %storage.path().display(),
+                    backup = %backup_path.display(),
+                    error = %format!("{error:#}"),
+                    "任务文件无法解析，已备份并以空任务列表启动"
+                );
+                Ok(Self::from_parts(
+                    storage,
+                    PersistState::from_legacy(HashMap::new()),
+                    TaskManagerLoadState::RecoveredFromCorruptFile,
+                ))
             }
+            outcome => Self::from_outcome(storage, outcome),
+        }
+    }
+}
+
+impl TaskManager<InMemoryStore> {
+    /// 创建不读写任何文件的空任务管理器，适合测试或快速构造示例数据。
+    pub fn in_memory() -> Self {
+        Self::from_parts(
+            InMemoryStore::new(),
+            PersistState::from_legacy(HashMap::new()),
+            TaskManagerLoadState::InitializedEmpty,
+        )
+    }
+}
+
+/// 收集任务构造内存任务管理器，任务按迭代顺序重新分配 ID。
+impl FromIterator<Task> for TaskManager<InMemoryStore> {
+    fn from_iter<I: IntoIterator<Item = Task>>(iter: I) -> Self {
+        let mut manager = Self::in_memory();
+        manager.set_auto_save(false);
+        for task in iter {
+            manager
+                .add_task(task)
+                .expect("adding to an in-memory manager without auto-save cannot fail");
+        }
+        manager
+            .flush()
+            .expect("saving to an in-memory store cannot fail");
+        manager.set_auto_save(true);
+        manager
+    }
+}
+
+impl<S: TaskStore> TaskManager<S> {
+    /// 通过已构造的存储实例创建任务管理器。
+    pub fn with_storage(storage: S) -> Result<Self> {
+        let outcome = storage.load_persisted();
+        Self::from_outcome(storage, outcome)
+    }
+
+    fn from_outcome(storage: S, outcome: Result<Option<PersistState>>) -> Result<Self> {
+        let Some(state) = outcome.context("Failed to initialize task manager from storage")? else {
+            return Ok(Self::from_parts(
+                storage,
+                PersistState::from_legacy(HashMap::new()),
+                TaskManagerLoadState::InitializedEmpty,
+            ));
         };
 
-        let next_id = next_task_id(&tasks);
+        Ok(Self::from_parts(
+            storage,
+            state,
+            TaskManagerLoadState::LoadedFromStorage,
+        ))
+    }
+
+    /// 持久化的 `next_id` 不会回退到已有任务 ID 之下，防止手工编辑的文件产生 ID 冲突。
+    fn from_parts(storage: S, state: PersistState, load_state: TaskManagerLoadState) -> Self {
+        let next_id = state.next_id.max(next_task_id(&state.tasks));
 
-        Ok(Self {
-            tasks,
-            next_id,
+        Self {
+            tasks: state.tasks,
+            ids: IdGenerator::new_starting_at(next_id),
             storage,
             load_state,
-        })
+            auto_save: true,
+            dirty: false,
+            archive_path: None,
+        }
     }
 
     /// 当前初始化路径的数据来源状态。
@@ -87,39 +197,97 @@ impl TaskManager {
         self.load_state
     }
 
-    /// 当前存储文件路径。
-    pub fn storage_path(&self) -> &Path {
-        self.storage.path()
+    /// 当前使用的存储后端。
+    pub fn store(&self) -> &S {
+        &self.storage
     }
 
-    /// 将当前内存中的任务状态持久化到存储。
-    pub fn persist(&self) -> Result<()> {
+    /// 将当前内存中的任务状态持久化到存储，成功后清除待保存标记。
+    pub fn persist(&mut self) -> Result<()> {
         self.save()
     }
 
+    /// 开关每次修改后的自动保存；关闭后需调用 `flush` 批量写入。
+    pub fn set_auto_save(&mut self, enabled: bool) {
+        self.auto_save = enabled;
+    }
+
+    /// 是否在每次修改后立即保存。
+    pub fn auto_save(&self) -> bool {
+        self.auto_save
+    }
+
+    /// 设置归档文件：此后 `delete_task` 与 `clear_completed` 会在移除前把任务逐行追加到该文件。
+    pub fn set_archive_path(&mut self, path: impl Into<PathBuf>) {
+        self.archive_path = Some(path.into());
+    }
+
+    /// 存在尚未写入存储的修改时保存一次，否则什么也不做。
+    pub fn flush(&mut self) -> Result<()> {
+        if self.dirty {
+            self.save()
+                .context("Failed to flush pending task changes")?;
+        }
+        Ok(())
+    }
+
     /// 添加任务
     pub fn add_task(&mut self, mut task: Task) -> Result<u64> {
-        let task_id = self.next_id;
+        let task_id = self.ids.next();
         task.assign_id(task_id);
         self.tasks.insert(task_id, task);
-        self.next_id += 1;
 
-        if let Err(error) = self.save() {
+        if let Err(error) = self.save_or_mark_dirty() {
             self.tasks.remove(&task_id);
-            self.next_id = task_id;
+            self.ids = IdGenerator::new_starting_at(task_id);
             return Err(error.context("Failed to persist newly added task"));
         }
 
         Ok(task_id)
     }
 
+    /// 从 JSON 导入任务，逐条校验并收集错误，而不是在第一条无效记录处中止。
+    ///
+    /// 输入可以是任务数组，也可以是存储文件同款的 `{ id: task }` 对象；导入的任务会分配新 ID。
+    /// 只有整个输入不是合法 JSON，或持久化失败时才返回 `Err`。
+    pub fn import_json(&mut self, json: &str) -> Result<ImportReport> {
+        let records = import::split_records(json).context("Failed to parse import file")?;
+        let first_new_id = self.ids.peek();
+        let mut report = ImportReport::default();
+
+        for (index, record) in records.iter().enumerate() {
+            match import::parse_record(record) {
+                Ok(mut task) => {
+                    let task_id = self.ids.next();
+                    task.assign_id(task_id);
+                    self.tasks.insert(task_id, task);
+                    report.imported += 1;
+                }
+                Err(errors) => {
+                    report.skipped += 1;
+                    report
+                        .errors
+                        .extend(errors.into_iter().map(|error| (index, error)));
+                }
+            }
+        }
+
+        if let Err(error) = self.save_or_mark_dirty() {
+            self.tasks.retain(|id, _| *id < first_new_id);
+            self.ids = IdGenerator::new_starting_at(first_new_id);
+            return Err(error.context("Failed to persist imported tasks"));
+        }
+
+        Ok(report)
+    }
+
     /// 获取任务
     pub fn get_task(&self, id: u64) -> Option<&Task> {
         self.tasks.get(&id)
     }
 
     /// 获取受控的任务更新句柄。
-    pub fn get_task_mut(&mut self, id: u64) -> Option<TaskUpdateHandle<'_>> {
+    pub fn get_task_mut(&mut self, id: u64) -> Option<TaskUpdateHandle<'_, S>> {
         if self.tasks.contains_key(&id) {
             Some(TaskUpdateHandle { manager: self, id })
         } else {
@@ -139,7 +307,7 @@ impl TaskManager {
             updater(task);
         }
 
-        if let Err(error) = self.save() {
+        if let Err(error) = self.save_or_mark_dirty() {
             self.tasks.insert(id, original_task);
             return Err(error.context("Failed to persist task update"));
         }
@@ -162,18 +330,68 @@ impl TaskManager {
         self.update_task(id, Task::cancel)
     }
 
+    /// 将任务优先级上调或下调一级，返回调整后的优先级。
+    pub fn bump_priority(&mut self, id: u64, up: bool) -> Result<Priority> {
+        self.update_task(id, |task| {
+            let priority = if up {
+                task.priority().next()
+            } else {
+                task.priority().prev()
+            };
+            task.set_priority(priority);
+        })?;
+
+        Ok(self.tasks[&id].priority())
+    }
+
     /// 删除任务
+    ///
+    /// 配置了归档文件（见 [`TaskManager::set_archive_path`]）时，先把任务追加到归档再移除；
+    /// 保存失败时恢复任务，并把归档截断回追加前的长度。
     pub fn delete_task(&mut self, id: u64) -> Result<Task> {
+        let archived = self.archive(self.tasks.get(&id))?;
         let task = self.tasks.remove(&id).context("Task not found")?;
 
-        if let Err(error) = self.save() {
+        if let Err(error) = self.save_or_mark_dirty() {
             self.tasks.insert(id, task.clone());
+            self.rollback_archive(archived);
             return Err(error.context("Failed to persist task deletion"));
         }
 
         Ok(task)
     }
 
+    /// 移除所有已完成的任务，返回移除数量
+    ///
+    /// 无论移除多少条都只保存一次；没有已完成任务时不触发保存。保存失败时恢复被移除的任务。
+    /// 配置了归档文件时，被移除的任务会先追加到归档，保存失败时一并撤销。
+    pub fn clear_completed(&mut self) -> Result<usize> {
+        let completed: Vec<u64> = self
+            .tasks
+            .values()
+            .filter(|task| task.status() == Status::Completed)
+            .map(|task| task.id())
+            .collect();
+        if completed.is_empty() {
+            return Ok(0);
+        }
+        let archived = self.archive(completed.iter().map(|id| &self.tasks[id]))?;
+
+        let removed: Vec<Task> = completed
+            .iter()
+            .filter_map(|id| self.tasks.remove(id))
+            .collect();
+
+        if let Err(error) = self.save_or_mark_dirty() {
+            self.tasks
+                .extend(removed.into_iter().map(|task| (task.id(), task)));
+            self.rollback_archive(archived);
+            return Err(error.context("Failed to persist cleared tasks"));
+        }
+
+        Ok(removed.len())
+    }
+
     /// 列出所有任务
     pub fn list_tasks(&self, filter: Option<Status>) -> Vec<&Task> {
         let mut tasks: Vec<&Task> = self.tasks.values().collect();
@@ -182,75 +400,253 @@ impl TaskManager {
             tasks.retain(|task| task.status() == status);
         }
 
-        tasks.sort_by(|left, right| {
-            left.priority()
-                .sort_order()
-                .cmp(&right.priority().sort_order())
-                .then_with(|| right.created_at().cmp(left.created_at()))
-        });
+        tasks.sort_by(|left, right| left.cmp_by_priority_then_created(right));
 
         tasks
     }
 
+    /// 与 [`TaskManager::list_tasks`] 顺序相同，但返回克隆后的任务
+    ///
+    /// 结果不借用管理器，可以发送到其他线程，也不受之后的修改影响。
+    pub fn list_tasks_owned(&self, filter: Option<Status>) -> Vec<Task> {
+        self.list_tasks(filter).into_iter().cloned().collect()
+    }
+
     /// 搜索任务
     pub fn search_tasks(&self, query: &str) -> Vec<&Task> {
-        let query_lower = query.to_lowercase();
         self.tasks
             .values()
             .filter(|task| {
-                task.title().to_lowercase().contains(&query_lower)
+                icontains(task.title(), query)
                     || task
                         .description()
-                        .map(|description| description.to_lowercase().contains(&query_lower))
+                        .map(|description| icontains(description, query))
                         .unwrap_or(false)
-                    || task
-                        .tags()
-                        .iter()
-                        .any(|tag| tag.to_lowercase().contains(&query_lower))
+                    || task.tags().iter().any(|tag| icontains(tag, query))
             })
             .collect()
     }
 
-    /// 获取统计信息
-    pub fn get_statistics(&self) -> TaskStatistics {
-        let total = self.tasks.len();
-        let completed = self
+    /// 模糊搜索任务标题
+    ///
+    /// 标题与查询都转为小写后计算编辑距离，返回距离不超过 `max_distance` 的任务，
+    /// 按距离升序、优先级从高到低排序。
+    pub fn fuzzy_search(&self, query: &str, max_distance: usize) -> Vec<&Task> {
+        let query_lower = query.to_lowercase();
+        let mut matches: Vec<(usize, &Task)> = self
             .tasks
             .values()
-            .filter(|task| task.status() == Status::Completed)
-            .count();
-        let pending = self
+            .map(|task| {
+                let distance = levenshtein(&task.title().to_lowercase(), &query_lower);
+                (distance, task)
+            })
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect();
+
+        matches.sort_by(|(left_distance, left), (right_distance, right)| {
+            left_distance
+                .cmp(right_distance)
+                .then_with(|| left.priority().sort_order().cmp(&right.priority().sort_order()))
+                .then_with(|| left.id().cmp(&right.id()))
+        });
+
+        matches.into_iter().map(|(_, task)| task).collect()
+    }
+
+    /// 可立即开始的任务
+    ///
+    /// 返回所有前置任务均已完成的待处理任务，按优先级从高到低、ID 升序排序。
+    /// 已被删除的前置任务不再阻塞后续任务。
+    pub fn ready_tasks(&self) -> Vec<&Task> {
+        let mut ready: Vec<&Task> = self
             .tasks
             .values()
             .filter(|task| task.status() == Status::Pending)
-            .count();
-        let in_progress = self
-            .tasks
-            .values()
-            .filter(|task| task.status() == Status::InProgress)
-            .count();
-        let urgent = self
-            .tasks
-            .values()
             .filter(|task| {
-                task.priority() == Priority::Urgent && task.status() != Status::Completed
+                task.dependencies().iter().all(|dependency| {
+                    self.tasks
+                        .get(dependency)
+                        .is_none_or(|dependency| dependency.status() == Status::Completed)
+                })
+            })
+            .collect();
+
+        ready.sort_by_key(|task| (task.priority().sort_order(), task.id()));
+        ready
+    }
+
+    /// 可立即开始的任务数量，供仪表盘展示
+    pub fn ready_count(&self) -> usize {
+        self.ready_tasks().len()
+    }
+
+    /// 以当前管理器为基准，比较 `other` 中新增、删除与修改过的任务。
+    ///
+    /// 按 ID 匹配任务，`updated_at` 不同即视为已修改。
+    pub fn diff<T: TaskStore>(&self, other: &TaskManager<T>) -> TaskDiff {
+        let mut diff = TaskDiff::default();
+
+        for (id, task) in &self.tasks {
+            match other.tasks.get(id) {
+                None => diff.removed.push(*id),
+                Some(other_task) if other_task.updated_at() != task.updated_at() => {
+                    diff.modified.push(*id)
+                }
+                Some(_) => {}
+            }
+        }
+        diff.added = other
+            .tasks
+            .keys()
+            .filter(|id| !self.tasks.contains_key(id))
+            .copied()
+            .collect();
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.modified.sort_unstable();
+        diff
+    }
+
+    /// 按 ISO 周统计已完成任务数量，键为 `(ISO 年, ISO 周)`，以本地时间计算。
+    pub fn completed_by_week(&self) -> BTreeMap<(i32, u32), usize> {
+        let mut weeks = BTreeMap::new();
+
+        for completed_at in self.tasks.values().filter_map(|task| task.completed_at()) {
+            let week = completed_at.iso_week();
+            *weeks.entry((week.year(), week.week())).or_insert(0) += 1;
+        }
+
+        weeks
+    }
+
+    /// 生成按标签汇总的 Markdown 表格，包含任务数与完成率，按任务数降序、标签名升序排列。
+    pub fn tag_report_markdown(&self) -> String {
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for task in self.tasks.values() {
+            for tag in task.tags() {
+                let entry = counts.entry(tag.as_str()).or_insert((0, 0));
+                entry.0 += 1;
+                if task.status() == Status::Completed {
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        let mut rows: Vec<(&str, usize, usize)> = counts
+            .into_iter()
+            .map(|(tag, (total, completed))| (tag, total, completed))
+            .collect();
+        rows.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(right.0)));
+
+        let mut report =
+            String::from("## Tags\n\n| Tag | Count | Completion |\n| --- | ---: | ---: |\n");
+        for (tag, total, completed) in rows {
+            let rate = completed as f64 / total as f64 * 100.0;
+            report.push_str(&format!("| #{} | {} | {:.0}% |\n", tag, total, rate));
+        }
+
+        report
+    }
+
+    /// 获取统计信息
+    pub fn get_statistics(&self) -> TaskStatistics {
+        let mut stats = TaskStatistics {
+            total: self.tasks.len(),
+            completed: 0,
+            pending: 0,
+            in_progress: 0,
+            urgent: 0,
+            by_priority: [0; 4],
+        };
+
+        for task in self.tasks.values() {
+            match task.status() {
+                Status::Completed => stats.completed += 1,
+                Status::Pending => stats.pending += 1,
+                Status::InProgress => stats.in_progress += 1,
+                Status::Cancelled => {}
+            }
+            if task.priority() == Priority::Urgent && task.status() != Status::Completed {
+                stats.urgent += 1;
+            }
+            stats.by_priority[priority_index(task.priority())] += 1;
+        }
+
+        stats
+    }
+
+    /// 所有写入存储的路径都经过这里；保存成功后内存与存储一致，清除待保存标记。
+    fn save(&mut self) -> Result<()> {
+        self.storage.save_persisted(&self.tasks, self.ids.peek())?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// 已配置归档文件时，把任务逐行追加为 JSON；未配置时什么也不做。
+    ///
+    /// 返回追加前的归档长度，供 [`Self::rollback_archive`] 在保存失败时撤销本次追加。
+    fn archive<'t>(&self, tasks: impl IntoIterator<Item = &'t Task>) -> Result<Option<u64>> {
+        let Some(path) = &self.archive_path else {
+            return Ok(None);
+        };
+
+        let mut lines = Vec::new();
+        for task in tasks {
+            serde_json::to_writer(&mut lines, task).context("Failed to serialize archived task")?;
+            lines.push(b'\n');
+        }
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| {
+                let previous_len = file.metadata()?.len();
+                file.write_all(&lines)?;
+                Ok(Some(previous_len))
             })
-            .count();
-
-        TaskStatistics {
-            total,
-            completed,
-            pending,
-            in_progress,
-            urgent,
+            .with_context(|| format!("Failed to append to archive {}", path.display()))
+    }
+
+    /// 把归档截断回 [`Self::archive`] 追加前的长度。
+    ///
+    /// 只在保存失败的回滚路径上调用，截断失败时保留原始的保存错误。
+    fn rollback_archive(&self, previous_len: Option<u64>) {
+        if let (Some(path), Some(len)) = (&self.archive_path, previous_len) {
+            let _ = std::fs::OpenOptions::new()
+                .write(true)
+                .open(path)
+                .and_then(|file| file.set_len(len));
+        }
+    }
+
+    /// 修改后的统一落盘入口：自动保存时立即写入，否则仅标记为脏。
+    fn save_or_mark_dirty(&mut self) -> Result<()> {
+        if self.auto_save {
+            self.save()
+        } else {
+            self.dirty = true;
+            Ok(())
         }
     }
+}
 
-    fn save(&self) -> Result<()> {
-        self.storage.save_tasks(&self.tasks)
+impl<S: TaskStore> Drop for TaskManager<S> {
+    /// 析构时写入尚未保存的修改；此处无法返回错误，只记录警告。
+    fn drop(&mut self) {
+        if let Err(error) = self.flush() {
+            tracing::warn!(error = %format!("{error:#}"), "析构时保存任务失败");
+        }
     }
 }
 
-fn next_task_id(tasks: &HashMap<u64, Task>) -> u64 {
-    tasks.keys().max().map(|max_id| max_id + 1).unwrap_or(1)
+fn is_parse_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<TaskLoadError>(),
+        Some(TaskLoadError::Parse { .. })
+    )
 }
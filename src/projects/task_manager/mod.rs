@@ -15,33 +15,44 @@
 //! ## 源码结构
 //!
 //! - `model`：任务实体、优先级与状态建模
+//! - `import`：导入文件的逐条记录校验
 //! - `manager`：任务集合管理、排序、过滤与持久化协调
-//! - `storage`：JSON 存储，以及首次运行与真实加载错误的区分
+//! - `storage`：可插拔存储后端（JSON / bincode），以及首次运行与真实加载错误的区分
 //! - `stats`：统计汇总展示
+//! - `cli`：基于 `clap` 的子命令定义与分发
 //! - `demo`：终端演示流程
 //! - `tests`：正常路径与异常路径覆盖
 //!
 //! ## 技术栈
 //!
 //! - `serde` - 数据序列化
+//! - `bincode` - 紧凑的二进制持久化
 //! - `chrono` - 日期时间处理
 //! - `anyhow` / `thiserror` - 错误处理与错误分层
+//! - `clap` - 命令行参数解析
 //! - `colored` - 终端颜色输出
-//! - `dirs` - 默认存储路径解析
+//! - `dirs` - 默认存储路径解析（可通过 `TASK_MANAGER_DIR` 环境变量覆盖）
 
 #![allow(dead_code)]
 
+mod cli;
 mod demo;
+mod import;
 mod manager;
 mod model;
 mod stats;
 mod storage;
 
+pub use cli::{TaskCli, TaskCommand, run_cli};
 pub use demo::run_task_manager_demo;
-pub use manager::{TaskManager, TaskManagerLoadState};
-pub use model::{Priority, Status, Task};
+pub use import::ImportReport;
+pub use manager::{TaskDiff, TaskManager, TaskManagerLoadState};
+pub use model::{ColorTheme, Priority, Status, Task};
 pub use stats::TaskStatistics;
-pub use storage::{TaskLoadError, TaskLoadOutcome, TaskStorage, TaskStorageConfig};
+pub use storage::{
+    BincodeFileStore, InMemoryStore, JsonFileStore, PersistState, TaskLoadError, TaskLoadOutcome,
+    TaskStorage, TaskStorageConfig, TaskStore,
+};
 
 #[cfg(test)]
 mod tests;
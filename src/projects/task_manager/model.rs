@@ -1,20 +1,28 @@
 use chrono::{DateTime, Local};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::ops::Deref;
 
 /// 任务优先级
+///
+/// 序列化为小写字符串（如 `"urgent"`），同时兼容旧文件中的 PascalCase 变体名。
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum Priority {
+    #[serde(alias = "Low")]
     Low,
+    #[serde(alias = "Medium")]
     Medium,
+    #[serde(alias = "High")]
     High,
+    #[serde(alias = "Urgent")]
     Urgent,
 }
 
 impl Priority {
-    /// 获取优先级的颜色表示
-    fn color(&self) -> colored::Color {
+    /// 获取优先级在默认主题下的颜色表示
+    pub fn color(&self) -> colored::Color {
         match self {
             Priority::Low => colored::Color::Green,
             Priority::Medium => colored::Color::Yellow,
@@ -33,6 +41,24 @@ impl Priority {
         }
     }
 
+    /// 提升一级优先级，`Urgent` 保持不变
+    pub fn next(&self) -> Priority {
+        match self {
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High | Priority::Urgent => Priority::Urgent,
+        }
+    }
+
+    /// 降低一级优先级，`Low` 保持不变
+    pub fn prev(&self) -> Priority {
+        match self {
+            Priority::Low | Priority::Medium => Priority::Low,
+            Priority::High => Priority::Medium,
+            Priority::Urgent => Priority::High,
+        }
+    }
+
     pub(crate) fn sort_order(&self) -> u8 {
         match self {
             Priority::Urgent => 0,
@@ -57,12 +83,53 @@ impl std::str::FromStr for Priority {
     }
 }
 
+/// 优先级配色主题
+///
+/// 默认值与 [`Priority::color`] 一致，可按需覆盖单个优先级的颜色。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorTheme {
+    pub low: colored::Color,
+    pub medium: colored::Color,
+    pub high: colored::Color,
+    pub urgent: colored::Color,
+}
+
+impl ColorTheme {
+    /// 获取指定优先级在当前主题下的颜色
+    pub fn color_for(&self, priority: Priority) -> colored::Color {
+        match priority {
+            Priority::Low => self.low,
+            Priority::Medium => self.medium,
+            Priority::High => self.high,
+            Priority::Urgent => self.urgent,
+        }
+    }
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self {
+            low: Priority::Low.color(),
+            medium: Priority::Medium.color(),
+            high: Priority::High.color(),
+            urgent: Priority::Urgent.color(),
+        }
+    }
+}
+
 /// 任务状态
+///
+/// 序列化为 snake_case 字符串（如 `"in_progress"`），同时兼容旧文件中的 PascalCase 变体名。
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum Status {
+    #[serde(alias = "Pending")]
     Pending,
+    #[serde(alias = "InProgress")]
     InProgress,
+    #[serde(alias = "Completed")]
     Completed,
+    #[serde(alias = "Cancelled")]
     Cancelled,
 }
 
@@ -86,8 +153,22 @@ impl Status {
     }
 }
 
+impl std::str::FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "pending" | "p" => Ok(Status::Pending),
+            "in_progress" | "progress" | "i" => Ok(Status::InProgress),
+            "completed" | "done" | "c" => Ok(Status::Completed),
+            "cancelled" | "canceled" | "x" => Ok(Status::Cancelled),
+            _ => Err(format!("Unknown status: {}", s)),
+        }
+    }
+}
+
 /// 面向外部的只读任务视图。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TaskView {
     pub id: u64,
     pub title: String,
@@ -99,10 +180,13 @@ pub struct TaskView {
     pub updated_at: DateTime<Local>,
     pub completed_at: Option<DateTime<Local>>,
     pub due_date: Option<DateTime<Local>>,
+    /// 前置任务 ID；旧文件中缺失该字段时视为无依赖。
+    #[serde(default)]
+    pub depends_on: Vec<u64>,
 }
 
 /// 任务结构体
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Task {
     #[serde(flatten)]
     inner: TaskView,
@@ -116,6 +200,12 @@ impl Deref for Task {
     }
 }
 
+impl From<TaskView> for Task {
+    fn from(inner: TaskView) -> Self {
+        Self { inner }
+    }
+}
+
 impl Task {
     /// 创建新任务
     pub fn new(id: u64, title: impl Into<String>, priority: Priority) -> Self {
@@ -132,6 +222,7 @@ impl Task {
                 updated_at: now,
                 completed_at: None,
                 due_date: None,
+                depends_on: Vec::new(),
             },
         }
     }
@@ -186,6 +277,35 @@ impl Task {
         self.inner.due_date.as_ref()
     }
 
+    /// 前置任务 ID 列表
+    pub fn dependencies(&self) -> &[u64] {
+        &self.inner.depends_on
+    }
+
+    /// 按截止时间升序比较，没有截止时间（`None`）的任务排在最后；
+    /// 截止时间相同时再按优先级从高到低排序。可直接传给 `sort_by`。
+    pub fn cmp_by_due_then_priority(&self, other: &Task) -> Ordering {
+        let due_order = match (self.due_date(), other.due_date()) {
+            (Some(left), Some(right)) => left.cmp(right),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        due_order.then_with(|| {
+            self.priority()
+                .sort_order()
+                .cmp(&other.priority().sort_order())
+        })
+    }
+
+    /// 按优先级从高到低比较，同优先级时较新创建的任务在前；与 `list_tasks` 的顺序一致。
+    pub fn cmp_by_priority_then_created(&self, other: &Task) -> Ordering {
+        self.priority()
+            .sort_order()
+            .cmp(&other.priority().sort_order())
+            .then_with(|| other.created_at().cmp(self.created_at()))
+    }
+
     pub(crate) fn assign_id(&mut self, id: u64) {
         self.inner.id = id;
     }
@@ -224,6 +344,12 @@ impl Task {
         self.touch();
     }
 
+    /// 设置前置任务
+    pub fn set_dependencies(&mut self, depends_on: Vec<u64>) {
+        self.inner.depends_on = depends_on;
+        self.touch();
+    }
+
     /// 设置描述
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.inner.description = Some(desc.into());
@@ -242,6 +368,12 @@ impl Task {
         self
     }
 
+    /// 设置前置任务
+    pub fn with_dependencies(mut self, depends_on: Vec<u64>) -> Self {
+        self.inner.depends_on = depends_on;
+        self
+    }
+
     /// 完成任务
     pub fn complete(&mut self) {
         self.inner.status = Status::Completed;
@@ -263,10 +395,16 @@ impl Task {
         self.touch();
     }
 
-    /// 格式化显示任务
-    pub fn display(&self) -> String {
+    /// 按给定配色主题格式化显示任务
+    ///
+    /// 处于 [`crate::plain_output`] 模式时输出不含颜色与 emoji 的纯文本。
+    pub fn display(&self, theme: &ColorTheme) -> String {
+        if crate::plain_output() {
+            return self.display_plain();
+        }
+
         let priority_str = format!("[{}]", self.inner.priority.as_str())
-            .color(self.inner.priority.color())
+            .color(theme.color_for(self.inner.priority))
             .bold();
 
         let status_symbol = self.inner.status.symbol();
@@ -302,4 +440,24 @@ impl Task {
 
         result
     }
+
+    fn display_plain(&self) -> String {
+        let mut result = format!(
+            "[{}] [{}] {} - {}",
+            self.inner.status.as_str(),
+            self.inner.priority.as_str(),
+            self.inner.id,
+            self.inner.title
+        );
+
+        for tag in &self.inner.tags {
+            result.push_str(&format!(" #{}", tag));
+        }
+
+        if let Some(ref due) = self.inner.due_date {
+            result.push_str(&format!(" due {}", due.format("%Y-%m-%d")));
+        }
+
+        result
+    }
 }
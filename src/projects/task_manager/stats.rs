@@ -1,5 +1,7 @@
 use colored::Colorize;
 
+use super::model::Priority;
+
 /// 任务统计信息
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TaskStatistics {
@@ -8,9 +10,82 @@ pub struct TaskStatistics {
     pub pending: usize,
     pub in_progress: usize,
     pub urgent: usize,
+    /// 各优先级的任务数，按 Low、Medium、High、Urgent 顺序排列
+    pub by_priority: [usize; 4],
 }
 
 impl TaskStatistics {
+    /// 指定优先级的任务数（包含所有状态）
+    pub fn priority_count(&self, priority: Priority) -> usize {
+        self.by_priority[priority_index(priority)]
+    }
+
+    /// 已完成任务占比（0.0 ~ 1.0），没有任务时为 0.0
+    pub fn completion_rate(&self) -> f64 {
+        self.rate(self.completed)
+    }
+
+    /// 待处理任务占比（0.0 ~ 1.0），没有任务时为 0.0
+    pub fn pending_rate(&self) -> f64 {
+        self.rate(self.pending)
+    }
+
+    /// 进行中任务占比（0.0 ~ 1.0），没有任务时为 0.0
+    pub fn in_progress_rate(&self) -> f64 {
+        self.rate(self.in_progress)
+    }
+
+    fn rate(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            count as f64 / self.total as f64
+        }
+    }
+
+    /// 按状态渲染占比条形图，每行一个状态，`█` 的个数与其占总数的比例成正比
+    ///
+    /// 各行的 `█` 个数按最大余数法分配，总和恰好等于 `width`；没有任务时返回空字符串。
+    pub fn render_bars(&self, width: usize) -> String {
+        if self.total == 0 {
+            return String::new();
+        }
+
+        let cancelled = self
+            .total
+            .saturating_sub(self.completed + self.pending + self.in_progress);
+        let rows = [
+            ("Completed", self.completed),
+            ("In Progress", self.in_progress),
+            ("Pending", self.pending),
+            ("Cancelled", cancelled),
+        ];
+
+        let mut filled: Vec<usize> = rows
+            .iter()
+            .map(|(_, count)| count * width / self.total)
+            .collect();
+        let mut by_remainder: Vec<usize> = (0..rows.len()).collect();
+        by_remainder.sort_by_key(|&index| std::cmp::Reverse(rows[index].1 * width % self.total));
+        let leftover = width - filled.iter().sum::<usize>();
+        for &index in by_remainder.iter().take(leftover) {
+            filled[index] += 1;
+        }
+
+        rows.iter()
+            .zip(filled)
+            .map(|((label, count), filled)| {
+                format!(
+                    "  {:<12} {}{} {}\n",
+                    label,
+                    "█".repeat(filled),
+                    "░".repeat(width - filled),
+                    count
+                )
+            })
+            .collect()
+    }
+
     /// 显示统计信息
     pub fn display(&self) {
         println!("\n{}", "📊 Task Statistics".bold().underline());
@@ -19,17 +94,38 @@ impl TaskStatistics {
             "  {} {} ({:.1}%)",
             "Completed:".green().bold(),
             self.completed,
-            if self.total > 0 {
-                (self.completed as f64 / self.total as f64) * 100.0
-            } else {
-                0.0
-            }
+            self.completion_rate() * 100.0
+        );
+        println!(
+            "  {} {} ({:.1}%)",
+            "Pending:".yellow().bold(),
+            self.pending,
+            self.pending_rate() * 100.0
+        );
+        println!(
+            "  {} {} ({:.1}%)",
+            "In Progress:".blue().bold(),
+            self.in_progress,
+            self.in_progress_rate() * 100.0
         );
-        println!("  {} {}", "Pending:".yellow().bold(), self.pending);
-        println!("  {} {}", "In Progress:".blue().bold(), self.in_progress);
 
         if self.urgent > 0 {
             println!("  {} {}", "⚠️  Urgent:".red().bold(), self.urgent);
         }
+
+        let bars = self.render_bars(20);
+        if !bars.is_empty() {
+            println!();
+            print!("{}", bars);
+        }
+    }
+}
+
+pub(crate) fn priority_index(priority: Priority) -> usize {
+    match priority {
+        Priority::Low => 0,
+        Priority::Medium => 1,
+        Priority::High => 2,
+        Priority::Urgent => 3,
     }
 }
@@ -1,12 +1,17 @@
 use anyhow::{Context, Result};
+use serde::de::{DeserializeSeed, Deserializer, Error as _, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::io;
+use std::io::{self, BufReader};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
-use super::model::Task;
+use super::model::{Task, TaskView};
+use crate::basics::FileLock;
 
 #[derive(Debug, Clone)]
 pub struct TaskStorageConfig {
@@ -14,11 +19,25 @@ pub struct TaskStorageConfig {
 }
 
 impl TaskStorageConfig {
+    /// 覆盖默认存储目录的环境变量名。
+    pub const DIR_ENV_VAR: &'static str = "TASK_MANAGER_DIR";
+
     /// 使用默认存储位置创建配置。
+    ///
+    /// 设置了 [`Self::DIR_ENV_VAR`] 时使用该目录下的 `tasks.json`，否则使用 `~/.task_manager/tasks.json`。
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            path: default_storage_path()?,
-        })
+        Self::from_env_with(|key| std::env::var(key).ok())
+    }
+
+    /// 与 [`TaskStorageConfig::new`] 相同，但通过 `lookup` 查找环境变量，便于注入测试数据。
+    ///
+    /// 变量值为空字符串时视为未设置。
+    pub fn from_env_with(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let path = match lookup(Self::DIR_ENV_VAR).filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir).join(STORAGE_FILE_NAME),
+            None => default_storage_path()?,
+        };
+        Ok(Self { path })
     }
 
     /// 直接注入指定文件路径，便于测试或示例隔离。
@@ -47,6 +66,14 @@ pub enum TaskLoadError {
         #[source]
         source: serde_json::Error,
     },
+    #[error("Failed to decode bincode tasks file at {0}", .path.display())]
+    Decode {
+        path: PathBuf,
+        #[source]
+        source: bincode::Error,
+    },
+    #[error("Tasks file at {0} looks like JSON; open it with JsonFileStore instead", .path.display())]
+    UnexpectedJson { path: PathBuf },
 }
 
 #[derive(Debug)]
@@ -55,9 +82,78 @@ pub enum TaskLoadOutcome {
     Loaded(HashMap<u64, Task>),
 }
 
+/// 任务文件的版本化信封，连同任务一起保存下一个可用 ID。
+///
+/// 旧版文件只是 `id -> Task` 的裸映射，加载时视为版本 0 并根据现有 ID 推算 `next_id`。
+#[derive(Debug, Clone)]
+pub struct PersistState {
+    pub version: u32,
+    pub next_id: u64,
+    pub tasks: HashMap<u64, Task>,
+}
+
+impl PersistState {
+    /// 当前写入磁盘的信封版本。
+    pub const CURRENT_VERSION: u32 = 1;
+
+    /// 从旧版裸任务映射迁移，`next_id` 取现有最大 ID 加一。
+    pub fn from_legacy(tasks: HashMap<u64, Task>) -> Self {
+        Self {
+            version: 0,
+            next_id: next_task_id(&tasks),
+            tasks,
+        }
+    }
+}
+
+/// 根据已有任务推算下一个可用 ID。
+pub(crate) fn next_task_id(tasks: &HashMap<u64, Task>) -> u64 {
+    tasks.keys().max().map(|max_id| max_id + 1).unwrap_or(1)
+}
+
+/// 可插拔的任务存储后端。
+///
+/// `TaskManager` 只通过该 trait 读写任务，便于替换为内存或其他持久化实现。
+pub trait TaskStore {
+    /// 加载全部任务；存储尚不存在时返回空集合。
+    fn load(&self) -> Result<HashMap<u64, Task>>;
+
+    /// 用给定任务集合整体替换存储内容。
+    fn save(&self, tasks: &HashMap<u64, Task>) -> Result<()>;
+
+    /// 加载任务并区分首次运行与真实加载，默认实现总是视为已加载。
+    fn load_outcome(&self) -> Result<TaskLoadOutcome> {
+        self.load().map(TaskLoadOutcome::Loaded)
+    }
+
+    /// 加载任务及持久化的下一个 ID；默认实现由任务 ID 推算，适用于不保存 `next_id` 的后端。
+    fn load_persisted(&self) -> Result<Option<PersistState>> {
+        Ok(match self.load_outcome()? {
+            TaskLoadOutcome::NotFound => None,
+            TaskLoadOutcome::Loaded(tasks) => Some(PersistState::from_legacy(tasks)),
+        })
+    }
+
+    /// 连同下一个任务 ID 一起保存；默认实现忽略 `next_id`。
+    fn save_persisted(&self, tasks: &HashMap<u64, Task>, next_id: u64) -> Result<()> {
+        let _ = next_id;
+        self.save(tasks)
+    }
+}
+
+/// 默认的 JSON 文件存储后端。
+pub type JsonFileStore = TaskStorage;
+
+/// 保存任务文件时等待其他进程释放写锁的最长时间。
+const SAVE_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 未指定容量提示时，按文件大小估算任务数所用的单个任务平均字节数。
+const ESTIMATED_BYTES_PER_TASK: u64 = 256;
+
 #[derive(Debug, Clone)]
 pub struct TaskStorage {
     path: PathBuf,
+    capacity_hint: Option<usize>,
 }
 
 impl TaskStorage {
@@ -65,18 +161,38 @@ impl TaskStorage {
         ensure_parent_dir(config.path())?;
         Ok(Self {
             path: config.path,
+            capacity_hint: None,
         })
     }
 
+    /// 指定流式加载时预分配的任务数量，覆盖基于文件大小的估算。
+    pub fn with_capacity_hint(mut self, capacity: usize) -> Self {
+        self.capacity_hint = Some(capacity);
+        self
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
 
+    /// 委托 [`Self::load_state`] 一次性读入并解析文件，只返回其中的任务。
+    ///
+    /// 新信封与旧版裸映射都能加载；`TaskManager` 加载时走的是流式的 [`Self::load_state_streaming`]。
     pub fn load_tasks(&self) -> std::result::Result<TaskLoadOutcome, TaskLoadError> {
+        self.load_state().map(into_outcome)
+    }
+
+    /// 通过 `BufReader` 流式解析任务文件，避免把整个文件读成 `String`。
+    pub fn load_tasks_streaming(&self) -> std::result::Result<TaskLoadOutcome, TaskLoadError> {
+        self.load_state_streaming().map(into_outcome)
+    }
+
+    /// 一次性读入并解析版本化信封，兼容旧版裸任务映射；文件不存在时返回 `None`。
+    pub fn load_state(&self) -> std::result::Result<Option<PersistState>, TaskLoadError> {
         let data = match fs::read_to_string(&self.path) {
             Ok(data) => data,
             Err(source) if source.kind() == io::ErrorKind::NotFound => {
-                return Ok(TaskLoadOutcome::NotFound);
+                return Ok(None);
             }
             Err(source) => {
                 return Err(TaskLoadError::Read {
@@ -86,46 +202,434 @@ impl TaskStorage {
             }
         };
 
-        let tasks = serde_json::from_str(&data).map_err(|source| TaskLoadError::Parse {
-            path: self.path.clone(),
-            source,
-        })?;
+        let mut deserializer = serde_json::Deserializer::from_str(&data);
+        let state = PersistStateSeed { capacity: 0 }
+            .deserialize(&mut deserializer)
+            .and_then(|state| deserializer.end().map(|()| state))
+            .map_err(|source| TaskLoadError::Parse {
+                path: self.path.clone(),
+                source,
+            })?;
 
-        Ok(TaskLoadOutcome::Loaded(tasks))
+        Ok(Some(state))
+    }
+
+    /// 流式解析版本化信封，兼容旧版裸任务映射；文件不存在时返回 `None`。
+    pub fn load_state_streaming(&self) -> std::result::Result<Option<PersistState>, TaskLoadError> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(source) if source.kind() == io::ErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(source) => {
+                return Err(TaskLoadError::Read {
+                    path: self.path.clone(),
+                    source,
+                });
+            }
+        };
+
+        let capacity = self.capacity_hint.unwrap_or_else(|| {
+            let file_len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            usize::try_from(file_len / ESTIMATED_BYTES_PER_TASK).unwrap_or(0)
+        });
+
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(file));
+        let state = PersistStateSeed { capacity }
+            .deserialize(&mut deserializer)
+            .and_then(|state| deserializer.end().map(|()| state))
+            .map_err(|source| TaskLoadError::Parse {
+                path: self.path.clone(),
+                source,
+            })?;
+
+        Ok(Some(state))
+    }
+
+    /// 将无法解析的任务文件改名为 `<文件名>.corrupt.<毫秒时间戳>`，返回备份路径。
+    pub fn back_up_corrupt_file(&self) -> Result<PathBuf> {
+        let backup_path = corrupt_backup_path_for(&self.path);
+        fs::rename(&self.path, &backup_path).with_context(|| {
+            format!(
+                "Failed to back up corrupt tasks file to {}",
+                backup_path.display()
+            )
+        })?;
+        Ok(backup_path)
     }
 
     pub fn save_tasks(&self, tasks: &HashMap<u64, Task>) -> Result<()> {
-        ensure_parent_dir(&self.path)?;
-        let data = serde_json::to_vec_pretty(tasks).context("Failed to serialize tasks")?;
-        let temp_path = temporary_path_for(&self.path);
-
-        fs::write(&temp_path, &data).context("Failed to write temporary tasks file")?;
-
-        match fs::rename(&temp_path, &self.path) {
-            Ok(()) => Ok(()),
-            Err(rename_error) => {
-                if rename_error.kind() == io::ErrorKind::AlreadyExists {
-                    fs::remove_file(&self.path)
-                        .context("Failed to remove existing tasks file before replacement")?;
-                    fs::rename(&temp_path, &self.path)
-                        .context("Failed to replace tasks file with temporary file")?;
-                    Ok(())
-                } else {
-                    let _ = fs::remove_file(&temp_path);
-                    Err(rename_error).context("Failed to atomically replace tasks file")
+        self.save_state(tasks, next_task_id(tasks))
+    }
+
+    /// 以当前版本的信封格式写入任务和下一个可用 ID。
+    pub fn save_state(&self, tasks: &HashMap<u64, Task>, next_id: u64) -> Result<()> {
+        let envelope = PersistStateRef {
+            version: PersistState::CURRENT_VERSION,
+            next_id,
+            tasks,
+        };
+        let data = serde_json::to_vec_pretty(&envelope).context("Failed to serialize tasks")?;
+        replace_file_atomically(&self.path, &data)
+    }
+}
+
+fn into_outcome(state: Option<PersistState>) -> TaskLoadOutcome {
+    match state {
+        None => TaskLoadOutcome::NotFound,
+        Some(state) => TaskLoadOutcome::Loaded(state.tasks),
+    }
+}
+
+/// 序列化时借用任务表的信封视图，避免为保存而克隆全部任务。
+#[derive(Serialize)]
+struct PersistStateRef<'a> {
+    version: u32,
+    next_id: u64,
+    tasks: &'a HashMap<u64, Task>,
+}
+
+/// 同时识别版本化信封与旧版裸任务映射的反序列化器。
+///
+/// 顶层键为 `version`/`next_id`/`tasks` 时按信封解析，其余键一律视为旧版的任务 ID。
+struct PersistStateSeed {
+    capacity: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for PersistStateSeed {
+    type Value = PersistState;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PersistStateSeed {
+    type Value = PersistState;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a versioned task file or a map of task ids to tasks")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut access: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut version = None;
+        let mut next_id = None;
+        let mut tasks = None;
+        let mut legacy: Option<HashMap<u64, Task>> = None;
+
+        while let Some(key) = access.next_key::<String>()? {
+            match key.as_str() {
+                "version" => version = Some(access.next_value::<u32>()?),
+                "next_id" => next_id = Some(access.next_value::<u64>()?),
+                "tasks" => {
+                    let capacity = self.capacity;
+                    tasks = Some(access.next_value_seed(TaskMapSeed { capacity })?);
+                }
+                id => {
+                    let id = id
+                        .parse::<u64>()
+                        .map_err(|_| A::Error::custom(format!("invalid task id `{id}`")))?;
+                    let task = access.next_value::<Task>()?;
+                    legacy
+                        .get_or_insert_with(|| HashMap::with_capacity(self.capacity))
+                        .insert(id, task);
                 }
             }
         }
+
+        let is_envelope = version.is_some() || next_id.is_some() || tasks.is_some();
+        match (is_envelope, legacy) {
+            (true, Some(_)) => Err(A::Error::custom(
+                "task file mixes envelope fields with legacy task ids",
+            )),
+            (true, None) => {
+                let version = version.ok_or_else(|| A::Error::missing_field("version"))?;
+                if version > PersistState::CURRENT_VERSION {
+                    return Err(A::Error::custom(format!(
+                        "unsupported task file version {version}"
+                    )));
+                }
+                let tasks = tasks.ok_or_else(|| A::Error::missing_field("tasks"))?;
+                let next_id = next_id.unwrap_or_else(|| next_task_id(&tasks));
+                Ok(PersistState {
+                    version,
+                    next_id,
+                    tasks,
+                })
+            }
+            (false, legacy) => Ok(PersistState::from_legacy(legacy.unwrap_or_default())),
+        }
+    }
+}
+
+/// 预先分配容量的任务表反序列化器，避免大文件加载时 `HashMap` 反复扩容。
+struct TaskMapSeed {
+    capacity: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for TaskMapSeed {
+    type Value = HashMap<u64, Task>;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TaskMapSeed {
+    type Value = HashMap<u64, Task>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of task ids to tasks")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut access: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut tasks = HashMap::with_capacity(self.capacity);
+        while let Some((id, task)) = access.next_entry::<u64, Task>()? {
+            tasks.insert(id, task);
+        }
+        Ok(tasks)
     }
 }
 
+/// 文件头魔数，用于区分二进制任务文件与其他格式；该版本的载荷是带 `next_id` 的信封。
+const BINCODE_MAGIC: &[u8; 4] = b"TMB2";
+
+/// 旧版二进制文件的魔数，载荷只是任务列表，加载时按旧版裸映射迁移。
+const BINCODE_LEGACY_MAGIC: &[u8; 4] = b"TMB1";
+
+/// 二进制文件中的信封；`Task` 的 `serde(flatten)` 不被 bincode 支持，这里保存内部视图。
+#[derive(Serialize)]
+struct BincodeStateRef<'a> {
+    version: u32,
+    next_id: u64,
+    tasks: Vec<&'a TaskView>,
+}
+
+#[derive(Deserialize)]
+struct BincodeState {
+    version: u32,
+    next_id: u64,
+    tasks: Vec<TaskView>,
+}
+
+/// 基于 `bincode` 的紧凑二进制存储后端，适合任务量较大的场景。
+#[derive(Debug, Clone)]
+pub struct BincodeFileStore {
+    path: PathBuf,
+}
+
+impl BincodeFileStore {
+    pub fn new(config: TaskStorageConfig) -> Result<Self> {
+        ensure_parent_dir(config.path())?;
+        Ok(Self { path: config.path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load_tasks(&self) -> std::result::Result<TaskLoadOutcome, TaskLoadError> {
+        self.load_state().map(into_outcome)
+    }
+
+    /// 读取版本化信封，兼容只含任务列表的旧版文件；文件不存在时返回 `None`。
+    pub fn load_state(&self) -> std::result::Result<Option<PersistState>, TaskLoadError> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(source) if source.kind() == io::ErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(source) => {
+                return Err(TaskLoadError::Read {
+                    path: self.path.clone(),
+                    source,
+                });
+            }
+        };
+
+        if let Some(payload) = data.strip_prefix(BINCODE_MAGIC.as_slice()) {
+            let state: BincodeState =
+                bincode::deserialize(payload).map_err(|source| TaskLoadError::Decode {
+                    path: self.path.clone(),
+                    source,
+                })?;
+            let version = state.version;
+            if version > PersistState::CURRENT_VERSION {
+                let message = format!("unsupported task file version {version}");
+                return Err(self.decode_error(message));
+            }
+            return Ok(Some(PersistState {
+                version,
+                next_id: state.next_id,
+                tasks: views_into_tasks(state.tasks),
+            }));
+        }
+
+        let Some(payload) = data.strip_prefix(BINCODE_LEGACY_MAGIC.as_slice()) else {
+            if looks_like_json(&data) {
+                return Err(TaskLoadError::UnexpectedJson {
+                    path: self.path.clone(),
+                });
+            }
+            return Err(self.decode_error("missing task file header".to_string()));
+        };
+
+        let views: Vec<TaskView> =
+            bincode::deserialize(payload).map_err(|source| TaskLoadError::Decode {
+                path: self.path.clone(),
+                source,
+            })?;
+        Ok(Some(PersistState::from_legacy(views_into_tasks(views))))
+    }
+
+    pub fn save_tasks(&self, tasks: &HashMap<u64, Task>) -> Result<()> {
+        self.save_state(tasks, next_task_id(tasks))
+    }
+
+    /// 以当前版本的信封格式写入任务和下一个可用 ID。
+    pub fn save_state(&self, tasks: &HashMap<u64, Task>, next_id: u64) -> Result<()> {
+        let envelope = BincodeStateRef {
+            version: PersistState::CURRENT_VERSION,
+            next_id,
+            tasks: tasks.values().map(|task| &**task).collect(),
+        };
+        let mut data = BINCODE_MAGIC.to_vec();
+        bincode::serialize_into(&mut data, &envelope).context("Failed to serialize tasks")?;
+        replace_file_atomically(&self.path, &data)
+    }
+
+    fn decode_error(&self, message: String) -> TaskLoadError {
+        TaskLoadError::Decode {
+            path: self.path.clone(),
+            source: Box::new(bincode::ErrorKind::Custom(message)),
+        }
+    }
+}
+
+fn views_into_tasks(views: Vec<TaskView>) -> HashMap<u64, Task> {
+    views
+        .into_iter()
+        .map(|view| (view.id, Task::from(view)))
+        .collect()
+}
+
+/// 只保存在内存中的存储后端，不读写任何文件，适合测试与快速构造示例数据。
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    state: RefCell<Option<PersistState>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TaskStore for TaskStorage {
+    fn load(&self) -> Result<HashMap<u64, Task>> {
+        match self.load_outcome()? {
+            TaskLoadOutcome::NotFound => Ok(HashMap::new()),
+            TaskLoadOutcome::Loaded(tasks) => Ok(tasks),
+        }
+    }
+
+    fn save(&self, tasks: &HashMap<u64, Task>) -> Result<()> {
+        self.save_tasks(tasks)
+    }
+
+    fn load_outcome(&self) -> Result<TaskLoadOutcome> {
+        Ok(into_outcome(self.load_persisted()?))
+    }
+
+    fn load_persisted(&self) -> Result<Option<PersistState>> {
+        Ok(self.load_state_streaming()?)
+    }
+
+    fn save_persisted(&self, tasks: &HashMap<u64, Task>, next_id: u64) -> Result<()> {
+        self.save_state(tasks, next_id)
+    }
+}
+
+impl TaskStore for BincodeFileStore {
+    fn load(&self) -> Result<HashMap<u64, Task>> {
+        match self.load_outcome()? {
+            TaskLoadOutcome::NotFound => Ok(HashMap::new()),
+            TaskLoadOutcome::Loaded(tasks) => Ok(tasks),
+        }
+    }
+
+    fn save(&self, tasks: &HashMap<u64, Task>) -> Result<()> {
+        self.save_tasks(tasks)
+    }
+
+    fn load_outcome(&self) -> Result<TaskLoadOutcome> {
+        Ok(self.load_tasks()?)
+    }
+
+    fn load_persisted(&self) -> Result<Option<PersistState>> {
+        Ok(self.load_state()?)
+    }
+
+    fn save_persisted(&self, tasks: &HashMap<u64, Task>, next_id: u64) -> Result<()> {
+        self.save_state(tasks, next_id)
+    }
+}
+
+fn looks_like_json(data: &[u8]) -> bool {
+    matches!(
+        data.iter().find(|byte| !byte.is_ascii_whitespace()),
+        Some(b'{') | Some(b'[')
+    )
+}
+
+fn replace_file_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    ensure_parent_dir(path)?;
+    // 持有锁直到替换完成，避免多个进程交错写入同一任务文件
+    let _lock = FileLock::acquire_blocking(path, SAVE_LOCK_TIMEOUT)
+        .context("Failed to lock tasks file for writing")?;
+    let temp_path = temporary_path_for(path);
+
+    fs::write(&temp_path, data).context("Failed to write temporary tasks file")?;
+
+    match fs::rename(&temp_path, path) {
+        Ok(()) => Ok(()),
+        Err(rename_error) => {
+            if rename_error.kind() == io::ErrorKind::AlreadyExists {
+                fs::remove_file(path)
+                    .context("Failed to remove existing tasks file before replacement")?;
+                fs::rename(&temp_path, path)
+                    .context("Failed to replace tasks file with temporary file")?;
+                Ok(())
+            } else {
+                let _ = fs::remove_file(&temp_path);
+                Err(rename_error).context("Failed to atomically replace tasks file")
+            }
+        }
+    }
+}
+
+const STORAGE_DIR_NAME: &str = ".task_manager";
+const STORAGE_FILE_NAME: &str = "tasks.json";
+
 fn default_storage_path() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not find home directory")?;
-    Ok(home.join(".task_manager").join("tasks.json"))
+    Ok(home.join(STORAGE_DIR_NAME).join(STORAGE_FILE_NAME))
 }
 
 fn fallback_storage_path() -> PathBuf {
-    PathBuf::from(".task_manager").join("tasks.json")
+    PathBuf::from(STORAGE_DIR_NAME).join(STORAGE_FILE_NAME)
 }
 
 fn ensure_parent_dir(path: &Path) -> Result<()> {
@@ -135,6 +639,19 @@ fn ensure_parent_dir(path: &Path) -> Result<()> {
     Ok(())
 }
 
+fn corrupt_backup_path_for(path: &Path) -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+    let mut file_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_else(|| "tasks.json".into());
+    file_name.push(format!(".corrupt.{timestamp}"));
+    path.with_file_name(file_name)
+}
+
 fn temporary_path_for(path: &Path) -> PathBuf {
     let file_name = path
         .file_name()
@@ -152,3 +669,35 @@ fn temporary_path_for(path: &Path) -> PathBuf {
         None => PathBuf::from(temp_file_name),
     }
 }
+
+impl TaskStore for InMemoryStore {
+    fn load(&self) -> Result<HashMap<u64, Task>> {
+        Ok(self
+            .state
+            .borrow()
+            .as_ref()
+            .map(|state| state.tasks.clone())
+            .unwrap_or_default())
+    }
+
+    fn save(&self, tasks: &HashMap<u64, Task>) -> Result<()> {
+        self.save_persisted(tasks, next_task_id(tasks))
+    }
+
+    fn load_outcome(&self) -> Result<TaskLoadOutcome> {
+        Ok(into_outcome(self.load_persisted()?))
+    }
+
+    fn load_persisted(&self) -> Result<Option<PersistState>> {
+        Ok(self.state.borrow().clone())
+    }
+
+    fn save_persisted(&self, tasks: &HashMap<u64, Task>, next_id: u64) -> Result<()> {
+        *self.state.borrow_mut() = Some(PersistState {
+            version: PersistState::CURRENT_VERSION,
+            next_id,
+            tasks: tasks.clone(),
+        });
+        Ok(())
+    }
+}
@@ -1,38 +1,909 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fs;
+use std::rc::Rc;
+use std::sync::Mutex;
 
 use tempfile::tempdir;
 
+use clap::Parser;
+
 use super::{
-    Priority, Status, Task, TaskLoadError, TaskLoadOutcome, TaskManager, TaskManagerLoadState,
-    TaskStatistics, TaskStorage, TaskStorageConfig,
+    BincodeFileStore, ColorTheme, InMemoryStore, PersistState, Priority, Status, Task, TaskCli,
+    TaskCommand, TaskDiff, TaskLoadError, TaskLoadOutcome, TaskManager, TaskManagerLoadState,
+    TaskStatistics, TaskStorage, TaskStorageConfig, TaskStore, run_cli,
 };
 
+/// `colored` 的颜色开关是进程级全局状态，修改它的测试需串行执行。
+static COLOR_OVERRIDE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 记录保存次数的内存存储，用于验证 `TaskManager` 与后端的交互。
+#[derive(Default)]
+struct RecordingStore {
+    tasks: RefCell<HashMap<u64, Task>>,
+    /// 共享计数，管理器析构后仍可检查写入次数
+    saves: Rc<Cell<usize>>,
+    fail_saves: Cell<bool>,
+}
+
+impl TaskStore for RecordingStore {
+    fn load(&self) -> anyhow::Result<HashMap<u64, Task>> {
+        Ok(self.tasks.borrow().clone())
+    }
+
+    fn save(&self, tasks: &HashMap<u64, Task>) -> anyhow::Result<()> {
+        if self.fail_saves.get() {
+            anyhow::bail!("simulated save failure");
+        }
+        *self.tasks.borrow_mut() = tasks.clone();
+        self.saves.set(self.saves.get() + 1);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_task_creation() {
+    let task = Task::new(1, "Test task", Priority::High);
+    assert_eq!(task.id(), 1);
+    assert_eq!(task.title(), "Test task");
+    assert_eq!(task.priority(), Priority::High);
+    assert_eq!(task.status(), Status::Pending);
+}
+
+#[test]
+fn test_task_display_uses_custom_color_theme() {
+    let _guard = COLOR_OVERRIDE_LOCK.lock().unwrap();
+    crate::set_plain_output(false);
+    colored::control::set_override(true);
+    let task = Task::new(1, "Themed task", Priority::Urgent);
+
+    let default_output = task.display(&ColorTheme::default());
+    let custom_theme = ColorTheme {
+        urgent: colored::Color::Blue,
+        ..ColorTheme::default()
+    };
+    let custom_output = task.display(&custom_theme);
+
+    assert!(default_output.contains("\x1b[1;31m[URGENT]"));
+    assert!(custom_output.contains("\x1b[1;34m[URGENT]"));
+    assert!(!custom_output.contains("\x1b[1;31m"));
+}
+
+#[test]
+fn test_priority_next_and_prev_saturate_at_ends() {
+    assert_eq!(Priority::Low.next(), Priority::Medium);
+    assert_eq!(Priority::Medium.next(), Priority::High);
+    assert_eq!(Priority::High.next(), Priority::Urgent);
+    assert_eq!(Priority::Urgent.next(), Priority::Urgent);
+
+    assert_eq!(Priority::Urgent.prev(), Priority::High);
+    assert_eq!(Priority::High.prev(), Priority::Medium);
+    assert_eq!(Priority::Medium.prev(), Priority::Low);
+    assert_eq!(Priority::Low.prev(), Priority::Low);
+}
+
+#[test]
+fn test_bump_priority_updates_and_persists_stored_task() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+    let id = manager
+        .add_task(Task::new(0, "Bump me", Priority::High))
+        .unwrap();
+
+    assert_eq!(manager.bump_priority(id, true).unwrap(), Priority::Urgent);
+    assert_eq!(manager.bump_priority(id, true).unwrap(), Priority::Urgent);
+    assert_eq!(manager.bump_priority(id, false).unwrap(), Priority::High);
+
+    let reloaded = TaskManager::with_storage_path(&storage_path).unwrap();
+    assert_eq!(reloaded.get_task(id).unwrap().priority(), Priority::High);
+    assert!(manager.bump_priority(id + 1, true).is_err());
+}
+
+#[test]
+fn test_plain_output_removes_ansi_codes_from_display() {
+    let _guard = COLOR_OVERRIDE_LOCK.lock().unwrap();
+    let task = Task::new(3, "Plain task", Priority::High).with_tags(vec!["ci".to_string()]);
+
+    crate::set_plain_output(false);
+    colored::control::set_override(true);
+    assert!(task.display(&ColorTheme::default()).contains('\x1b'));
+
+    crate::set_plain_output(true);
+    let plain = task.display(&ColorTheme::default());
+    crate::set_plain_output(false);
+
+    assert!(
+        !plain.contains('\x1b'),
+        "plain output should have no ANSI codes: {plain:?}"
+    );
+    assert_eq!(plain, "[PENDING] [HIGH] 3 - Plain task #ci");
+}
+
+#[test]
+fn test_plain_output_is_visible_from_spawned_threads() {
+    let _guard = COLOR_OVERRIDE_LOCK.lock().unwrap();
+
+    crate::set_plain_output(true);
+    let seen_by_worker = std::thread::spawn(crate::plain_output).join().unwrap();
+    crate::set_plain_output(false);
+
+    assert!(seen_by_worker, "worker threads should see the plain flag");
+}
+
+#[test]
+fn test_strip_ansi_recovers_plain_content_from_colored_display() {
+    let _guard = COLOR_OVERRIDE_LOCK.lock().unwrap();
+    let task = Task::new(4, "Colored task", Priority::Urgent).with_tags(vec!["ops".to_string()]);
+
+    crate::set_plain_output(false);
+    colored::control::set_override(true);
+    let colored_output = task.display(&ColorTheme::default());
+    assert!(colored_output.contains('\x1b'));
+
+    let stripped = crate::best_practices::strip_ansi(&colored_output);
+    assert_eq!(stripped, "⏳ [URGENT] 4 - Colored task #ops");
+    assert_eq!(crate::best_practices::strip_ansi(&stripped), stripped);
+}
+
+#[test]
+fn test_task_completion() {
+    let mut task = Task::new(1, "Test task", Priority::Medium);
+    assert_eq!(task.status(), Status::Pending);
+    assert!(task.completed_at().is_none());
+
+    task.complete();
+    assert_eq!(task.status(), Status::Completed);
+    assert!(task.completed_at().is_some());
+}
+
+#[test]
+fn test_priority_from_str() {
+    assert_eq!("high".parse::<Priority>().unwrap(), Priority::High);
+    assert_eq!("LOW".parse::<Priority>().unwrap(), Priority::Low);
+    assert_eq!("u".parse::<Priority>().unwrap(), Priority::Urgent);
+    assert!("unknown".parse::<Priority>().is_err());
+}
+
+#[test]
+fn test_priority_and_status_serialize_as_snake_case_strings() {
+    let priorities = [
+        (Priority::Low, "\"low\""),
+        (Priority::Medium, "\"medium\""),
+        (Priority::High, "\"high\""),
+        (Priority::Urgent, "\"urgent\""),
+    ];
+    for (priority, expected) in priorities {
+        let json = serde_json::to_string(&priority).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<Priority>(&json).unwrap(), priority);
+    }
+
+    let statuses = [
+        (Status::Pending, "\"pending\""),
+        (Status::InProgress, "\"in_progress\""),
+        (Status::Completed, "\"completed\""),
+        (Status::Cancelled, "\"cancelled\""),
+    ];
+    for (status, expected) in statuses {
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<Status>(&json).unwrap(), status);
+    }
+}
+
+#[test]
+fn test_storage_loads_legacy_pascal_case_priority_and_status() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("legacy_tasks.json");
+    let legacy_json = r#"{
+  "7": {
+    "id": 7,
+    "title": "Legacy task",
+    "description": null,
+    "priority": "Urgent",
+    "status": "InProgress",
+    "tags": ["legacy"],
+    "created_at": "2024-01-02T03:04:05+00:00",
+    "updated_at": "2024-01-02T03:04:05+00:00",
+    "completed_at": null,
+    "due_date": null
+  }
+}"#;
+    fs::write(&storage_path, legacy_json).unwrap();
+
+    let mut manager = TaskManager::with_storage_path(&storage_path)
+        .expect("legacy PascalCase files should still load");
+    let task = manager.get_task(7).expect("legacy task should be loaded");
+    assert_eq!(task.priority(), Priority::Urgent);
+    assert_eq!(task.status(), Status::InProgress);
+
+    manager.persist().unwrap();
+    let rewritten = fs::read_to_string(&storage_path).unwrap();
+    assert!(
+        rewritten.contains("\"in_progress\"") && rewritten.contains("\"urgent\""),
+        "re-saving should migrate legacy names to snake_case, got: {rewritten}"
+    );
+}
+
+#[test]
+fn test_storage_config_honors_task_manager_dir_override() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path().to_string_lossy().into_owned();
+
+    let config = TaskStorageConfig::from_env_with(|key| {
+        (key == TaskStorageConfig::DIR_ENV_VAR).then(|| dir.clone())
+    })
+    .unwrap();
+    assert_eq!(config.path(), temp_dir.path().join("tasks.json"));
+
+    let default = TaskStorageConfig::from_env_with(|_| None).unwrap();
+    assert!(default.path().ends_with(".task_manager/tasks.json"));
+    let empty = TaskStorageConfig::from_env_with(|_| Some(String::new())).unwrap();
+    assert_eq!(empty.path(), default.path());
+}
+
+#[test]
+fn test_persisted_next_id_survives_deleting_highest_task() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+
+    {
+        let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+        for title in ["one", "two", "three"] {
+            manager
+                .add_task(Task::new(0, title, Priority::Medium))
+                .unwrap();
+        }
+        manager.delete_task(3).unwrap();
+    }
+
+    let state = TaskStorage::new(TaskStorageConfig::from_path(&storage_path))
+        .unwrap()
+        .load_state()
+        .unwrap()
+        .expect("saved file should exist");
+    assert_eq!(state.version, PersistState::CURRENT_VERSION);
+    assert_eq!(state.next_id, 4);
+    assert_eq!(state.tasks.len(), 2);
+
+    let mut reloaded = TaskManager::with_storage_path(&storage_path).unwrap();
+    let next_id = reloaded
+        .add_task(Task::new(0, "after reload", Priority::Low))
+        .unwrap();
+    assert_eq!(next_id, 4, "deleted ids must not be reused after reload");
+}
+
+#[test]
+fn test_legacy_bare_map_file_recomputes_next_id_and_migrates_on_save() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut legacy = HashMap::new();
+    for id in [2, 5] {
+        legacy.insert(id, Task::new(id, format!("legacy {id}"), Priority::Low));
+    }
+    fs::write(&storage_path, serde_json::to_string(&legacy).unwrap()).unwrap();
+
+    let storage = TaskStorage::new(TaskStorageConfig::from_path(&storage_path)).unwrap();
+    let state = storage.load_state_streaming().unwrap().unwrap();
+    assert_eq!(state.version, 0);
+    assert_eq!(state.next_id, 6);
+
+    let mut manager = TaskManager::with_storage(storage).unwrap();
+    let next_id = manager
+        .add_task(Task::new(0, "migrated", Priority::High))
+        .unwrap();
+    assert_eq!(next_id, 6);
+
+    let rewritten: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&storage_path).unwrap()).unwrap();
+    assert_eq!(rewritten["version"], PersistState::CURRENT_VERSION);
+    assert_eq!(rewritten["next_id"], 7);
+    assert_eq!(rewritten["tasks"].as_object().unwrap().len(), 3);
+}
+
+#[test]
+fn test_streaming_and_buffered_load_produce_identical_state() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+
+    {
+        let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+        manager
+            .add_task(
+                Task::new(0, "Streamed task", Priority::High)
+                    .with_description("loaded via BufReader")
+                    .with_tags(vec!["io".to_string()]),
+            )
+            .unwrap();
+        let second_id = manager
+            .add_task(Task::new(0, "Completed task", Priority::Low))
+            .unwrap();
+        manager.complete_task(second_id).unwrap();
+    }
+
+    let storage = TaskStorage::new(TaskStorageConfig::from_path(&storage_path)).unwrap();
+    let buffered = match storage.load_tasks().unwrap() {
+        TaskLoadOutcome::Loaded(tasks) => tasks,
+        TaskLoadOutcome::NotFound => panic!("buffered load should find the file"),
+    };
+    let streamed = match storage.load_tasks_streaming().unwrap() {
+        TaskLoadOutcome::Loaded(tasks) => tasks,
+        TaskLoadOutcome::NotFound => panic!("streaming load should find the file"),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&buffered).unwrap(),
+        serde_json::to_value(&streamed).unwrap()
+    );
+
+    let manager = TaskManager::with_storage(storage).unwrap();
+    assert_eq!(
+        manager.load_state(),
+        TaskManagerLoadState::LoadedFromStorage
+    );
+    assert_eq!(manager.list_tasks(None).len(), buffered.len());
+    for (id, task) in &buffered {
+        let loaded = manager.get_task(*id).expect("task should be loaded");
+        assert_eq!(
+            serde_json::to_value(loaded).unwrap(),
+            serde_json::to_value(task).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_streaming_load_reports_missing_file_as_not_found() {
+    let temp_dir = tempdir().unwrap();
+    let storage = TaskStorage::new(TaskStorageConfig::from_path(
+        temp_dir.path().join("missing.json"),
+    ))
+    .unwrap();
+
+    assert!(matches!(
+        storage.load_tasks_streaming(),
+        Ok(TaskLoadOutcome::NotFound)
+    ));
+}
+
+#[test]
+fn test_task_manager_with_in_memory_store_records_saves() {
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    assert_eq!(manager.store().saves.get(), 0, "loading should not save");
+
+    let first = manager
+        .add_task(Task::new(0, "In memory", Priority::Medium))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "Also in memory", Priority::Low))
+        .unwrap();
+    manager.complete_task(first).unwrap();
+
+    assert_eq!(manager.store().saves.get(), 3);
+    let stored = manager.store().tasks.borrow();
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[&first].status(), Status::Completed);
+}
+
+#[test]
+fn test_clear_completed_removes_only_completed_tasks_and_saves_once() {
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    let mut ids = Vec::new();
+    for title in ["done 1", "pending", "done 2", "in progress", "cancelled"] {
+        ids.push(
+            manager
+                .add_task(Task::new(0, title, Priority::Medium))
+                .unwrap(),
+        );
+    }
+    manager.complete_task(ids[0]).unwrap();
+    manager.complete_task(ids[2]).unwrap();
+    manager.start_task(ids[3]).unwrap();
+    manager.cancel_task(ids[4]).unwrap();
+    let saves_before = manager.store().saves.get();
+
+    assert_eq!(manager.clear_completed().unwrap(), 2);
+
+    assert_eq!(manager.store().saves.get(), saves_before + 1);
+    assert!(manager.get_task(ids[0]).is_none());
+    assert!(manager.get_task(ids[2]).is_none());
+    for id in [ids[1], ids[3], ids[4]] {
+        assert!(manager.get_task(id).is_some());
+    }
+    assert_eq!(manager.store().tasks.borrow().len(), 3);
+}
+
+#[test]
+fn test_clear_completed_without_completed_tasks_is_a_no_op() {
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    let id = manager
+        .add_task(Task::new(0, "still pending", Priority::Low))
+        .unwrap();
+    let saves_before = manager.store().saves.get();
+
+    assert_eq!(manager.clear_completed().unwrap(), 0);
+
+    assert_eq!(manager.store().saves.get(), saves_before);
+    assert!(manager.get_task(id).is_some());
+}
+
+#[test]
+fn test_delete_task_appends_one_archive_line_when_configured() {
+    let temp_dir = tempdir().unwrap();
+    let archive_path = temp_dir.path().join("archive.jsonl");
+    let mut manager = TaskManager::in_memory();
+    manager.set_archive_path(&archive_path);
+    let keep = manager
+        .add_task(Task::new(0, "keep", Priority::Low))
+        .unwrap();
+    let id = manager
+        .add_task(Task::new(0, "archive me", Priority::High))
+        .unwrap();
+
+    manager.delete_task(id).unwrap();
+
+    let archive = fs::read_to_string(&archive_path).unwrap();
+    let lines: Vec<&str> = archive.lines().collect();
+    assert_eq!(lines.len(), 1);
+    let archived: Task = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(archived.id(), id);
+    assert_eq!(archived.title(), "archive me");
+    assert!(manager.get_task(keep).is_some());
+
+    assert!(manager.delete_task(id).is_err());
+    let archive = fs::read_to_string(&archive_path).unwrap();
+    assert_eq!(archive.lines().count(), 1, "failed delete must not archive");
+}
+
+#[test]
+fn test_failed_save_rolls_back_archive_lines() {
+    let temp_dir = tempdir().unwrap();
+    let archive_path = temp_dir.path().join("archive.jsonl");
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    manager.set_archive_path(&archive_path);
+    let archived = manager
+        .add_task(Task::new(0, "archived", Priority::Low))
+        .unwrap();
+    let kept = manager
+        .add_task(Task::new(0, "kept", Priority::High))
+        .unwrap();
+    manager.complete_task(kept).unwrap();
+    manager.delete_task(archived).unwrap();
+    let archive_lines = || fs::read_to_string(&archive_path).unwrap().lines().count();
+    assert_eq!(archive_lines(), 1);
+
+    manager.store().fail_saves.set(true);
+    assert!(manager.delete_task(kept).is_err());
+    assert_eq!(archive_lines(), 1, "failed delete must not stay archived");
+    assert!(manager.clear_completed().is_err());
+    assert_eq!(archive_lines(), 1, "failed clear must not stay archived");
+    assert!(manager.get_task(kept).is_some());
+
+    manager.store().fail_saves.set(false);
+    assert_eq!(manager.clear_completed().unwrap(), 1);
+    assert_eq!(archive_lines(), 2);
+}
+
+#[test]
+fn test_delete_task_without_archive_writes_nothing() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+    let id = manager
+        .add_task(Task::new(0, "gone", Priority::Medium))
+        .unwrap();
+
+    manager.delete_task(id).unwrap();
+
+    assert!(manager.get_task(id).is_none());
+    let entries: Vec<String> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        entries,
+        ["tasks.json"],
+        "only the store file should remain: no archive, lock or temp files"
+    );
+}
+
+#[test]
+fn test_clear_completed_archives_removed_tasks() {
+    let temp_dir = tempdir().unwrap();
+    let archive_path = temp_dir.path().join("archive.jsonl");
+    let mut manager = TaskManager::in_memory();
+    manager.set_archive_path(&archive_path);
+    let done = manager
+        .add_task(Task::new(0, "done", Priority::Medium))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "pending", Priority::Medium))
+        .unwrap();
+    manager.complete_task(done).unwrap();
+
+    assert_eq!(manager.clear_completed().unwrap(), 1);
+
+    let archive = fs::read_to_string(&archive_path).unwrap();
+    let ids: Vec<u64> = archive
+        .lines()
+        .map(|line| serde_json::from_str::<Task>(line).unwrap().id())
+        .collect();
+    assert_eq!(ids, vec![done]);
+}
+
+#[test]
+fn test_persist_clears_pending_changes_before_drop() {
+    let store = RecordingStore::default();
+    let saves = Rc::clone(&store.saves);
+    {
+        let mut manager = TaskManager::with_storage(store).unwrap();
+        manager.set_auto_save(false);
+        manager
+            .add_task(Task::new(0, "Persisted once", Priority::Medium))
+            .unwrap();
+
+        manager.persist().unwrap();
+        assert_eq!(saves.get(), 1);
+    }
+
+    assert_eq!(saves.get(), 1, "drop should not rewrite a persisted store");
+}
+
+#[test]
+fn test_flush_batches_changes_when_auto_save_is_off() {
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    manager.set_auto_save(false);
+
+    manager
+        .add_task(Task::new(0, "Batched one", Priority::Medium))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "Batched two", Priority::Low))
+        .unwrap();
+    assert_eq!(manager.store().saves.get(), 0, "edits only mark dirty");
+
+    manager.flush().unwrap();
+    assert_eq!(manager.store().saves.get(), 1);
+    assert_eq!(manager.store().tasks.borrow().len(), 2);
+
+    manager.flush().unwrap();
+    assert_eq!(manager.store().saves.get(), 1, "clean flush is a no-op");
+}
+
+#[test]
+fn test_dropping_manager_flushes_pending_changes() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+
+    {
+        let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+        manager.set_auto_save(false);
+        manager
+            .add_task(Task::new(0, "Saved on drop", Priority::High))
+            .unwrap();
+        assert!(!storage_path.exists(), "nothing written before drop");
+    }
+
+    let reloaded = TaskManager::with_storage_path(&storage_path).unwrap();
+    assert_eq!(reloaded.get_task(1).unwrap().title(), "Saved on drop");
+}
+
+#[test]
+fn test_list_tasks_owned_is_independent_of_later_mutations() {
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    let urgent = manager
+        .add_task(Task::new(0, "Urgent", Priority::Urgent))
+        .unwrap();
+    let low = manager
+        .add_task(Task::new(0, "Low", Priority::Low))
+        .unwrap();
+
+    let owned = manager.list_tasks_owned(None);
+    manager.complete_task(urgent).unwrap();
+    manager.delete_task(low).unwrap();
+
+    let titles = std::thread::spawn(move || {
+        owned
+            .iter()
+            .map(|task| (task.title().to_string(), task.status()))
+            .collect::<Vec<_>>()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(
+        titles,
+        vec![
+            ("Urgent".to_string(), Status::Pending),
+            ("Low".to_string(), Status::Pending),
+        ]
+    );
+    assert!(manager.list_tasks_owned(Some(Status::Pending)).is_empty());
+}
+
+#[test]
+fn test_bincode_store_round_trips_large_task_set_like_json() {
+    let temp_dir = tempdir().unwrap();
+    let bincode_path = temp_dir.path().join("tasks.bin");
+    let json_path = temp_dir.path().join("tasks.json");
+
+    let mut tasks = HashMap::new();
+    for id in 1..=1000 {
+        let priority = match id % 4 {
+            0 => Priority::Low,
+            1 => Priority::Medium,
+            2 => Priority::High,
+            _ => Priority::Urgent,
+        };
+        let mut task = Task::new(id, format!("Task {id}"), priority)
+            .with_tags(vec![format!("batch-{}", id % 10)])
+            .with_dependencies(if id > 1 { vec![id - 1] } else { Vec::new() });
+        if id % 3 == 0 {
+            task.complete();
+        }
+        if id % 5 == 0 {
+            task.set_description(Some(format!("Description for {id}")));
+        }
+        tasks.insert(id, task);
+    }
+
+    let bincode_store = BincodeFileStore::new(TaskStorageConfig::from_path(&bincode_path)).unwrap();
+    let json_store = TaskStorage::new(TaskStorageConfig::from_path(&json_path)).unwrap();
+    bincode_store.save(&tasks).unwrap();
+    json_store.save(&tasks).unwrap();
+
+    let from_bincode = bincode_store.load().unwrap();
+    let from_json = json_store.load().unwrap();
+    assert_eq!(from_bincode.len(), 1000);
+    assert_eq!(
+        serde_json::to_value(&from_bincode).unwrap(),
+        serde_json::to_value(&from_json).unwrap()
+    );
+    assert!(
+        fs::metadata(&bincode_path).unwrap().len() < fs::metadata(&json_path).unwrap().len(),
+        "bincode output should be more compact than pretty JSON"
+    );
+
+    let manager = TaskManager::with_storage(bincode_store).unwrap();
+    assert_eq!(
+        manager.load_state(),
+        TaskManagerLoadState::LoadedFromStorage
+    );
+    assert_eq!(manager.list_tasks(None).len(), 1000);
+}
+
+#[test]
+fn test_bincode_persisted_next_id_survives_deleting_highest_task() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.bin");
+    let open_store = || BincodeFileStore::new(TaskStorageConfig::from_path(&storage_path)).unwrap();
+
+    {
+        let mut manager = TaskManager::with_storage(open_store()).unwrap();
+        for title in ["one", "two", "three"] {
+            manager
+                .add_task(Task::new(0, title, Priority::Medium))
+                .unwrap();
+        }
+        manager.delete_task(3).unwrap();
+    }
+
+    let state = open_store()
+        .load_state()
+        .unwrap()
+        .expect("saved file should exist");
+    assert_eq!(state.version, PersistState::CURRENT_VERSION);
+    assert_eq!(state.next_id, 4);
+    assert_eq!(state.tasks.len(), 2);
+
+    let mut reloaded = TaskManager::with_storage(open_store()).unwrap();
+    let next_id = reloaded
+        .add_task(Task::new(0, "after reload", Priority::Low))
+        .unwrap();
+    assert_eq!(next_id, 4, "deleted ids must not be reused after reload");
+}
+
+#[test]
+fn test_bincode_store_rejects_json_file_with_clear_error() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    {
+        let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+        manager
+            .add_task(Task::new(0, "Stored as JSON", Priority::Low))
+            .unwrap();
+    }
+
+    let store = BincodeFileStore::new(TaskStorageConfig::from_path(&storage_path)).unwrap();
+    let error = store
+        .load_tasks()
+        .expect_err("JSON content should not decode as bincode");
+    assert!(
+        matches!(&error, TaskLoadError::UnexpectedJson { path } if path == &storage_path),
+        "JSON file should be reported as UnexpectedJson, got {error:?}"
+    );
+
+    let error = match TaskManager::with_storage(store) {
+        Ok(_) => panic!("manager should refuse a JSON file in the bincode store"),
+        Err(error) => error,
+    };
+    assert!(format!("{error:#}").contains("looks like JSON"));
+}
+
+#[test]
+fn test_bincode_store_missing_file_returns_not_found() {
+    let temp_dir = tempdir().unwrap();
+    let store = BincodeFileStore::new(TaskStorageConfig::from_path(
+        temp_dir.path().join("missing.bin"),
+    ))
+    .unwrap();
+
+    assert!(matches!(store.load_tasks(), Ok(TaskLoadOutcome::NotFound)));
+}
+
+#[test]
+fn test_streaming_load_preallocates_from_capacity_hint() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    {
+        let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+        for index in 0..3 {
+            manager
+                .add_task(Task::new(0, format!("Task {index}"), Priority::Low))
+                .unwrap();
+        }
+    }
+
+    let storage = TaskStorage::new(TaskStorageConfig::from_path(&storage_path))
+        .unwrap()
+        .with_capacity_hint(500);
+    let tasks = match storage.load_tasks_streaming().unwrap() {
+        TaskLoadOutcome::Loaded(tasks) => tasks,
+        TaskLoadOutcome::NotFound => panic!("tasks file should exist"),
+    };
+    assert_eq!(tasks.len(), 3);
+    assert!(tasks.capacity() >= 500);
+
+    let manager = TaskManager::with_capacity(&storage_path, 500).unwrap();
+    assert_eq!(manager.list_tasks(None).len(), 3);
+}
+
+#[test]
+fn test_task_cli_parses_each_subcommand() {
+    let parse = |line: &str| {
+        TaskCli::try_parse_from(std::iter::once("task-manager").chain(line.split_whitespace()))
+            .unwrap()
+            .command
+    };
+
+    assert_eq!(
+        parse("add --title Ship --priority urgent --tag a --tag b"),
+        TaskCommand::Add {
+            title: "Ship".to_string(),
+            priority: Priority::Urgent,
+            tags: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+    assert_eq!(
+        parse("add --title Defaults"),
+        TaskCommand::Add {
+            title: "Defaults".to_string(),
+            priority: Priority::Medium,
+            tags: Vec::new(),
+        }
+    );
+    assert_eq!(
+        parse("list --status in-progress"),
+        TaskCommand::List {
+            status: Some(Status::InProgress),
+        }
+    );
+    assert_eq!(parse("list"), TaskCommand::List { status: None });
+    assert_eq!(parse("complete 3"), TaskCommand::Complete { id: 3 });
+    assert_eq!(parse("delete 4"), TaskCommand::Delete { id: 4 });
+    assert_eq!(parse("stats"), TaskCommand::Stats);
+
+    assert!(TaskCli::try_parse_from(["task-manager", "add"]).is_err());
+    assert!(TaskCli::try_parse_from(["task-manager", "list", "--status", "bogus"]).is_err());
+}
+
 #[test]
-fn test_task_creation() {
-    let task = Task::new(1, "Test task", Priority::High);
-    assert_eq!(task.id(), 1);
-    assert_eq!(task.title(), "Test task");
-    assert_eq!(task.priority(), Priority::High);
-    assert_eq!(task.status(), Status::Pending);
+fn test_run_cli_dispatches_add_then_list() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    let added = run_cli(
+        TaskCli::parse_from(["task-manager", "add", "--title", "From CLI", "--tag", "cli"]),
+        &mut manager,
+    )
+    .unwrap();
+    assert_eq!(added, "Added task #1: From CLI");
+
+    let listed = run_cli(
+        TaskCli::parse_from(["task-manager", "list", "--status", "pending"]),
+        &mut manager,
+    )
+    .unwrap();
+    assert!(listed.contains("From CLI"), "list output: {listed}");
+    assert!(listed.contains("#cli"), "list output: {listed}");
+
+    let stats = run_cli(TaskCli::parse_from(["task-manager", "stats"]), &mut manager).unwrap();
+    assert!(stats.starts_with("Total: 1"));
 }
 
 #[test]
-fn test_task_completion() {
-    let mut task = Task::new(1, "Test task", Priority::Medium);
-    assert_eq!(task.status(), Status::Pending);
-    assert!(task.completed_at().is_none());
+fn test_import_json_reports_invalid_fields_per_record() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
 
-    task.complete();
-    assert_eq!(task.status(), Status::Completed);
-    assert!(task.completed_at().is_some());
+    let report = manager
+        .import_json(
+            r#"[
+                {"title": "Good record", "priority": "high", "status": "completed", "tags": ["ok"]},
+                {"title": "Bad record", "priority": "critical", "tags": "not-a-list"}
+            ]"#,
+        )
+        .unwrap();
+
+    assert_eq!(report.imported, 1);
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.errors.len(), 2);
+    assert!(report.errors.iter().all(|(index, _)| *index == 1));
+    let messages: Vec<&str> = report
+        .errors
+        .iter()
+        .map(|(_, message)| message.as_str())
+        .collect();
+    assert!(messages[0].starts_with("priority:"), "{messages:?}");
+    assert!(messages[1].starts_with("tags:"), "{messages:?}");
+
+    let tasks = manager.list_tasks(None);
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].title(), "Good record");
+    assert_eq!(tasks[0].priority(), Priority::High);
+    assert_eq!(tasks[0].status(), Status::Completed);
+
+    let reloaded = TaskManager::with_storage_path(&storage_path).unwrap();
+    assert_eq!(reloaded.list_tasks(None).len(), 1);
+    assert!(manager.import_json("not json").is_err());
 }
 
 #[test]
-fn test_priority_from_str() {
-    assert_eq!("high".parse::<Priority>().unwrap(), Priority::High);
-    assert_eq!("LOW".parse::<Priority>().unwrap(), Priority::Low);
-    assert_eq!("u".parse::<Priority>().unwrap(), Priority::Urgent);
-    assert!("unknown".parse::<Priority>().is_err());
+fn test_import_json_accepts_file_written_by_save() {
+    let temp_dir = tempdir().unwrap();
+    let source_path = temp_dir.path().join("source.json");
+    let mut source = TaskManager::with_storage_path(&source_path).unwrap();
+    source
+        .add_task(Task::new(0, "Exported one", Priority::High))
+        .unwrap();
+    let second = source
+        .add_task(Task::new(0, "Exported two", Priority::Low))
+        .unwrap();
+    source.complete_task(second).unwrap();
+
+    let saved = fs::read_to_string(&source_path).unwrap();
+    assert!(
+        saved.contains("\"next_id\""),
+        "save should write the envelope: {saved}"
+    );
+
+    let mut target = TaskManager::in_memory();
+    let report = target.import_json(&saved).unwrap();
+    assert_eq!(report.imported, 2, "{:?}", report.errors);
+    assert_eq!(report.skipped, 0);
+
+    let mut titles: Vec<String> = target
+        .list_tasks(None)
+        .iter()
+        .map(|task| task.title().to_string())
+        .collect();
+    titles.sort();
+    assert_eq!(titles, ["Exported one", "Exported two"]);
+    assert_eq!(target.list_tasks(Some(Status::Completed)).len(), 1);
+
+    let mixed = r#"{"version": 1, "tasks": {}, "7": {"title": "stray"}}"#;
+    assert!(target.import_json(mixed).is_err());
 }
 
 #[test]
@@ -50,6 +921,22 @@ fn test_task_manager_uses_injected_storage_path() {
     assert!(storage_path.exists());
 }
 
+#[test]
+fn test_save_reclaims_lock_left_by_crashed_process() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let lock_path = temp_dir.path().join("tasks.json.lock");
+    fs::write(&lock_path, "4000000000\n0\n").unwrap();
+
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+    manager
+        .add_task(Task::new(0, "After crash", Priority::Medium))
+        .unwrap();
+
+    assert!(storage_path.exists());
+    assert!(!lock_path.exists(), "the reclaimed lock should be released");
+}
+
 #[test]
 fn test_task_manager_reloads_existing_tasks_from_injected_storage() {
     let temp_dir = tempdir().unwrap();
@@ -112,6 +999,189 @@ fn test_task_manager_persists_status_and_assigns_next_id_after_reload() {
     assert_eq!(next_id, 3, "next id should continue from persisted max id");
 }
 
+#[test]
+fn test_ready_tasks_follow_dependency_chain() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    let design = manager
+        .add_task(Task::new(0, "Design schema", Priority::Medium))
+        .unwrap();
+    let implement = manager
+        .add_task(
+            Task::new(0, "Implement schema", Priority::Urgent).with_dependencies(vec![design]),
+        )
+        .unwrap();
+    let release = manager
+        .add_task(Task::new(0, "Release", Priority::High).with_dependencies(vec![implement]))
+        .unwrap();
+    let docs = manager
+        .add_task(Task::new(0, "Write docs", Priority::Low))
+        .unwrap();
+
+    let ready_ids: Vec<u64> = manager.ready_tasks().iter().map(|task| task.id()).collect();
+    assert_eq!(ready_ids, vec![design, docs]);
+    assert_eq!(manager.ready_count(), 2);
+
+    manager.complete_task(design).unwrap();
+    let ready_ids: Vec<u64> = manager.ready_tasks().iter().map(|task| task.id()).collect();
+    assert_eq!(
+        ready_ids,
+        vec![implement, docs],
+        "completing the dependency should unblock the next task, sorted by priority"
+    );
+
+    manager.complete_task(implement).unwrap();
+    let ready_ids: Vec<u64> = manager.ready_tasks().iter().map(|task| task.id()).collect();
+    assert_eq!(ready_ids, vec![release, docs]);
+}
+
+#[test]
+fn test_ready_tasks_skip_started_tasks_and_ignore_deleted_dependencies() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    let blocker = manager
+        .add_task(Task::new(0, "Blocker", Priority::Low))
+        .unwrap();
+    let blocked = manager
+        .add_task(Task::new(0, "Blocked", Priority::High).with_dependencies(vec![blocker]))
+        .unwrap();
+    manager.start_task(blocker).unwrap();
+
+    assert_eq!(manager.ready_count(), 0);
+
+    manager.delete_task(blocker).unwrap();
+    let ready_ids: Vec<u64> = manager.ready_tasks().iter().map(|task| task.id()).collect();
+    assert_eq!(ready_ids, vec![blocked]);
+}
+
+#[test]
+fn test_diff_reports_added_removed_and_modified_tasks() {
+    let temp_dir = tempdir().unwrap();
+    let base_path = temp_dir.path().join("base.json");
+    let changed_path = temp_dir.path().join("changed.json");
+
+    let mut base = TaskManager::with_storage_path(&base_path).unwrap();
+    let kept = base
+        .add_task(Task::new(0, "Kept as is", Priority::Low))
+        .unwrap();
+    let renamed = base
+        .add_task(Task::new(0, "Old title", Priority::Medium))
+        .unwrap();
+    let deleted = base
+        .add_task(Task::new(0, "Deleted later", Priority::High))
+        .unwrap();
+
+    fs::copy(&base_path, &changed_path).unwrap();
+    let mut changed = TaskManager::with_storage_path(&changed_path).unwrap();
+    assert!(
+        base.diff(&changed).is_empty(),
+        "identical snapshots have no diff"
+    );
+
+    changed
+        .update_task(renamed, |task| task.set_title("New title"))
+        .unwrap();
+    changed.delete_task(deleted).unwrap();
+    let added = changed
+        .add_task(Task::new(0, "Brand new", Priority::Urgent))
+        .unwrap();
+
+    assert_eq!(
+        base.diff(&changed),
+        TaskDiff {
+            added: vec![added],
+            removed: vec![deleted],
+            modified: vec![renamed],
+        }
+    );
+    assert!(
+        !base.diff(&changed).modified.contains(&kept),
+        "untouched tasks should not be reported as modified"
+    );
+
+    let reverse = changed.diff(&base);
+    assert_eq!(reverse.added, vec![deleted]);
+    assert_eq!(reverse.removed, vec![added]);
+    assert_eq!(reverse.modified, vec![renamed]);
+}
+
+#[test]
+fn test_completed_by_week_buckets_across_year_boundary() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("weekly_tasks.json");
+    let task_json = |id: u64, completed_at: Option<&str>| {
+        let (status, completed_at) = match completed_at {
+            Some(timestamp) => ("completed", format!("\"{timestamp}\"")),
+            None => ("pending", "null".to_string()),
+        };
+        format!(
+            r#""{id}": {{
+    "id": {id},
+    "title": "Task {id}",
+    "description": null,
+    "priority": "medium",
+    "status": "{status}",
+    "tags": [],
+    "created_at": "2020-12-01T12:00:00+00:00",
+    "updated_at": "2020-12-01T12:00:00+00:00",
+    "completed_at": {completed_at},
+    "due_date": null
+  }}"#
+        )
+    };
+    let entries = [
+        task_json(1, Some("2020-12-31T12:00:00+00:00")),
+        task_json(2, Some("2021-01-01T12:00:00+00:00")),
+        task_json(3, Some("2021-01-04T12:00:00+00:00")),
+        task_json(4, Some("2024-12-30T12:00:00+00:00")),
+        task_json(5, None),
+    ];
+    fs::write(&storage_path, format!("{{{}}}", entries.join(","))).unwrap();
+
+    let manager = TaskManager::with_storage_path(&storage_path).unwrap();
+    let weeks = manager.completed_by_week();
+
+    assert_eq!(
+        weeks.into_iter().collect::<Vec<_>>(),
+        vec![((2020, 53), 2), ((2021, 1), 1), ((2025, 1), 1)],
+        "Dec 31 2020 and Jan 1 2021 share ISO week 2020-W53; Dec 30 2024 is 2025-W01"
+    );
+}
+
+#[test]
+fn test_tag_report_markdown_counts_and_completion_rate() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    let backend = vec!["backend".to_string()];
+    let done = manager
+        .add_task(Task::new(0, "API", Priority::High).with_tags(backend.clone()))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "DB", Priority::Medium).with_tags(backend.clone()))
+        .unwrap();
+    manager
+        .add_task(
+            Task::new(0, "Docs", Priority::Low)
+                .with_tags(vec!["backend".to_string(), "docs".to_string()]),
+        )
+        .unwrap();
+    manager.complete_task(done).unwrap();
+
+    let report = manager.tag_report_markdown();
+    let rows: Vec<&str> = report
+        .lines()
+        .filter(|line| line.starts_with("| #"))
+        .collect();
+
+    assert_eq!(rows, vec!["| #backend | 3 | 33% |", "| #docs | 1 | 0% |"]);
+}
+
 #[test]
 fn test_task_manager_search_matches_title_description_and_tags_case_insensitively() {
     let temp_dir = tempdir().unwrap();
@@ -146,6 +1216,40 @@ fn test_task_manager_search_matches_title_description_and_tags_case_insensitivel
     assert_eq!(tag_matches[0].title(), "Prepare release");
 }
 
+#[test]
+fn test_fuzzy_search_matches_title_with_one_typo() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    manager
+        .add_task(Task::new(0, "Deploy service", Priority::Low))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "Deploy servics", Priority::Urgent))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "Write changelog", Priority::High))
+        .unwrap();
+
+    let matches = manager.fuzzy_search("DEPLOY SERVIC", 1);
+    let titles: Vec<&str> = matches.iter().map(|task| task.title()).collect();
+    assert_eq!(titles, vec!["Deploy servics", "Deploy service"]);
+}
+
+#[test]
+fn test_fuzzy_search_ignores_far_off_queries() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    manager
+        .add_task(Task::new(0, "Deploy service", Priority::Low))
+        .unwrap();
+
+    assert!(manager.fuzzy_search("write changelog", 2).is_empty());
+}
+
 #[test]
 fn test_task_manager_statistics_and_pending_filter_reflect_task_states() {
     let temp_dir = tempdir().unwrap();
@@ -189,11 +1293,168 @@ fn test_task_manager_statistics_and_pending_filter_reflect_task_states() {
             pending: 1,
             in_progress: 1,
             urgent: 1,
+            by_priority: [1, 1, 1, 1],
         },
         "statistics should reflect each task state and count unfinished urgent work"
     );
 }
 
+/// 旧实现的多次遍历统计，作为单次遍历结果的对照
+fn multi_pass_statistics(tasks: &[&Task]) -> TaskStatistics {
+    let count =
+        |predicate: &dyn Fn(&Task) -> bool| tasks.iter().filter(|task| predicate(task)).count();
+
+    TaskStatistics {
+        total: tasks.len(),
+        completed: count(&|task| task.status() == Status::Completed),
+        pending: count(&|task| task.status() == Status::Pending),
+        in_progress: count(&|task| task.status() == Status::InProgress),
+        urgent: count(&|task| {
+            task.priority() == Priority::Urgent && task.status() != Status::Completed
+        }),
+        by_priority: PRIORITIES.map(|priority| count(&|task| task.priority() == priority)),
+    }
+}
+
+const PRIORITIES: [Priority; 4] = [
+    Priority::Low,
+    Priority::Medium,
+    Priority::High,
+    Priority::Urgent,
+];
+
+#[test]
+fn test_single_pass_statistics_match_multi_pass_counts() {
+    let mut manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+    for index in 0..40 {
+        let id = manager
+            .add_task(Task::new(0, format!("Task {index}"), PRIORITIES[index % 4]))
+            .unwrap();
+        match index % 5 {
+            0 => manager.complete_task(id).unwrap(),
+            1 => manager.start_task(id).unwrap(),
+            2 => manager.cancel_task(id).unwrap(),
+            _ => {}
+        }
+    }
+
+    let stats = manager.get_statistics();
+
+    assert_eq!(stats, multi_pass_statistics(&manager.list_tasks(None)));
+    assert_eq!(stats.priority_count(Priority::Urgent), 10);
+}
+
+#[test]
+fn test_statistics_rates_are_zero_for_empty_manager() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    let stats = manager.get_statistics();
+    assert_eq!(stats.completion_rate(), 0.0);
+    assert_eq!(stats.pending_rate(), 0.0);
+    assert_eq!(stats.in_progress_rate(), 0.0);
+}
+
+#[test]
+fn test_render_bars_are_proportional_and_fill_width() {
+    let stats = TaskStatistics {
+        total: 8,
+        completed: 4,
+        pending: 1,
+        in_progress: 2,
+        urgent: 0,
+        by_priority: [2, 2, 2, 2],
+    };
+
+    let bars = stats.render_bars(16);
+    let filled: Vec<usize> = bars
+        .lines()
+        .map(|line| line.chars().filter(|&c| c == '█').count())
+        .collect();
+    assert_eq!(
+        filled,
+        vec![8, 4, 2, 2],
+        "completed, in progress, pending, cancelled"
+    );
+    assert!(
+        bars.lines()
+            .all(|line| line.chars().filter(|&c| c == '█' || c == '░').count() == 16)
+    );
+
+    // 不能整除时按最大余数分配，总宽度仍然精确
+    let uneven = TaskStatistics {
+        total: 3,
+        completed: 1,
+        pending: 1,
+        in_progress: 1,
+        urgent: 0,
+        by_priority: [3, 0, 0, 0],
+    };
+    let filled: usize = uneven.render_bars(10).chars().filter(|&c| c == '█').count();
+    assert_eq!(filled, 10);
+}
+
+#[test]
+fn test_collecting_tasks_builds_in_memory_manager_with_unique_ids() {
+    let manager: TaskManager<InMemoryStore> = (1..=5)
+        .map(|index| Task::new(0, format!("Task {index}"), Priority::Medium))
+        .collect();
+
+    let mut ids: Vec<u64> = manager
+        .list_tasks(None)
+        .iter()
+        .map(|task| task.id())
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(
+        ids,
+        vec![1, 2, 3, 4, 5],
+        "ids should be auto-assigned and unique"
+    );
+    assert_eq!(manager.get_statistics().total, 5);
+    assert_eq!(manager.store().load().unwrap().len(), 5);
+    assert!(manager.auto_save());
+
+    let empty = TaskManager::in_memory();
+    assert_eq!(empty.load_state(), TaskManagerLoadState::InitializedEmpty);
+    assert!(empty.list_tasks(None).is_empty());
+}
+
+#[test]
+fn test_render_bars_is_empty_without_tasks() {
+    let manager = TaskManager::with_storage(RecordingStore::default()).unwrap();
+
+    assert_eq!(manager.get_statistics().render_bars(20), "");
+}
+
+#[test]
+fn test_statistics_rates_reflect_task_states() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+
+    let done = manager
+        .add_task(Task::new(0, "Done", Priority::Low))
+        .unwrap();
+    let started = manager
+        .add_task(Task::new(0, "Started", Priority::Medium))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "Waiting", Priority::High))
+        .unwrap();
+    manager
+        .add_task(Task::new(0, "Also waiting", Priority::High))
+        .unwrap();
+    manager.complete_task(done).unwrap();
+    manager.start_task(started).unwrap();
+
+    let stats = manager.get_statistics();
+    assert_eq!(stats.completion_rate(), 0.25);
+    assert_eq!(stats.in_progress_rate(), 0.25);
+    assert_eq!(stats.pending_rate(), 0.5);
+}
+
 #[test]
 fn test_list_tasks_orders_by_priority_before_recency() {
     let temp_dir = tempdir().unwrap();
@@ -231,6 +1492,55 @@ fn test_list_tasks_orders_by_priority_before_recency() {
     );
 }
 
+#[test]
+fn test_cmp_by_due_then_priority_sorts_missing_due_dates_last() {
+    let now = chrono::Local::now();
+    let mut tasks = [
+        Task::new(1, "no due, urgent", Priority::Urgent),
+        Task::new(2, "later", Priority::High).with_due_date(now + chrono::Duration::days(3)),
+        Task::new(3, "sooner, low", Priority::Low).with_due_date(now + chrono::Duration::days(1)),
+        Task::new(4, "sooner, high", Priority::High).with_due_date(now + chrono::Duration::days(1)),
+        Task::new(5, "no due, low", Priority::Low),
+    ];
+
+    tasks.sort_by(Task::cmp_by_due_then_priority);
+
+    let ids: Vec<u64> = tasks.iter().map(Task::id).collect();
+    assert_eq!(ids, [4, 3, 2, 1, 5]);
+}
+
+#[test]
+fn test_cmp_by_priority_then_created_puts_newer_tasks_first_within_priority() {
+    let task = |id: u64, priority: &str, created_at: &str| -> Task {
+        serde_json::from_str(&format!(
+            r#"{{
+    "id": {id},
+    "title": "Task {id}",
+    "description": null,
+    "priority": "{priority}",
+    "status": "pending",
+    "tags": [],
+    "created_at": "{created_at}",
+    "updated_at": "{created_at}",
+    "completed_at": null,
+    "due_date": null
+  }}"#
+        ))
+        .unwrap()
+    };
+    let mut tasks = [
+        task(1, "low", "2024-01-03T00:00:00+00:00"),
+        task(2, "high", "2024-01-01T00:00:00+00:00"),
+        task(3, "high", "2024-01-02T00:00:00+00:00"),
+        task(4, "urgent", "2024-01-01T00:00:00+00:00"),
+    ];
+
+    tasks.sort_by(Task::cmp_by_priority_then_created);
+
+    let ids: Vec<u64> = tasks.iter().map(Task::id).collect();
+    assert_eq!(ids, [4, 3, 2, 1]);
+}
+
 #[test]
 fn test_storage_load_missing_file_returns_not_found_outcome_without_creating_file() {
     let temp_dir = tempdir().unwrap();
@@ -321,6 +1631,45 @@ fn test_task_manager_load_state_distinguishes_first_run_from_reload() {
     );
 }
 
+#[test]
+fn test_corrupt_tasks_file_is_backed_up_and_manager_starts_empty() {
+    let temp_dir = tempdir().unwrap();
+    let storage_path = temp_dir.path().join("tasks.json");
+    fs::write(&storage_path, "{ this is not valid json").unwrap();
+
+    let mut manager = TaskManager::with_storage_path(&storage_path)
+        .expect("a corrupt tasks file should not prevent startup");
+    assert_eq!(
+        manager.load_state(),
+        TaskManagerLoadState::RecoveredFromCorruptFile
+    );
+    assert!(manager.list_tasks(None).is_empty());
+
+    let backups: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("tasks.json.corrupt."))
+        })
+        .collect();
+    assert_eq!(backups.len(), 1, "exactly one backup should be created");
+    assert_eq!(
+        fs::read_to_string(&backups[0]).unwrap(),
+        "{ this is not valid json"
+    );
+
+    manager
+        .add_task(Task::new(0, "Fresh start", Priority::Medium))
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&backups[0]).unwrap(),
+        "{ this is not valid json",
+        "saving afterwards must not touch the backup"
+    );
+}
+
 #[test]
 fn test_add_task_returns_error_and_rolls_back_when_persist_fails() {
     let temp_dir = tempdir().unwrap();
@@ -459,3 +1808,88 @@ fn test_storage_save_replaces_existing_file_without_leaving_temp_file() {
         .collect::<Vec<_>>();
     assert_eq!(entries, vec!["atomic_tasks.json".to_string()]);
 }
+
+mod round_trip_props {
+    use chrono::{Local, TimeZone};
+    use proptest::prelude::*;
+    use tempfile::tempdir;
+
+    use super::{Priority, Status, Task, TaskManager};
+
+    // 变体按“最简单”到“最复杂”排列，prop_oneof 收缩时会朝第一个变体靠拢。
+    impl Arbitrary for Priority {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            prop_oneof![
+                Just(Priority::Low),
+                Just(Priority::Medium),
+                Just(Priority::High),
+                Just(Priority::Urgent),
+            ]
+            .boxed()
+        }
+    }
+
+    impl Arbitrary for Status {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            prop_oneof![
+                Just(Status::Pending),
+                Just(Status::InProgress),
+                Just(Status::Completed),
+                Just(Status::Cancelled),
+            ]
+            .boxed()
+        }
+    }
+
+    fn arb_task() -> impl Strategy<Value = Task> {
+        (
+            "[a-zA-Z0-9 ]{1,20}",
+            proptest::option::of("[a-z ]{0,30}"),
+            any::<Priority>(),
+            any::<Status>(),
+            proptest::collection::vec("[a-z]{1,8}", 0..4),
+            proptest::option::of(0i64..4_000_000_000),
+        )
+            .prop_map(|(title, description, priority, status, tags, due)| {
+                let mut task = Task::new(0, title, priority).with_tags(tags);
+                task.set_description(description);
+                task.set_due_date(due.map(|secs| Local.timestamp_opt(secs, 0).unwrap()));
+                match status {
+                    Status::Pending => {}
+                    Status::InProgress => task.start(),
+                    Status::Completed => task.complete(),
+                    Status::Cancelled => task.cancel(),
+                }
+                task
+            })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_save_and_reload_round_trips_tasks(
+            tasks in proptest::collection::vec(arb_task(), 0..16),
+        ) {
+            let temp_dir = tempdir().unwrap();
+            let storage_path = temp_dir.path().join("tasks.json");
+            let mut manager = TaskManager::with_storage_path(&storage_path).unwrap();
+            let ids: Vec<u64> = tasks
+                .into_iter()
+                .map(|task| manager.add_task(task).unwrap())
+                .collect();
+
+            let reloaded = TaskManager::with_storage_path(&storage_path).unwrap();
+            prop_assert_eq!(reloaded.list_tasks(None).len(), ids.len());
+            for id in ids {
+                prop_assert_eq!(reloaded.get_task(id), manager.get_task(id));
+            }
+        }
+    }
+}
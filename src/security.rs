@@ -4,6 +4,7 @@
 //! 输入验证和清理、内存安全保证等安全开发的关键要素。
 //! 采用了现代化的Rust 2021/2024最佳实践。
 
+use std::borrow::Cow;
 use std::str;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -44,6 +45,106 @@ pub fn cryptography_hash_functions() {
     hasher512.update(data);
     let result512 = hasher512.finalize();
     println!("🧮 SHA-512: {}", hex::encode(result512));
+    
+    // 玩具级 Diffie-Hellman：双方只交换公开值，却能算出相同的共享密钥
+    let (prime, generator) = (23, 5);
+    let (alice_secret, bob_secret) = (6, 15);
+    let alice_public = mod_pow(generator, alice_secret, prime);
+    let bob_public = mod_pow(generator, bob_secret, prime);
+    println!("🤝 DH 公开值: A={}, B={}", alice_public, bob_public);
+    println!(
+        "🤝 DH 共享密钥: {} / {}（真实场景需使用大素数）",
+        mod_pow(bob_public, alice_secret, prime),
+        mod_pow(alice_public, bob_secret, prime)
+    );
+    
+    // 经典密码对照：维吉尼亚密码只能演示“可逆变换”，不能替代现代加密
+    if let Ok(ciphertext) = vigenere_encrypt("Attack at dawn!", "LEMON") {
+        println!("📜 维吉尼亚密码(LEMON): {}", ciphertext);
+    }
+}
+
+/// 模幂运算：计算 `base^exp mod modulus`
+///
+/// 基于快速幂，中间乘积提升到 `u128`，因此任意 `u64` 输入都不会溢出；
+/// `modulus == 1` 时结果恒为 0，`modulus == 0` 时 panic。
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "mod_pow 的模数不能为 0");
+    
+    let m = modulus as u128;
+    crate::basics::algorithms::pow_by_squaring(
+        base % modulus,
+        exp,
+        |a, b| ((*a as u128 * *b as u128) % m) as u64,
+        1 % modulus,
+    )
+}
+
+/// 凯撒/ROT-N 移位：把每个 ASCII 字母在字母表中后移 `n` 位
+///
+/// 大小写保持不变，非字母字符原样保留；`n` 按 26 取模，`rot_n(text, 13)` 即 ROT13。
+/// 这只是经典密码的教学示例，不提供任何安全性。
+pub fn rot_n(text: &str, n: u8) -> String {
+    let shift = n % 26;
+    text.chars().map(|c| shift_letter(c, shift)).collect()
+}
+
+/// 把单个 ASCII 字母后移 `shift`（须小于 26）位，其余字符原样返回
+fn shift_letter(c: char, shift: u8) -> char {
+    match c {
+        'a'..='z' => ((c as u8 - b'a' + shift) % 26 + b'a') as char,
+        'A'..='Z' => ((c as u8 - b'A' + shift) % 26 + b'A') as char,
+        _ => c,
+    }
+}
+
+/// [`rot_n`] 的逆变换，等价于 `rot_n(text, 26 - n % 26)`
+pub fn rot_n_decode(text: &str, n: u8) -> String {
+    rot_n(text, 26 - n % 26)
+}
+
+/// 维吉尼亚密码加密：逐个字母按密钥字母对应的位移做 [`rot_n`]
+///
+/// 密钥只能由 ASCII 字母组成且不区分大小写（`A`/`a` 位移 0）；
+/// 非字母字符原样保留且不消耗密钥位置。密钥为空或含非字母时返回错误。
+pub fn vigenere_encrypt(text: &str, key: &str) -> Result<String, String> {
+    let shifts = vigenere_shifts(key)?;
+    Ok(apply_vigenere(text, &shifts, |shift| shift))
+}
+
+/// 维吉尼亚密码解密，[`vigenere_encrypt`] 的逆变换
+pub fn vigenere_decrypt(text: &str, key: &str) -> Result<String, String> {
+    let shifts = vigenere_shifts(key)?;
+    Ok(apply_vigenere(text, &shifts, |shift| (26 - shift) % 26))
+}
+
+fn vigenere_shifts(key: &str) -> Result<Vec<u8>, String> {
+    if key.is_empty() {
+        return Err("维吉尼亚密钥不能为空".to_string());
+    }
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                Ok(c.to_ascii_lowercase() as u8 - b'a')
+            } else {
+                Err(format!("维吉尼亚密钥只能包含字母，发现非法字符 '{}'", c))
+            }
+        })
+        .collect()
+}
+
+fn apply_vigenere(text: &str, shifts: &[u8], direction: impl Fn(u8) -> u8) -> String {
+    let mut key_index = 0;
+    text.chars()
+        .map(|c| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let shift = direction(shifts[key_index % shifts.len()]);
+            key_index += 1;
+            shift_letter(c, shift)
+        })
+        .collect()
 }
 
 /// HMAC消息认证码
@@ -55,20 +156,222 @@ pub fn hmac_message_authentication() {
     println!("🔐 HMAC值: 未计算（功能暂时禁用）");
 }
 
+/// 十六进制解码
+///
+/// 对 `hex::decode` 的薄封装，把底层错误转换为便于展示的中文说明，
+/// 用于把以十六进制存储的哈希还原为字节进行比较。
+pub fn from_hex(s: &str) -> Result<Vec<u8>, String> {
+    hex::decode(s).map_err(|error| match error {
+        hex::FromHexError::OddLength => {
+            format!("十六进制字符串长度必须为偶数，实际长度: {}", s.len())
+        }
+        hex::FromHexError::InvalidHexCharacter { c, index } => {
+            format!("位置 {} 处的字符 '{}' 不是合法的十六进制字符", index, c)
+        }
+        other => format!("十六进制解码失败: {}", other),
+    })
+}
+
+/// Base64 字母表变体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Variant {
+    /// 标准字母表（`+`、`/`），带填充
+    Standard,
+    /// URL 安全字母表（`-`、`_`），不带填充，适合令牌与 URL 参数
+    UrlSafe,
+}
+
+impl Base64Variant {
+    fn engine(self) -> &'static base64::engine::GeneralPurpose {
+        match self {
+            Base64Variant::Standard => &base64::engine::general_purpose::STANDARD,
+            Base64Variant::UrlSafe => &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+        }
+    }
+}
+
+/// Base64 解码错误，保留底层 `DecodeError` 并记录输入长度
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Base64 解码失败（输入长度 {input_len}）: {source}")]
+pub struct Base64Error {
+    input_len: usize,
+    #[source]
+    source: base64::DecodeError,
+}
+
+impl Base64Error {
+    /// 解码失败的输入长度
+    pub fn input_len(&self) -> usize {
+        self.input_len
+    }
+    
+    /// 底层的解码错误
+    pub fn decode_error(&self) -> &base64::DecodeError {
+        &self.source
+    }
+}
+
+/// 使用指定变体进行 Base64 编码
+pub fn base64_encode(data: &[u8], variant: Base64Variant) -> String {
+    base64::Engine::encode(variant.engine(), data)
+}
+
+/// 使用指定变体进行 Base64 解码
+pub fn base64_decode(input: &str, variant: Base64Variant) -> Result<Vec<u8>, Base64Error> {
+    base64::Engine::decode(variant.engine(), input).map_err(|source| Base64Error {
+        input_len: input.len(),
+        source,
+    })
+}
+
+/// 百分号编码（RFC 3986）
+///
+/// 仅保留非保留字符（`A-Z a-z 0-9 - . _ ~`），其余字节按 UTF-8 编码为 `%XX`。
+pub fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// 百分号解码
+///
+/// `%` 后必须紧跟两位十六进制数字，解码后的字节必须是合法的 UTF-8。
+pub fn percent_decode(input: &str) -> Result<String, String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let pair = bytes
+                .get(index + 1..index + 3)
+                .ok_or_else(|| format!("位置 {} 处的百分号编码不完整", index))?;
+            if !pair.iter().all(u8::is_ascii_hexdigit) {
+                return Err(format!("位置 {} 处的百分号编码不是合法的十六进制", index));
+            }
+            let hex = str::from_utf8(pair).map_err(|error| error.to_string())?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|error| error.to_string())?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    
+    String::from_utf8(decoded).map_err(|error| format!("解码结果不是合法的 UTF-8: {}", error))
+}
+
 /// Base64编码解码
 pub fn base64_encoding_decoding() {
     println!("📦 Base64编码解码：");
     
     // 编码
     let data = b"Rust security programming best practices";
-    let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, data);
+    let encoded = base64_encode(data, Base64Variant::Standard);
     println!("📤 原始数据: {}", str::from_utf8(data).unwrap());
     println!("📤 Base64编码: {}", encoded);
+    println!("📤 URL安全编码: {}", base64_encode(data, Base64Variant::UrlSafe));
     
     // 解码
-    if let Ok(decoded) = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &encoded) {
-        println!("📥 解码数据: {}", str::from_utf8(&decoded).unwrap());
+    match base64_decode(&encoded, Base64Variant::Standard) {
+        Ok(decoded) => println!("📥 解码数据: {}", String::from_utf8_lossy(&decoded)),
+        Err(e) => println!("❌ {}", e),
+    }
+    
+    // 非法输入会返回带类型的错误，而不是被静默忽略
+    if let Err(e) = base64_decode("not*base64", Base64Variant::Standard) {
+        println!("❌ {}", e);
+    }
+}
+
+/// 单条验证规则：通过返回 `Ok(())`，失败返回错误描述。
+pub type ValidationRule<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+
+/// 可组合的输入验证器
+///
+/// 通过 [`Validator::rule`] 逐条追加规则，[`Validator::validate`] 会执行全部规则
+/// 并收集所有失败信息，而不是在第一条失败时就返回。
+pub struct Validator<T: ?Sized> {
+    rules: Vec<ValidationRule<T>>,
+}
+
+impl<T: ?Sized> Validator<T> {
+    /// 创建不含任何规则的验证器
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// 追加一条验证规则（构建器风格）
+    pub fn rule(mut self, rule: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
     }
+
+    /// 已注册的规则数量
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// 执行全部规则，返回所有失败信息
+    pub fn validate(&self, value: &T) -> Result<(), Vec<String>> {
+        let errors: Vec<String> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule(value).err())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<T: ?Sized> Default for Validator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 规范化邮箱：去除首尾空白并转为小写
+///
+/// 输入已经是规范形式（或只需去掉首尾空白）时直接借用原字符串的切片，
+/// 只有确实包含需要转换的大写字符时才分配新字符串。
+pub fn normalize_email(input: &str) -> Cow<'_, str> {
+    let trimmed = input.trim();
+    if trimmed.chars().all(|c| c.to_lowercase().eq([c])) {
+        Cow::Borrowed(trimmed)
+    } else {
+        Cow::Owned(trimmed.to_lowercase())
+    }
+}
+
+/// 邮箱验证
+///
+/// 经 [`normalize_email`] 清理后校验格式，成功时返回清理后的邮箱。
+pub fn validate_email(email: &str) -> Result<String, String> {
+    let clean = normalize_email(email);
+    
+    if clean.is_empty() {
+        return Err("邮箱不能为空".to_string());
+    }
+    
+    let email_regex = regex::Regex::new(
+        r"^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$"
+    ).unwrap();
+    
+    if !email_regex.is_match(&clean) {
+        return Err("邮箱格式无效".to_string());
+    }
+    
+    Ok(clean.into_owned())
 }
 
 /// 输入验证和清理
@@ -95,25 +398,6 @@ pub fn input_validation_sanitization() {
         Ok(clean.to_string())
     }
     
-    // 邮箱验证
-    fn validate_email(email: &str) -> Result<String, String> {
-        let clean = email.trim().to_lowercase();
-        
-        if clean.is_empty() {
-            return Err("邮箱不能为空".to_string());
-        }
-        
-        let email_regex = regex::Regex::new(
-            r"^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$"
-        ).unwrap();
-        
-        if !email_regex.is_match(&clean) {
-            return Err("邮箱格式无效".to_string());
-        }
-        
-        Ok(clean)
-    }
-    
     // 测试用例
     let binding = "a".repeat(25);
     let test_usernames = vec![
@@ -149,30 +433,146 @@ pub fn input_validation_sanitization() {
             Err(e) => println!("  ❌ '{}' -> {}", email, e),
         }
     }
+    
+    // Cow：已规范的输入无需分配
+    for email in ["user@example.com", " User@Example.COM "] {
+        let kind = match normalize_email(email) {
+            Cow::Borrowed(_) => "借用，无分配",
+            Cow::Owned(_) => "新分配",
+        };
+        println!("  🐄 normalize_email({:?}) -> {}", email, kind);
+    }
+    
+    // 组合验证器：一次性报告所有问题
+    let username_validator = Validator::<str>::new()
+        .rule(|name| {
+            if name.trim().is_empty() {
+                Err("用户名不能为空".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .rule(|name| {
+            if (3..=20).contains(&name.len()) {
+                Ok(())
+            } else {
+                Err("用户名长度必须在3-20个字符之间".to_string())
+            }
+        })
+        .rule(|name| {
+            if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                Ok(())
+            } else {
+                Err("用户名只能包含字母、数字和下划线".to_string())
+            }
+        });
+    
+    println!("\n🔍 组合验证器测试（收集全部错误）:");
+    for username in ["valid_user", "a@", ""] {
+        match username_validator.validate(username) {
+            Ok(()) => println!("  ✅ '{}'", username),
+            Err(errors) => println!("  ❌ '{}' -> {}", username, errors.join("；")),
+        }
+    }
+}
+
+/// 默认的 PBKDF2 迭代次数
+///
+/// 迭代次数写入编码结果，调整默认值后旧哈希仍按各自的次数验证。
+pub const DEFAULT_PASSWORD_ROUNDS: u32 = 100_000;
+
+/// 验证时接受的最大迭代次数，防止篡改后的超大次数拖垮验证方
+pub const MAX_PASSWORD_ROUNDS: u32 = 10_000_000;
+
+const PASSWORD_SALT_LEN: usize = 16;
+const PASSWORD_HASH_LEN: usize = 32;
+
+fn derive_password_hash(password: &str, salt: &[u8], rounds: u32) -> [u8; PASSWORD_HASH_LEN] {
+    let mut output = [0u8; PASSWORD_HASH_LEN];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password.as_bytes(), salt, rounds, &mut output);
+    output
+}
+
+/// 以默认迭代次数哈希密码，等价于 `hash_password_with(password, DEFAULT_PASSWORD_ROUNDS)`
+pub fn hash_password(password: &str) -> Result<String, String> {
+    hash_password_with(password, DEFAULT_PASSWORD_ROUNDS)
+}
+
+/// 以指定迭代次数哈希密码（PBKDF2-HMAC-SHA256）
+///
+/// 输出格式为 `rounds$salt$hash`，盐值与哈希均为十六进制；
+/// 每次调用都会生成新的随机盐，因此同一密码两次哈希结果不同。
+pub fn hash_password_with(password: &str, rounds: u32) -> Result<String, String> {
+    use ring::rand::{SecureRandom, SystemRandom};
+    
+    if !(1..=MAX_PASSWORD_ROUNDS).contains(&rounds) {
+        return Err(format!("迭代次数必须在1-{}之间，实际: {}", MAX_PASSWORD_ROUNDS, rounds));
+    }
+    
+    let mut salt = [0u8; PASSWORD_SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| "无法从系统获取随机盐值".to_string())?;
+    
+    let hash = derive_password_hash(password, &salt, rounds);
+    Ok(format!("{}${}${}", rounds, hex::encode(salt), hex::encode(hash)))
+}
+
+/// 验证密码是否与 `rounds$salt$hash` 格式的存储值匹配
+///
+/// 迭代次数从存储值中读取；格式错误、次数越界或哈希不符都返回 `false`，
+/// 哈希比较使用常量时间比较。
+pub fn verify_password(password: &str, stored: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (Some(rounds), Some(salt), Some(hash), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    
+    let Ok(rounds) = rounds.parse::<u32>() else {
+        return false;
+    };
+    if !(1..=MAX_PASSWORD_ROUNDS).contains(&rounds) {
+        return false;
+    }
+    
+    let (Ok(salt), Ok(expected)) = (from_hex(salt), from_hex(hash)) else {
+        return false;
+    };
+    
+    constant_time_eq_bytes(&derive_password_hash(password, &salt, rounds), &expected)
 }
 
 /// 安全密码存储
 pub fn secure_password_storage() {
     println!("🔑 安全密码存储：");
     
-    println!("⚠️ PBKDF2功能由于依赖版本冲突暂时禁用");
     println!("🔒 密码输入: [示例中不回显；应由调用者提供]");
-    println!("🧂 盐值: [示例中不展示；应使用每次唯一的随机盐]");
-    println!("🔐 PBKDF2哈希: 未计算（功能暂时禁用）");
-    
-    // 密码验证函数
-    fn verify_password(_password: &str, _salt: &str, _stored_hash: &[u8]) -> bool {
-        println!("⚠️ 密码验证功能暂时禁用");
-        false
-    }
+    println!("🧂 盐值: 每次哈希都从系统随机源生成 {} 字节新盐", PASSWORD_SALT_LEN);
+    
+    // 演示使用较小的迭代次数，生产环境应使用默认值或更高
+    let demo_rounds = 10_000;
+    let stored = match hash_password_with("test", demo_rounds) {
+        Ok(stored) => stored,
+        Err(e) => {
+            println!("❌ 密码哈希失败: {}", e);
+            return;
+        }
+    };
+    println!("🔐 PBKDF2哈希: rounds={}，编码长度 {} 字符", demo_rounds, stored.len());
     
     // 测试验证
-    let is_valid = verify_password("test", "salt", &[0; 32]);
+    let is_valid = verify_password("test", &stored);
     println!("✅ 密码验证结果: {}", if is_valid { "有效" } else { "无效" });
     
     // 测试错误密码
-    let is_wrong_valid = verify_password("wrong_password", "salt", &[0; 32]);
+    let is_wrong_valid = verify_password("wrong_password", &stored);
     println!("✅ 错误密码验证: {}", if is_wrong_valid { "有效" } else { "无效" });
+    
+    // 篡改迭代次数后验证失败
+    let tampered = stored.replacen(&demo_rounds.to_string(), "1", 1);
+    println!("✅ 篡改迭代次数: {}", if verify_password("test", &tampered) { "有效" } else { "无效" });
 }
 
 /// 安全随机字符串生成
@@ -269,6 +669,29 @@ pub fn memory_safety_guarantees() {
     println!("最终安全值: {}", *shared_data.lock().unwrap());
 }
 
+/// 常量时间字节比较
+///
+/// 基于 `subtle::ConstantTimeEq`，比较耗时只与长度有关，不受首个不同字节位置影响。
+pub fn constant_time_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    use subtle::ConstantTimeEq;
+    
+    a.ct_eq(b).into()
+}
+
+/// 校验令牌（如 CSRF / API 令牌）
+///
+/// 两个令牌都按 URL 安全 Base64 解码后再做常量时间比较；任一解码失败都视为不匹配。
+pub fn verify_token(presented: &str, expected: &str) -> bool {
+    let (Ok(presented), Ok(expected)) = (
+        base64_decode(presented, Base64Variant::UrlSafe),
+        base64_decode(expected, Base64Variant::UrlSafe),
+    ) else {
+        return false;
+    };
+    
+    constant_time_eq_bytes(&presented, &expected)
+}
+
 /// 固定流程比较示意
 pub fn constant_time_comparison() {
     println!("⏱️ 固定流程比较示意：");
@@ -306,6 +729,11 @@ pub fn constant_time_comparison() {
         let status = if result == expected { "✅" } else { "❌" };
         println!("  {} {} = {} (期望: {})", status, label, result, expected);
     }
+    
+    // 端到端令牌校验：URL安全Base64 + 常量时间比较
+    let expected_token = base64_encode(b"demo-csrf-token", Base64Variant::UrlSafe);
+    println!("🔑 令牌匹配: {}", verify_token(&expected_token, &expected_token));
+    println!("🔑 畸形令牌: {}", verify_token("%%%", &expected_token));
 }
 
 /// 运行安全编程示例
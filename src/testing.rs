@@ -12,8 +12,8 @@ mod performance;
 mod strategies;
 
 pub use domain::{
-    EmailValidationError, User, UserCreationError, UserManager, UserManagerError, add_two,
-    greeting, validate_email,
+    EmailValidationError, User, UserBuilder, UserCreationError, UserManager, UserManagerError,
+    ValidationReport, add_two, checked_add, greeting, validate_email, validate_users,
 };
 pub use documentation::{documented_function, run_testing_examples, timed_operations_demo};
 pub use performance::{integration_testing_scenarios, performance_testing_examples};
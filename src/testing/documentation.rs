@@ -7,7 +7,7 @@
 
 use std::time::Instant;
 
-use super::domain::{User, UserManager, add_two, greeting};
+use super::domain::{User, UserManager, add_two, greeting, validate_users};
 
 /// 现代化文档测试。
 ///
@@ -98,6 +98,17 @@ pub fn run_testing_examples() {
     }
 
     println!("当前用户数量: {}", manager.user_count());
+
+    let signups = [
+        ("王五".to_string(), "wangwu@example.com".to_string(), 30),
+        ("赵六".to_string(), "zhaoliu@".to_string(), 22),
+        ("小红".to_string(), "xiaohong@example.com".to_string(), 10),
+    ];
+    let report = validate_users(&signups);
+    println!("批量校验: {} 个通过", report.valid.len());
+    for (index, error) in &report.errors {
+        println!("  第 {} 项: {}", index + 1, error);
+    }
     println!();
 
     println!("问候测试:");
@@ -12,8 +12,15 @@ pub fn add_two(a: i32) -> i32 {
 }
 
 /// 私有辅助函数，用于展示如何测试非公开实现细节。
+///
+/// 溢出时按补码回绕；需要检测溢出时使用 [`checked_add`]。
 fn internal_adder(a: i32, b: i32) -> i32 {
-    a + b
+    a.wrapping_add(b)
+}
+
+/// 溢出安全的加法：结果超出 `i32` 范围时返回 `None`。
+pub fn checked_add(a: i32, b: i32) -> Option<i32> {
+    a.checked_add(b)
 }
 
 /// 邮箱校验失败的稳定错误类型。
@@ -99,6 +106,9 @@ pub struct User {
 }
 
 impl User {
+    /// [`User::is_adult`] 与 [`UserManager::get_adult_users`] 使用的成年年龄。
+    pub const ADULT_AGE: u8 = 18;
+
     pub fn new(name: String, email: String, age: u8) -> Result<Self, UserCreationError> {
         validate_email_with_error(&email)?;
 
@@ -109,8 +119,13 @@ impl User {
         Ok(Self { name, email, age })
     }
 
+    /// 创建用户构建器，未设置的字段使用合法的占位值，便于编写测试夹具。
+    pub fn builder() -> UserBuilder {
+        UserBuilder::default()
+    }
+
     pub fn is_adult(&self) -> bool {
-        self.age >= 18
+        self.age >= Self::ADULT_AGE
     }
 
     pub fn greet(&self) -> String {
@@ -118,6 +133,46 @@ impl User {
     }
 }
 
+/// [`User`] 构建器，默认值可直接通过校验。
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    name: String,
+    email: String,
+    age: u8,
+}
+
+impl Default for UserBuilder {
+    fn default() -> Self {
+        Self {
+            name: "测试用户".to_string(),
+            email: "user@example.com".to_string(),
+            age: 30,
+        }
+    }
+}
+
+impl UserBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = email.to_string();
+        self
+    }
+
+    pub fn age(mut self, age: u8) -> Self {
+        self.age = age;
+        self
+    }
+
+    /// 使用与 [`User::new`] 相同的校验构建用户。
+    pub fn build(self) -> Result<User, UserCreationError> {
+        User::new(self.name, self.email, self.age)
+    }
+}
+
 /// 现代化用户管理器。
 #[derive(Debug, Default)]
 pub struct UserManager {
@@ -143,7 +198,12 @@ impl UserManager {
     }
 
     pub fn get_adult_users(&self) -> Vec<&User> {
-        self.users.iter().filter(|user| user.is_adult()).collect()
+        self.users_at_least(User::ADULT_AGE)
+    }
+
+    /// 返回年龄不低于 `age` 的用户，保持插入顺序。
+    pub fn users_at_least(&self, age: u8) -> Vec<&User> {
+        self.users.iter().filter(|user| user.age >= age).collect()
     }
 
     pub fn user_count(&self) -> usize {
@@ -151,6 +211,27 @@ impl UserManager {
     }
 }
 
+/// 批量校验的结果：通过校验的用户与失败项（输入下标、错误描述）。
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationReport {
+    pub valid: Vec<User>,
+    pub errors: Vec<(usize, String)>,
+}
+
+/// 对每组 `(name, email, age)` 调用 [`User::new`]，一次收集所有问题。
+///
+/// 与遇错即停的 `?` 不同，适合注册表单这类需要一次性反馈全部错误的场景。
+pub fn validate_users(inputs: &[(String, String, u8)]) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    for (index, (name, email, age)) in inputs.iter().enumerate() {
+        match User::new(name.clone(), email.clone(), *age) {
+            Ok(user) => report.valid.push(user),
+            Err(error) => report.errors.push((index, error.to_string())),
+        }
+    }
+    report
+}
+
 /// 现代化集成测试辅助函数。
 pub fn greeting(name: &str) -> String {
     format!("你好，{}！", name)
@@ -176,6 +257,16 @@ mod unit_tests {
     fn test_internal_adder() {
         assert_eq!(internal_adder(2, 3), 5);
         assert_eq!(internal_adder(0, 0), 0);
+        assert_eq!(internal_adder(i32::MAX, 1), i32::MIN);
+    }
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(checked_add(2, 3), Some(5));
+        assert_eq!(checked_add(-7, 4), Some(-3));
+        assert_eq!(checked_add(i32::MAX, 0), Some(i32::MAX));
+        assert_eq!(checked_add(i32::MAX, 1), None);
+        assert_eq!(checked_add(i32::MIN, -1), None);
     }
 
     #[test]
@@ -244,6 +335,71 @@ mod unit_tests {
         assert_eq!(user.greet(), "你好，王五！");
     }
 
+    #[test]
+    fn test_validate_users_collects_valid_and_errors() {
+        let inputs = vec![
+            ("张三".to_string(), "zhangsan@example.com".to_string(), 25),
+            ("李四".to_string(), "invalid-email".to_string(), 30),
+            ("小明".to_string(), "xiaoming@example.com".to_string(), 12),
+            ("王五".to_string(), "wangwu@example.com".to_string(), 13),
+        ];
+
+        let report = validate_users(&inputs);
+
+        let names: Vec<&str> = report.valid.iter().map(|user| user.name.as_str()).collect();
+        assert_eq!(names, ["张三", "王五"]);
+        assert_eq!(
+            report.errors,
+            vec![
+                (1, EmailValidationError::InvalidAtCount.to_string()),
+                (2, UserCreationError::AgeTooYoung.to_string()),
+            ]
+        );
+        assert_eq!(validate_users(&[]), ValidationReport::default());
+    }
+
+    fn age_fixture() -> UserManager {
+        let mut manager = UserManager::new();
+        let fixture = [
+            ("a", 13),
+            ("b", 17),
+            ("c", 18),
+            ("d", 20),
+            ("e", 21),
+            ("f", 40),
+        ];
+        for (name, age) in fixture {
+            let user = User::new(name.to_string(), format!("{}@example.com", name), age)
+                .expect("Failed to create fixture user");
+            manager.add_user(user).expect("Failed to add fixture user");
+        }
+        manager
+    }
+
+    fn names(users: &[&User]) -> Vec<String> {
+        users.iter().map(|user| user.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_users_at_least_threshold_18() {
+        let manager = age_fixture();
+
+        assert_eq!(names(&manager.users_at_least(18)), ["c", "d", "e", "f"]);
+        assert_eq!(
+            manager.get_adult_users(),
+            manager.users_at_least(User::ADULT_AGE)
+        );
+    }
+
+    #[test]
+    fn test_users_at_least_threshold_21() {
+        let manager = age_fixture();
+
+        assert_eq!(names(&manager.users_at_least(21)), ["e", "f"]);
+        assert_eq!(manager.users_at_least(0).len(), 6);
+        assert!(manager.users_at_least(41).is_empty());
+    }
+
     #[test]
     #[should_panic]
     fn test_panic_case() {
@@ -5,8 +5,10 @@
 
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
+use std::time::Duration;
 
 /// 现代化结构体演示
 pub fn structs() {
@@ -102,6 +104,112 @@ pub fn structs() {
     println!("单元结构体: {}", unit);
 }
 
+/// 交通信号灯状态机
+///
+/// 状态按 红 → 绿 → 黄 → 红 的顺序循环。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficLight {
+    Red,
+    Yellow,
+    Green,
+}
+
+impl TrafficLight {
+    /// 下一个状态
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Red => Self::Green,
+            Self::Green => Self::Yellow,
+            Self::Yellow => Self::Red,
+        }
+    }
+
+    /// 当前状态的持续时间
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Red => Duration::from_secs(30),
+            Self::Yellow => Duration::from_secs(5),
+            Self::Green => Duration::from_secs(25),
+        }
+    }
+
+    /// 从当前状态开始无限循环的迭代器
+    pub fn cycle(self) -> TrafficLightCycle {
+        TrafficLightCycle { current: self }
+    }
+}
+
+/// 信号灯状态循环迭代器，由 [`TrafficLight::cycle`] 创建
+#[derive(Debug, Clone)]
+pub struct TrafficLightCycle {
+    current: TrafficLight,
+}
+
+impl Iterator for TrafficLightCycle {
+    type Item = TrafficLight;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let light = self.current;
+        self.current = light.next();
+        Some(light)
+    }
+}
+
+/// 几何形状
+///
+/// 相等按变体和字段逐一比较；按面积比较请使用 [`Shape::cmp_by_area`]。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Triangle { a: f64, b: f64, c: f64 },
+}
+
+impl Shape {
+    /// 创建三角形，边长必须为有限正数且满足三角形不等式
+    pub fn triangle(a: f64, b: f64, c: f64) -> Result<Shape, String> {
+        if [a, b, c].iter().any(|side| !side.is_finite()) {
+            return Err(format!("三角形边长必须为有限数: {}, {}, {}", a, b, c));
+        }
+        if a <= 0.0 || b <= 0.0 || c <= 0.0 {
+            return Err(format!("三角形边长必须为正数: {}, {}, {}", a, b, c));
+        }
+
+        if a + b <= c || a + c <= b || b + c <= a {
+            return Err(format!("边长 {}, {}, {} 不满足三角形不等式", a, b, c));
+        }
+
+        Ok(Shape::Triangle { a, b, c })
+    }
+
+    pub fn area(&self) -> f64 {
+        match self {
+            Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+            Shape::Rectangle { width, height } => width * height,
+            Shape::Triangle { a, b, c } => {
+                // 使用海伦公式计算三角形面积
+                let s = (a + b + c) / 2.0;
+                (s * (s - a) * (s - b) * (s - c)).sqrt()
+            }
+        }
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        match self {
+            Shape::Circle { radius } => 2.0 * std::f64::consts::PI * radius,
+            Shape::Rectangle { width, height } => 2.0 * (width + height),
+            Shape::Triangle { a, b, c } => a + b + c,
+        }
+    }
+
+    /// 按面积比较两个形状，可直接用于 `shapes.sort_by(Shape::cmp_by_area)`
+    ///
+    /// 使用 `f64::total_cmp`，因此总能给出确定的顺序。
+    pub fn cmp_by_area(&self, other: &Self) -> std::cmp::Ordering {
+        self.area().total_cmp(&other.area())
+    }
+}
+
 /// 现代化枚举演示
 pub fn enums() {
     println!("🎯 现代化枚举：");
@@ -365,6 +473,52 @@ pub fn generics() {
     }
 }
 
+/// 可注册到 [`PluginRegistry`] 的插件
+pub trait Plugin {
+    fn name(&self) -> &str;
+    fn run(&self, input: &str) -> String;
+}
+
+/// 基于特征对象的插件注册表
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: HashMap<String, Box<dyn Plugin>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按插件名称注册；同名插件会被替换
+    pub fn register(&mut self, plugin: Box<dyn Plugin>) {
+        self.plugins.insert(plugin.name().to_string(), plugin);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Plugin> {
+        self.plugins.get(name).map(|plugin| plugin.as_ref())
+    }
+
+    /// 使用同一输入运行所有插件，按插件名称排序返回 `(名称, 输出)`
+    pub fn run_all(&self, input: &str) -> Vec<(String, String)> {
+        let mut outputs: Vec<_> = self
+            .plugins
+            .iter()
+            .map(|(name, plugin)| (name.clone(), plugin.run(input)))
+            .collect();
+        outputs.sort_by(|a, b| a.0.cmp(&b.0));
+        outputs
+    }
+
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+}
+
 /// 演示高级特征对象和动态分发
 pub fn advanced_trait_objects() {
     println!("🎨 高级特征对象：");
@@ -426,6 +580,42 @@ pub fn advanced_trait_objects() {
             shape.area()
         );
     }
+
+    // 可注册的插件系统
+    struct UppercasePlugin;
+
+    impl Plugin for UppercasePlugin {
+        fn name(&self) -> &str {
+            "uppercase"
+        }
+
+        fn run(&self, input: &str) -> String {
+            input.to_uppercase()
+        }
+    }
+
+    struct ReversePlugin;
+
+    impl Plugin for ReversePlugin {
+        fn name(&self) -> &str {
+            "reverse"
+        }
+
+        fn run(&self, input: &str) -> String {
+            input.chars().rev().collect()
+        }
+    }
+
+    let mut registry = PluginRegistry::new();
+    registry.register(Box::new(UppercasePlugin));
+    registry.register(Box::new(ReversePlugin));
+
+    if let Some(plugin) = registry.get("uppercase") {
+        println!("插件 {} 输出: {}", plugin.name(), plugin.run("rust"));
+    }
+    for (name, output) in registry.run_all("plugin") {
+        println!("插件 {}: {}", name, output);
+    }
 }
 
 /// 演示关联类型和泛型关联类型
@@ -579,6 +769,46 @@ pub fn associated_types() {
     }
 }
 
+/// 保证非空的字符串
+///
+/// 只能通过 [`NonEmptyString::new`] 构造，空串或只含空白的字符串会被拒绝，
+/// 因此持有者无需再重复校验；通过 `Deref<Target = str>` 可直接调用 `str` 的方法。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonEmptyString(String);
+
+impl NonEmptyString {
+    pub fn new(s: String) -> Result<Self, String> {
+        if s.trim().is_empty() {
+            Err("字符串不能为空或只包含空白字符".to_string())
+        } else {
+            Ok(Self(s))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 取出内部的 `String`
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl std::ops::Deref for NonEmptyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for NonEmptyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// 演示NewType模式和类型安全
 pub fn newtype_pattern() {
     println!("📦 NewType模式：");
@@ -648,6 +878,25 @@ pub fn newtype_pattern() {
 
     println!("用户ID: {}", user_id.as_u64());
     println!("会话ID: {}", session_id.as_str());
+
+    // 受约束的类型：任务标题在构造时校验一次，之后无需重复检查
+    struct TaskDraft {
+        title: NonEmptyString,
+    }
+
+    for raw in ["编写周报", "   "] {
+        match NonEmptyString::new(raw.to_string()) {
+            Ok(title) => {
+                let draft = TaskDraft { title };
+                println!(
+                    "任务标题: {} ({} 个字符)",
+                    draft.title,
+                    draft.title.chars().count()
+                );
+            }
+            Err(e) => println!("拒绝标题 {:?}: {}", raw, e),
+        }
+    }
 }
 
 /// 演示高级特征边界和约束
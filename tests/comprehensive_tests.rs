@@ -64,6 +64,604 @@ mod basics_tests {
     fn test_advanced_algorithms() {
         basics::advanced_algorithms();
     }
+
+    /// 测试 checked_fib 的小值、精确上限与溢出
+    #[test]
+    fn test_checked_fib() {
+        let small: Vec<_> = (0..8).map(basics::checked_fib).collect();
+        assert_eq!(small, [0, 1, 1, 2, 3, 5, 8, 13].map(Some).to_vec());
+
+        assert_eq!(basics::checked_fib(90), Some(2_880_067_194_370_816_120));
+        assert_eq!(basics::checked_fib(92), Some(7_540_113_804_746_346_429));
+        assert_eq!(basics::checked_fib(93), Some(12_200_160_415_121_876_738));
+        assert_eq!(basics::checked_fib(94), None);
+    }
+
+    /// 测试 saturating_fib 溢出时饱和为 u64::MAX
+    #[test]
+    fn test_saturating_fib() {
+        assert_eq!(basics::saturating_fib(10), 55);
+        assert_eq!(basics::saturating_fib(90), 2_880_067_194_370_816_120);
+        assert_eq!(basics::saturating_fib(93), 12_200_160_415_121_876_738);
+        assert_eq!(basics::saturating_fib(94), u64::MAX);
+    }
+
+    /// 测试环形缓冲区的回绕
+    #[test]
+    fn test_ring_buffer_wraps_around() {
+        let mut ring = basics::RingBuffer::new(3);
+        ring.push(1);
+        ring.push(2);
+        assert_eq!(ring.pop_front(), Some(1));
+
+        ring.push(3);
+        ring.push(4);
+        assert!(ring.is_full());
+        assert_eq!(ring.pop_front(), Some(2));
+        assert_eq!(ring.pop_front(), Some(3));
+        assert_eq!(ring.pop_front(), Some(4));
+    }
+
+    /// 测试环形缓冲区写满后覆盖最旧元素
+    #[test]
+    fn test_ring_buffer_overwrites_oldest_when_full() {
+        let mut ring = basics::RingBuffer::new(2);
+        assert_eq!(ring.push("a"), None);
+        assert_eq!(ring.push("b"), None);
+        assert_eq!(ring.push("c"), Some("a"));
+        assert_eq!(ring.push("d"), Some("b"));
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.pop_front(), Some("c"));
+    }
+
+    /// 测试环形缓冲区清空
+    #[test]
+    fn test_ring_buffer_drains_to_empty() {
+        let mut ring = basics::RingBuffer::new(4);
+        for value in 0..6 {
+            ring.push(value);
+        }
+
+        let drained: Vec<_> = std::iter::from_fn(|| ring.pop_front()).collect();
+        assert_eq!(drained, vec![2, 3, 4, 5]);
+        assert!(ring.is_empty());
+        assert_eq!(ring.pop_front(), None);
+    }
+
+    /// 测试最短路径选择代价更低的多跳路线
+    #[test]
+    fn test_graph_shortest_path_prefers_cheaper_multi_hop_route() {
+        let mut graph = basics::Graph::new(5);
+        graph.add_edge(0, 4, 20);
+        graph.add_edge(0, 1, 2);
+        graph.add_edge(1, 2, 3);
+        graph.add_edge(2, 4, 4);
+        graph.add_edge(0, 3, 1);
+        graph.add_edge(3, 4, 15);
+
+        assert_eq!(graph.shortest_path(0, 4), Some((9, vec![0, 1, 2, 4])));
+        assert_eq!(graph.shortest_path(0, 0), Some((0, vec![0])));
+        assert_eq!(
+            graph.dijkstra(0),
+            vec![Some(0), Some(2), Some(5), Some(1), Some(9)]
+        );
+    }
+
+    /// 测试不可达节点返回 None
+    #[test]
+    fn test_graph_shortest_path_unreachable_returns_none() {
+        let mut graph = basics::Graph::new(3);
+        graph.add_edge(1, 0, 1);
+
+        assert_eq!(graph.shortest_path(0, 1), None);
+        assert_eq!(graph.shortest_path(0, 7), None);
+        assert_eq!(graph.dijkstra(0), vec![Some(0), None, None]);
+    }
+
+    /// 测试总权重溢出的路径被跳过而不是回绕
+    #[test]
+    fn test_graph_dijkstra_skips_overflowing_paths() {
+        let mut graph = basics::Graph::new(4);
+        graph.add_edge(0, 1, u64::MAX);
+        graph.add_edge(1, 2, u64::MAX);
+        graph.add_edge(0, 3, 1);
+        graph.add_edge(3, 2, u64::MAX);
+
+        assert_eq!(
+            graph.dijkstra(0),
+            vec![Some(0), Some(u64::MAX), None, Some(1)]
+        );
+        assert_eq!(graph.shortest_path(0, 2), None);
+    }
+
+    /// 测试广度优先遍历按层访问
+    #[test]
+    fn test_graph_bfs_visits_level_by_level() {
+        let mut graph = basics::Graph::new(6);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(5, 0, 1);
+
+        assert_eq!(graph.bfs(0), vec![0, 1, 2, 3, 4]);
+        assert_eq!(graph.bfs(4), vec![4]);
+    }
+
+    /// 测试两个互不相连的子图得到两个连通分量
+    #[test]
+    fn test_graph_connected_components_ignore_edge_direction() {
+        let mut graph = basics::Graph::new(5);
+        graph.add_edge(1, 0, 1);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(4, 3, 1);
+
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![0, 1, 2], vec![3, 4]]
+        );
+    }
+
+    /// 测试完全在范围内的窗口
+    #[test]
+    fn test_safe_window_in_bounds() {
+        let data = [1, 2, 3, 4, 5];
+        assert_eq!(basics::safe_window(&data, 1, 3), Some(&[2, 3, 4][..]));
+        assert_eq!(basics::safe_window(&data, 5, 0), Some(&[][..]));
+    }
+
+    /// 测试部分越界与完全越界的窗口返回 None
+    #[test]
+    fn test_safe_window_out_of_bounds() {
+        let data = [1, 2, 3, 4, 5];
+        assert_eq!(basics::safe_window(&data, 3, 3), None);
+        assert_eq!(basics::safe_window(&data, 6, 1), None);
+        assert_eq!(basics::safe_window(&data, 1, usize::MAX), None);
+    }
+
+    /// 测试 IdGenerator 单调递增且 peek 不消耗 ID
+    #[test]
+    fn test_id_generator_is_monotonic() {
+        let mut ids = basics::IdGenerator::new();
+        assert_eq!(ids.peek(), 1);
+
+        let issued: Vec<u64> = (0..5).map(|_| ids.next()).collect();
+        assert_eq!(issued, [1, 2, 3, 4, 5]);
+        assert_eq!(ids.peek(), 6);
+        assert_eq!(basics::IdGenerator::default(), basics::IdGenerator::new());
+    }
+
+    /// 测试 IdGenerator 可从指定偏移开始分配
+    #[test]
+    fn test_id_generator_starting_offset() {
+        let mut ids = basics::IdGenerator::new_starting_at(100);
+        assert_eq!(ids.next(), 100);
+        assert_eq!(ids.next(), 101);
+
+        let atomic = basics::AtomicIdGenerator::new_starting_at(42);
+        assert_eq!(atomic.next(), 42);
+        assert_eq!(atomic.peek(), 43);
+    }
+
+    /// 测试 AtomicIdGenerator 在多线程下分配的 ID 互不重复
+    #[test]
+    fn test_atomic_id_generator_concurrent_uniqueness() {
+        use std::collections::HashSet;
+
+        let ids = basics::AtomicIdGenerator::new();
+        let issued: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| (0..500).map(|_| ids.next()).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        let unique: HashSet<u64> = issued.iter().copied().collect();
+        assert_eq!(issued.len(), 4000);
+        assert_eq!(unique.len(), 4000, "every issued id should be unique");
+        assert_eq!(unique.iter().max(), Some(&4000));
+        assert_eq!(ids.peek(), 4001);
+    }
+
+    /// 测试 split_trim 去掉字段两端空白
+    #[test]
+    fn test_split_trim_strips_surrounding_spaces() {
+        let fields: Vec<&str> = basics::split_trim("  host , 8080,\tdebug  ", ',').collect();
+        assert_eq!(fields, ["host", "8080", "debug"]);
+    }
+
+    /// 测试相邻分隔符之间的空字段被保留
+    #[test]
+    fn test_split_trim_keeps_empty_fields() {
+        let fields: Vec<&str> = basics::split_trim("a, ,b,,c;", ',').collect();
+        assert_eq!(fields, ["a", "", "b", "", "c;"]);
+        assert_eq!(basics::split_trim("", ',').collect::<Vec<_>>(), [""]);
+    }
+
+    /// 测试守卫存活期间同一路径无法再次加锁，析构后可重新加锁
+    #[test]
+    fn test_file_lock_is_exclusive_while_guard_alive() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("tasks.json");
+
+        let first = basics::FileLock::try_acquire(&target).unwrap().unwrap();
+        assert_eq!(first.lock_path(), dir.path().join("tasks.json.lock"));
+        assert!(basics::FileLock::try_acquire(&target).unwrap().is_none());
+
+        drop(first);
+        assert!(!dir.path().join("tasks.json.lock").exists());
+        assert!(basics::FileLock::try_acquire(&target).unwrap().is_some());
+    }
+
+    /// 测试崩溃进程遗留的失效锁文件会被回收
+    #[test]
+    fn test_file_lock_reclaims_stale_lock_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("tasks.json");
+        let lock_path = dir.path().join("tasks.json.lock");
+
+        std::fs::write(&lock_path, "4000000000\n0\n").unwrap();
+        let lock = basics::FileLock::try_acquire(&target)
+            .unwrap()
+            .expect("an expired lock should be reclaimed");
+        let contents = std::fs::read_to_string(lock.lock_path()).unwrap();
+        assert!(contents.starts_with(&std::process::id().to_string()));
+        drop(lock);
+
+        // 时间戳较新但进程不存在的锁同样可以回收（PID 超出 Linux 上限）
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        std::fs::write(&lock_path, format!("4000000000\n{now}\n")).unwrap();
+        let reclaimed = basics::FileLock::try_acquire(&target).unwrap();
+        assert_eq!(reclaimed.is_some(), cfg!(target_os = "linux"));
+    }
+
+    /// 测试阻塞加锁在超时后返回 TimedOut
+    #[test]
+    fn test_file_lock_acquire_blocking_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("tasks.json");
+
+        let _held = basics::FileLock::try_acquire(&target).unwrap().unwrap();
+        let error =
+            basics::FileLock::acquire_blocking(&target, std::time::Duration::from_millis(30))
+                .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    /// 测试双调排序在非 2 的幂长度上与标准库排序一致
+    #[test]
+    fn test_bitonic_sort_matches_std_sort_for_non_power_of_two_lengths() {
+        let mut rng = basics::Lcg::new(7);
+        for len in [5, 7, 13] {
+            // 小取值范围保证出现重复值；首尾放入极值以覆盖与填充值相同的情况
+            let mut values: Vec<i32> = (0..len).map(|_| rng.gen_range(0, 10) as i32 - 5).collect();
+            values[0] = i32::MAX;
+            values[len - 1] = i32::MIN;
+
+            let mut expected = values.clone();
+            expected.sort();
+            basics::algorithms::bitonic_sort(&mut values);
+            assert_eq!(values, expected, "length {len}");
+        }
+
+        for len in [5, 7, 13] {
+            let mut values: Vec<i32> = (0..len).rev().collect();
+            basics::algorithms::bitonic_sort(&mut values);
+            assert_eq!(values, (0..len).collect::<Vec<_>>());
+        }
+    }
+
+    /// 测试经典背包示例
+    #[test]
+    fn test_knapsack_classic_example() {
+        let result = basics::algorithms::knapsack_01(&[2, 3, 4, 5], &[3, 4, 5, 6], 8);
+        assert_eq!(result, Ok(10));
+        assert_eq!(basics::algorithms::knapsack_01(&[], &[], 8), Ok(0));
+    }
+
+    /// 测试放不下的物品不计入价值
+    #[test]
+    fn test_knapsack_item_that_does_not_fit() {
+        assert_eq!(basics::algorithms::knapsack_01(&[10], &[100], 9), Ok(0));
+        assert_eq!(
+            basics::algorithms::knapsack_01(&[10, 3, u64::MAX], &[100, 7, 1_000], 9),
+            Ok(7)
+        );
+    }
+
+    /// 测试长度不一致与价值溢出返回错误
+    #[test]
+    fn test_knapsack_rejects_invalid_input() {
+        use basics::algorithms::KnapsackError;
+
+        assert_eq!(
+            basics::algorithms::knapsack_01(&[1, 2], &[3], 5),
+            Err(KnapsackError::LengthMismatch {
+                weights: 2,
+                values: 1
+            })
+        );
+        assert_eq!(
+            basics::algorithms::knapsack_01(&[1, 1], &[u64::MAX, 1], 2),
+            Err(KnapsackError::ValueOverflow)
+        );
+    }
+
+    /// 测试快速幂计算整数幂，次数为 0 时返回单位元
+    #[test]
+    fn test_pow_by_squaring_integer_powers() {
+        use basics::algorithms::pow_by_squaring;
+
+        let mul = |a: &u64, b: &u64| a * b;
+        assert_eq!(pow_by_squaring(2u64, 10, mul, 1), 1024);
+        assert_eq!(pow_by_squaring(3u64, 13, mul, 1), 1_594_323);
+        assert_eq!(pow_by_squaring(7u64, 0, mul, 1), 1);
+        assert_eq!(pow_by_squaring(2u64, 63, mul, 1), 1 << 63);
+
+        let concat = |a: &String, b: &String| format!("{a}{b}");
+        assert_eq!(
+            pow_by_squaring("ab".to_string(), 3, concat, String::new()),
+            "ababab"
+        );
+    }
+
+    /// 测试同一快速幂函数计算矩阵幂（斐波那契矩阵）
+    #[test]
+    fn test_pow_by_squaring_matrix_powers() {
+        use advanced_types::Matrix;
+        use basics::algorithms::pow_by_squaring;
+
+        let fib = |n: u64| {
+            let power = pow_by_squaring(
+                Matrix::new([[1, 1], [1, 0]]),
+                n,
+                |a, b| a.matmul(b),
+                Matrix::<2, 2>::identity(),
+            );
+            power.get(0, 1)
+        };
+
+        assert_eq!(fib(0), 0);
+        assert_eq!(fib(1), 1);
+        assert_eq!(fib(10), 55);
+        assert_eq!(fib(92), basics::checked_fib(92).unwrap());
+    }
+
+    /// 测试稳定与不稳定两个分支都能得到有序结果
+    #[test]
+    fn test_sort_both_branches_produce_sorted_output() {
+        use basics::algorithms::sort;
+
+        let original = vec![5, -3, 9, 0, 5, 12, -7, 3, 3, 8, 1, 0, 42, -1];
+        let mut expected = original.clone();
+        expected.sort();
+
+        for stable in [true, false] {
+            let mut data = original.clone();
+            sort(&mut data, stable);
+            assert_eq!(data, expected, "stable = {}", stable);
+
+            let mut empty: Vec<i32> = Vec::new();
+            sort(&mut empty, stable);
+            assert!(empty.is_empty());
+
+            let mut descending: Vec<u32> = (0..100).rev().collect();
+            sort(&mut descending, stable);
+            assert!(descending.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    /// 测试带深度保护的快速排序处理退化输入时不会栈溢出且结果正确
+    #[test]
+    fn test_quick_sort_optimized_handles_adversarial_inputs() {
+        use basics::algorithms::quick_sort_optimized;
+
+        let n = 200_000;
+        let ascending: Vec<i64> = (0..n).collect();
+        let descending: Vec<i64> = (0..n).rev().collect();
+        let repeated = vec![3i64; n as usize];
+        let mut rng = basics::Lcg::new(11);
+        let random: Vec<i64> = (0..n).map(|_| rng.gen_range(0, 1000) as i64).collect();
+
+        for original in [ascending, descending, repeated, random] {
+            let mut expected = original.clone();
+            expected.sort();
+            let mut data = original;
+            quick_sort_optimized(&mut data);
+            assert_eq!(data, expected);
+        }
+    }
+
+    /// 测试深度上限为 0 时直接退化为堆排序
+    #[test]
+    fn test_quick_sort_with_zero_depth_falls_back_to_heap_sort() {
+        use basics::algorithms::{heap_sort, quick_sort_with_depth_limit};
+
+        let original = vec![5.5, -1.0, 3.25, 0.0, 9.0, -7.5, 3.25];
+        let mut expected = original.clone();
+        expected.sort_by(f64::total_cmp);
+
+        let mut limited = original.clone();
+        quick_sort_with_depth_limit(&mut limited, 0);
+        assert_eq!(limited, expected);
+
+        let mut heap = original;
+        heap_sort(&mut heap);
+        assert_eq!(heap, expected);
+    }
+
+    /// 测试稳定分支保留相等键的原始顺序
+    #[test]
+    fn test_sort_stable_branch_preserves_equal_key_order() {
+        use basics::algorithms::sort;
+        use std::cmp::Ordering;
+
+        // 只按 key 比较，seq 记录原始位置
+        #[derive(Debug, PartialEq, Eq)]
+        struct Keyed {
+            key: u8,
+            seq: usize,
+        }
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        let keys = [3, 1, 2, 3, 1, 2, 3, 1, 2, 0, 3, 1];
+        let mut data: Vec<Keyed> = keys
+            .iter()
+            .enumerate()
+            .map(|(seq, &key)| Keyed { key, seq })
+            .collect();
+        sort(&mut data, true);
+
+        assert!(data.windows(2).all(|w| w[0].key <= w[1].key));
+        for pair in data.windows(2) {
+            if pair[0].key == pair[1].key {
+                assert!(pair[0].seq < pair[1].seq, "{:?}", pair);
+            }
+        }
+    }
+
+    /// 测试双调排序处理空切片与单元素
+    #[test]
+    fn test_bitonic_sort_trivial_inputs() {
+        let mut empty: [i32; 0] = [];
+        basics::algorithms::bitonic_sort(&mut empty);
+
+        let mut single = [42];
+        basics::algorithms::bitonic_sort(&mut single);
+        assert_eq!(single, [42]);
+    }
+
+    /// 测试 Levenshtein 编辑距离
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(basics::levenshtein("rust", "rust"), 0);
+        assert_eq!(basics::levenshtein("", ""), 0);
+        assert_eq!(basics::levenshtein("rust", "trust"), 1);
+        assert_eq!(basics::levenshtein("rust", "rest"), 1);
+        assert_eq!(basics::levenshtein("kitten", "sitting"), 3);
+        assert_eq!(basics::levenshtein("", "abc"), 3);
+    }
+
+    /// 测试 Levenshtein 按字符而非字节计算
+    #[test]
+    fn test_levenshtein_multibyte() {
+        assert_eq!(basics::levenshtein("你好世界", "你好世间"), 1);
+        assert_eq!(basics::levenshtein("数据", "数据库"), 1);
+    }
+
+    /// 测试 gen_titles 相同种子输出一致、不同种子输出不同
+    #[test]
+    fn test_gen_titles_is_reproducible_per_seed() {
+        use basics::test_util::gen_titles;
+
+        let titles = gen_titles(50, 42);
+        assert_eq!(titles.len(), 50);
+        assert_eq!(titles, gen_titles(50, 42));
+        assert_ne!(titles, gen_titles(50, 43));
+
+        let unique: std::collections::HashSet<_> = titles.iter().collect();
+        assert_eq!(unique.len(), titles.len());
+        assert!(gen_titles(0, 42).is_empty());
+    }
+
+    /// 测试相同种子的 Lcg 产生相同序列
+    #[test]
+    fn test_lcg_same_seed_same_sequence() {
+        let mut first = basics::Lcg::new(2024);
+        let mut second = basics::Lcg::new(2024);
+        let a: Vec<u64> = (0..16).map(|_| first.next_u64()).collect();
+        let b: Vec<u64> = (0..16).map(|_| second.next_u64()).collect();
+        assert_eq!(a, b);
+
+        let mut other = basics::Lcg::new(2025);
+        assert_ne!(a[0], other.next_u64());
+
+        let mut left: Vec<u32> = (0..20).collect();
+        let mut right = left.clone();
+        basics::Lcg::new(7).shuffle(&mut left);
+        basics::Lcg::new(7).shuffle(&mut right);
+        assert_eq!(left, right);
+        left.sort_unstable();
+        assert_eq!(left, (0..20).collect::<Vec<_>>());
+    }
+
+    /// 测试 gen_range 始终落在区间内
+    #[test]
+    fn test_lcg_gen_range_stays_in_bounds() {
+        let mut rng = basics::Lcg::new(1);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let value = rng.gen_range(10, 16);
+            assert!((10..16).contains(&value));
+            seen[(value - 10) as usize] = true;
+        }
+        assert!(
+            seen.iter().all(|hit| *hit),
+            "every value in range should appear"
+        );
+        assert_eq!(rng.gen_range(5, 6), 5);
+    }
+
+    /// 测试固定种子下蓄水池抽样结果确定且不超过 k
+    #[test]
+    fn test_reservoir_sample_is_deterministic_and_bounded() {
+        let first = basics::reservoir_sample(0..1000, 10, &mut basics::Lcg::new(99));
+        let second = basics::reservoir_sample(0..1000, 10, &mut basics::Lcg::new(99));
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 10);
+        assert!(first.iter().all(|value| (0..1000).contains(value)));
+
+        for k in [0, 1, 3, 50] {
+            let sample = basics::reservoir_sample(0..200, k, &mut basics::Lcg::new(k as u64));
+            assert!(sample.len() <= k);
+        }
+    }
+
+    /// 测试流长度不足 k 时返回全部元素
+    #[test]
+    fn test_reservoir_sample_short_stream_returns_everything() {
+        let sample =
+            basics::reservoir_sample(["a", "b", "c"].into_iter(), 5, &mut basics::Lcg::new(1));
+        assert_eq!(sample, vec!["a", "b", "c"]);
+    }
+
+    /// 测试 icontains 对 ASCII 与非 ASCII 大小写不敏感
+    #[test]
+    fn test_icontains_mixed_case() {
+        assert!(basics::icontains("Write Rust Tests", "rust"));
+        assert!(basics::icontains("write rust tests", "RUST T"));
+        assert!(basics::icontains("Größe ÄNDERN", "äNdErN"));
+        assert!(basics::icontains("anything", ""));
+        assert!(!basics::icontains("Rust", "rusty"));
+        assert!(!basics::icontains("", "a"));
+    }
+
+    /// 测试土耳其语 i 的大小写对——已知限制：不做语言相关的大小写折叠
+    #[test]
+    fn test_icontains_turkish_i_known_limitation() {
+        // `İ` 小写后为 `i` + U+0307 组合点，因此与普通 `i` 不匹配
+        assert!(!basics::icontains("İstanbul", "istanbul"));
+        // `I` 小写为 `i` 而非土耳其语的无点 `ı`
+        assert!(!basics::icontains("ISPARTA", "ısparta"));
+        // 同一字符的大小写形式仍可互相匹配
+        assert!(basics::icontains("İstanbul", "İSTANBUL"));
+    }
 }
 
 // ==================== 所有权模块详细测试 ====================
@@ -144,315 +742,3132 @@ mod ownership_tests {
     }
 }
 
+// ==================== 模块系统详细测试 ====================
+
+mod modules_tests {
+    use super::*;
+    use modules::farm::animals::Animal;
+
+    /// 测试农场添加、喂食与按名称移除动物
+    #[test]
+    fn test_farm_feed_all_and_remove_animal() {
+        let mut farm = modules::Farm::new("测试农场");
+        farm.add_animal(Animal::new("Max", "牧羊犬", 5));
+        farm.add_animal(Animal::new("Bella", "奶牛", 3));
+        farm.add_animal(Animal::new("Dolly", "绵羊", 2));
+        assert_eq!(farm.animals().len(), 3);
+
+        farm.feed_all();
+        assert!(farm.animals().iter().all(|animal| animal.fed));
+
+        let removed = farm.remove_animal("Bella").expect("Bella should exist");
+        assert_eq!(removed.species, "奶牛");
+        assert_eq!(farm.animals().len(), 2);
+        assert!(farm.remove_animal("Bella").is_none());
+    }
+
+    /// 测试畜棚批量喂食并按注入时间统计饥饿动物
+    #[test]
+    fn test_barn_feed_all_and_hungry_with_injected_time() {
+        use modules::farm::animals::Barn;
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let interval = Duration::from_secs(4 * 3600);
+        let mut barn = Barn::new();
+        barn.add(Animal::new("Max", "牧羊犬", 5));
+        barn.add(Animal::new("Bella", "奶牛", 3));
+        assert_eq!(
+            barn.hungry_at(interval, start).len(),
+            2,
+            "never-fed animals are hungry"
+        );
+
+        assert_eq!(barn.feed_all_at(start), 2);
+        let mut dolly = Animal::new("Dolly", "绵羊", 2);
+        dolly.feed_at(start + Duration::from_secs(2 * 3600));
+        barn.add(dolly);
+
+        let later = start + interval;
+        let hungry: Vec<&str> = barn
+            .hungry_at(interval, later)
+            .into_iter()
+            .map(|animal| animal.name.as_str())
+            .collect();
+        assert_eq!(hungry, ["Max", "Bella"]);
+        assert!(
+            barn.hungry_at(interval, start + Duration::from_secs(60))
+                .is_empty()
+        );
+
+        assert_eq!(
+            barn.feed_all_at(later),
+            0,
+            "every animal had been fed before"
+        );
+        barn.add(Animal::new("Lamb", "羊羔", 1));
+        assert_eq!(
+            barn.feed_all_at(later),
+            1,
+            "only the new animal is newly fed"
+        );
+        assert!(barn.hungry_at(interval, later).is_empty());
+    }
+
+    /// 测试作物总量上限：未超限、恰好达到上限与超出上限
+    #[test]
+    fn test_crop_manager_with_limit_enforces_total_quantity() {
+        use modules::farm::crops::CropManager;
+
+        let mut manager = CropManager::with_limit(100);
+        assert_eq!(manager.plant("wheat", 60), Ok(()));
+        assert_eq!(
+            manager.plant("corn", 40),
+            Ok(()),
+            "reaching the limit exactly is allowed"
+        );
+        assert_eq!(manager.total_quantity(), 100);
+
+        let error = manager.plant("soybean", 1).unwrap_err();
+        assert!(
+            error.contains("100"),
+            "error should mention the limit: {error}"
+        );
+        assert_eq!(
+            manager.total_quantity(),
+            100,
+            "rejected plant should not change totals"
+        );
+        assert!(!manager.get_status().contains("soybean"));
+
+        let mut unbounded = CropManager::new();
+        assert_eq!(
+            unbounded.plant("wheat", modules::modern_exports::MAX_CROPS + 1),
+            Ok(())
+        );
+    }
+
+    /// 测试按价格区间筛选产品
+    #[test]
+    fn test_product_service_find_in_price_range() {
+        use modules::enterprise::{InMemoryProductRepository, ProductService};
+
+        let mut service = ProductService::new(InMemoryProductRepository::new());
+        for (name, price) in [("键盘", 199.0), ("显示器", 1499.0), ("笔记本", 8999.0)] {
+            service
+                .create_product(name.to_string(), price, "电子".to_string(), 10)
+                .unwrap();
+        }
+
+        let names: Vec<_> = service
+            .find_in_price_range(100.0, 1500.0)
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["键盘", "显示器"]);
+        assert!(service.find_in_price_range(10_000.0, 20_000.0).is_empty());
+    }
+
+    /// 测试通用内存仓库的 save/get/delete
+    #[test]
+    fn test_memory_repo_save_get_delete() {
+        use modules::{MemoryRepo, Repository};
+
+        let mut repo: MemoryRepo<String, u32> = MemoryRepo::new();
+        assert_eq!(repo.save("a".to_string(), 1), None);
+        assert_eq!(repo.save("b".to_string(), 2), None);
+        assert_eq!(
+            repo.save("a".to_string(), 10),
+            Some(1),
+            "saving an existing id replaces it"
+        );
+
+        assert_eq!(repo.get(&"a".to_string()), Some(10));
+        let mut all = repo.all();
+        all.sort();
+        assert_eq!(all, vec![2, 10]);
+
+        assert_eq!(repo.delete(&"b".to_string()), Some(2));
+        assert_eq!(repo.delete(&"b".to_string()), None);
+        assert_eq!(repo.get(&"b".to_string()), None);
+        assert_eq!(repo.all(), vec![10]);
+    }
+
+    /// 测试通用内存仓库按条件查找
+    #[test]
+    fn test_memory_repo_find_returns_matching_entity() {
+        use modules::{MemoryRepo, Repository};
+
+        let mut repo: MemoryRepo<u64, String> = MemoryRepo::new();
+        repo.save(1, "alice@example.com".to_string());
+        repo.save(2, "bob@example.com".to_string());
+
+        assert_eq!(
+            repo.find(|email| email.starts_with("bob")),
+            Some("bob@example.com".to_string())
+        );
+        assert_eq!(repo.find(|email| email.is_empty()), None);
+    }
+
+    /// 测试基于通用仓库的用户仓库仍支持按 ID 与邮箱查找
+    #[test]
+    fn test_in_memory_user_repository_built_on_generic_repo() {
+        use modules::enterprise::{InMemoryUserRepository, UserRepository, UserService};
+
+        let mut repo = InMemoryUserRepository::new();
+        let user = modules::enterprise::User {
+            id: 0,
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            created_at: chrono::Utc::now(),
+        };
+        let saved = repo.save(user).unwrap();
+        assert_eq!(saved.id, 1);
+        assert_eq!(repo.find_by_id(1), Some(saved.clone()));
+        assert_eq!(repo.find_by_email("alice@example.com"), Some(saved));
+        assert_eq!(repo.find_by_email("bob@example.com"), None);
+
+        let mut service = UserService::new(repo);
+        assert!(
+            service
+                .create_user("alice2".to_string(), "alice@example.com".to_string())
+                .is_err()
+        );
+    }
+
+    /// 测试产品的 serde 往返
+    #[test]
+    fn test_product_serde_round_trip() {
+        use modules::enterprise::Product;
+
+        let product = Product {
+            id: 7,
+            name: "机械键盘".to_string(),
+            price: 399.5,
+            category: "外设".to_string(),
+            stock: 12,
+        };
+
+        let json = serde_json::to_string(&product).unwrap();
+        let restored: Product = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, product);
+    }
+
+    /// 测试购买成功后扣减库存
+    #[test]
+    fn test_product_service_purchase_decrements_stock() {
+        use modules::enterprise::{InMemoryProductRepository, ProductService};
+
+        let mut service = ProductService::new(InMemoryProductRepository::new());
+        let product = service
+            .create_product("鼠标".to_string(), 99.0, "外设".to_string(), 5)
+            .unwrap();
+
+        service.purchase(product.id, 3).unwrap();
+        let remaining = service.find_in_price_range(0.0, 100.0);
+        assert_eq!(remaining[0].stock, 2);
+    }
+
+    /// 测试超量购买被拒绝且库存不变
+    #[test]
+    fn test_product_service_rejects_over_purchase() {
+        use modules::enterprise::{InMemoryProductRepository, ProductService};
+
+        let mut service = ProductService::new(InMemoryProductRepository::new());
+        let product = service
+            .create_product("鼠标".to_string(), 99.0, "外设".to_string(), 2)
+            .unwrap();
+
+        let error = service.purchase(product.id, 3).unwrap_err();
+        assert!(error.contains("库存不足"));
+        assert_eq!(service.find_in_price_range(0.0, 100.0)[0].stock, 2);
+    }
+
+    /// 测试购买不存在的产品
+    #[test]
+    fn test_product_service_purchase_unknown_product() {
+        use modules::enterprise::{InMemoryProductRepository, ProductService};
+
+        let mut service = ProductService::new(InMemoryProductRepository::new());
+        let error = service.purchase(42, 1).unwrap_err();
+        assert!(error.contains("产品不存在"));
+    }
+}
+
 // ==================== 类型系统详细测试 ====================
 
 mod types_tests {
     use super::*;
 
-    /// 测试结构体
+    /// 测试结构体
+    #[test]
+    fn test_structs() {
+        types::structs();
+    }
+
+    /// 测试枚举
+    #[test]
+    fn test_enums() {
+        types::enums();
+    }
+
+    /// 测试特征
+    #[test]
+    fn test_traits() {
+        types::traits();
+    }
+
+    /// 测试泛型
+    #[test]
+    fn test_generics() {
+        types::generics();
+    }
+
+    /// 测试高级特征对象
+    #[test]
+    fn test_advanced_trait_objects() {
+        types::advanced_trait_objects();
+    }
+
+    /// 测试关联类型
+    #[test]
+    fn test_associated_types() {
+        types::associated_types();
+    }
+
+    /// 测试NewType模式
+    #[test]
+    fn test_newtype_pattern() {
+        types::newtype_pattern();
+    }
+
+    /// 测试高级特征约束
+    #[test]
+    fn test_advanced_trait_bounds() {
+        types::advanced_trait_bounds();
+    }
+
+    /// 测试类型级编程
+    #[test]
+    fn test_type_level_programming() {
+        types::type_level_programming();
+    }
+
+    /// 测试组合模式
+    #[test]
+    fn test_composition_patterns() {
+        types::composition_patterns();
+    }
+
+    /// 测试高级类型模式
+    #[test]
+    fn test_advanced_type_patterns() {
+        types::advanced_type_patterns();
+    }
+
+    /// 测试类型安全API模式
+    #[test]
+    fn test_type_safe_api_patterns() {
+        types::type_safe_api_patterns();
+    }
+
+    /// 测试信号灯状态循环 红→绿→黄→红
+    #[test]
+    fn test_traffic_light_cycle() {
+        use types::TrafficLight;
+
+        assert_eq!(TrafficLight::Red.next(), TrafficLight::Green);
+        let states: Vec<_> = TrafficLight::Red.cycle().take(4).collect();
+        assert_eq!(
+            states,
+            vec![
+                TrafficLight::Red,
+                TrafficLight::Green,
+                TrafficLight::Yellow,
+                TrafficLight::Red,
+            ]
+        );
+    }
+
+    /// 测试信号灯各状态的持续时间
+    #[test]
+    fn test_traffic_light_durations() {
+        use std::time::Duration;
+        use types::TrafficLight;
+
+        assert_eq!(TrafficLight::Red.duration(), Duration::from_secs(30));
+        assert_eq!(TrafficLight::Green.duration(), Duration::from_secs(25));
+        assert_eq!(TrafficLight::Yellow.duration(), Duration::from_secs(5));
+    }
+
+    /// 测试形状的面积与周长
+    #[test]
+    fn test_shape_area_and_perimeter() {
+        use types::Shape;
+
+        let circle = Shape::Circle { radius: 1.0 };
+        assert!((circle.area() - std::f64::consts::PI).abs() < 1e-9);
+        assert!((circle.perimeter() - 2.0 * std::f64::consts::PI).abs() < 1e-9);
+
+        let rectangle = Shape::Rectangle {
+            width: 4.0,
+            height: 6.0,
+        };
+        assert_eq!(rectangle.area(), 24.0);
+        assert_eq!(rectangle.perimeter(), 20.0);
+
+        let triangle = Shape::triangle(3.0, 4.0, 5.0).unwrap();
+        assert_eq!(triangle.area(), 6.0);
+        assert_eq!(triangle.perimeter(), 12.0);
+    }
+
+    /// 测试无效三角形被拒绝
+    #[test]
+    fn test_shape_rejects_invalid_triangle() {
+        assert!(types::Shape::triangle(1.0, 2.0, 10.0).is_err());
+        assert!(types::Shape::triangle(0.0, 1.0, 1.0).is_err());
+        assert!(types::Shape::triangle(f64::NAN, 1.0, 1.0).is_err());
+        assert!(types::Shape::triangle(f64::INFINITY, 1.0, 1.0).is_err());
+    }
+
+    /// 测试形状按面积排序，而相等比较基于变体数据
+    #[test]
+    fn test_shape_ordering_by_area() {
+        use std::cmp::Ordering;
+        use types::Shape;
+
+        let small = Shape::triangle(3.0, 4.0, 5.0).unwrap();
+        let large = Shape::Rectangle {
+            width: 4.0,
+            height: 6.0,
+        };
+        assert_eq!(small.cmp_by_area(&large), Ordering::Less);
+        assert_eq!(large.cmp_by_area(&small), Ordering::Greater);
+
+        let mut shapes = vec![large.clone(), small.clone()];
+        shapes.sort_by(Shape::cmp_by_area);
+        assert_eq!(shapes, vec![small, large]);
+
+        // 面积相同但形状不同：按面积比较相等，结构上不相等
+        let square = Shape::Rectangle {
+            width: 2.0,
+            height: 2.0,
+        };
+        let strip = Shape::Rectangle {
+            width: 1.0,
+            height: 4.0,
+        };
+        assert_eq!(square.cmp_by_area(&strip), Ordering::Equal);
+        assert_ne!(square, strip);
+    }
+
+    /// 测试插件注册表按名称调用与批量运行
+    #[test]
+    fn test_plugin_registry_get_and_run_all() {
+        use types::{Plugin, PluginRegistry};
+
+        struct Echo;
+        impl Plugin for Echo {
+            fn name(&self) -> &str {
+                "echo"
+            }
+            fn run(&self, input: &str) -> String {
+                input.to_string()
+            }
+        }
+
+        struct Length;
+        impl Plugin for Length {
+            fn name(&self) -> &str {
+                "length"
+            }
+            fn run(&self, input: &str) -> String {
+                input.len().to_string()
+            }
+        }
+
+        let mut registry = PluginRegistry::new();
+        registry.register(Box::new(Echo));
+        registry.register(Box::new(Length));
+        assert_eq!(registry.len(), 2);
+
+        let length = registry.get("length").expect("length plugin registered");
+        assert_eq!(length.run("abcd"), "4");
+        assert!(registry.get("missing").is_none());
+
+        assert_eq!(
+            registry.run_all("hi"),
+            vec![
+                ("echo".to_string(), "hi".to_string()),
+                ("length".to_string(), "2".to_string()),
+            ]
+        );
+    }
+
+    /// 测试非空字符串接受正常输入
+    #[test]
+    fn test_non_empty_string_accepts_text() {
+        let title = types::NonEmptyString::new("  写周报 ".to_string()).unwrap();
+        assert_eq!(title.as_str(), "  写周报 ");
+        assert_eq!(title.to_string(), "  写周报 ");
+        assert_eq!(title.into_inner(), "  写周报 ");
+    }
+
+    /// 测试空串与纯空白字符串被拒绝
+    #[test]
+    fn test_non_empty_string_rejects_blank() {
+        assert!(types::NonEmptyString::new(String::new()).is_err());
+        assert!(types::NonEmptyString::new("   ".to_string()).is_err());
+        assert!(types::NonEmptyString::new("\t\n".to_string()).is_err());
+    }
+
+    /// 测试通过 Deref 直接使用 str 的方法
+    #[test]
+    fn test_non_empty_string_derefs_to_str() {
+        let name = types::NonEmptyString::new("Rust Book".to_string()).unwrap();
+
+        assert_eq!(name.len(), 9);
+        assert!(name.starts_with("Rust"));
+        assert_eq!(name.to_uppercase(), "RUST BOOK");
+
+        fn takes_str(s: &str) -> usize {
+            s.split_whitespace().count()
+        }
+        assert_eq!(takes_str(&name), 2);
+    }
+}
+
+// ==================== 高级类型详细测试 ====================
+
+mod advanced_types_tests {
+    use super::*;
+    use advanced_types::{SessionId, TreeNode, UserId};
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    /// 测试类型化事件分发给多个观察者，注销后不再接收
+    #[test]
+    fn test_subject_delivers_typed_event_to_observers() {
+        use advanced_types::{Observer, Subject};
+        use std::cell::RefCell;
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct PriceChanged {
+            sku: &'static str,
+            old: u32,
+            new: u32,
+        }
+
+        struct Recorder {
+            name: &'static str,
+            log: Rc<RefCell<Vec<(&'static str, PriceChanged)>>>,
+        }
+
+        impl Observer<PriceChanged> for Recorder {
+            fn on_event(&self, event: &PriceChanged) {
+                self.log.borrow_mut().push((self.name, event.clone()));
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut subject = Subject::new();
+        let cart = subject.attach(Box::new(Recorder {
+            name: "cart",
+            log: Rc::clone(&log),
+        }));
+        subject.attach(Box::new(Recorder {
+            name: "alerts",
+            log: Rc::clone(&log),
+        }));
+        assert_eq!(subject.observer_count(), 2);
+
+        let first = PriceChanged {
+            sku: "A1",
+            old: 100,
+            new: 80,
+        };
+        subject.notify(&first);
+        assert_eq!(
+            *log.borrow(),
+            vec![("cart", first.clone()), ("alerts", first.clone())]
+        );
+
+        assert!(subject.detach(cart));
+        assert!(!subject.detach(cart));
+        let second = PriceChanged {
+            sku: "B2",
+            old: 50,
+            new: 55,
+        };
+        subject.notify(&second);
+        assert_eq!(log.borrow().len(), 3);
+        assert_eq!(log.borrow()[2], ("alerts", second));
+    }
+
+    /// 测试屏幕按名称添加、查找与移除组件
+    #[test]
+    fn test_modern_screen_find_and_remove_by_name() {
+        use advanced_types::{ModernButton, ModernScreen, ModernText};
+
+        let mut screen = ModernScreen::new();
+        screen.add_component(Box::new(ModernButton {
+            name: "submit".to_string(),
+            width: 80,
+            height: 24,
+            label: "提交".to_string(),
+            color: "绿色".to_string(),
+            style: "扁平".to_string(),
+        }));
+        screen.add_component(Box::new(ModernText {
+            name: "title".to_string(),
+            text: "标题".to_string(),
+            font_size: 20,
+            color: "黑色".to_string(),
+            style: "加粗".to_string(),
+        }));
+
+        let submit = screen.find("submit").expect("button should be found");
+        assert_eq!(submit.name(), "submit");
+        assert_eq!(submit.get_color(), "绿色");
+        assert!(submit.draw().contains("提交"));
+        assert!(screen.find("missing").is_none());
+
+        let removed = screen.remove("submit").expect("button should be removed");
+        assert_eq!(removed.name(), "submit");
+        assert!(screen.find("submit").is_none());
+        assert!(screen.remove("submit").is_none());
+        assert_eq!(
+            screen.render_lines(),
+            vec!["绘制文本: '标题' (大小:20, 黑色-加粗)"]
+        );
+    }
+
+    /// 测试每次 next() 后大小提示、剩余数与进度同步变化
+    #[test]
+    fn test_modern_counter_hint_shrinks_after_each_next() {
+        use advanced_types::{ModernCounter, ModernIterator};
+
+        let mut counter = ModernCounter::new(3);
+        assert_eq!(counter.size_hint(), (3, Some(3)));
+        assert_eq!(counter.progress(), (0, 3));
+
+        for consumed in 1..=3 {
+            assert_eq!(counter.next(), Some(consumed - 1));
+            let remaining = 3 - consumed;
+            assert_eq!(counter.remaining(), remaining);
+            assert_eq!(counter.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(counter.progress(), (consumed, 3));
+        }
+
+        assert_eq!(counter.next(), None);
+        assert_eq!(counter.remaining(), 0);
+        assert_eq!(counter.progress(), (3, 3), "exhausted counter stays at max");
+    }
+
+    /// 测试空计数器的进度
+    #[test]
+    fn test_modern_counter_empty_progress() {
+        use advanced_types::{ModernCounter, ModernIterator};
+
+        let mut counter = ModernCounter::new(0);
+        assert_eq!(counter.next(), None);
+        assert_eq!(counter.size_hint(), (0, Some(0)));
+        assert_eq!(counter.progress(), (0, 0));
+    }
+
+    /// 测试 UserId 可比较并可作为 HashMap 键
+    #[test]
+    fn test_user_id_as_hash_map_key() {
+        assert_eq!(UserId::from(42), UserId::new(42));
+        assert_ne!(UserId::from(42), UserId::from(43));
+
+        let mut sessions = HashMap::new();
+        sessions.insert(UserId::from(42), SessionId::from("session-a".to_string()));
+        sessions.insert(UserId::from(7), SessionId::new("session-b".to_string()));
+
+        assert_eq!(
+            sessions.get(&UserId::new(42)),
+            Some(&SessionId::from("session-a".to_string()))
+        );
+        assert_eq!(sessions.len(), 2);
+    }
+
+    /// 丢弃时计数的树节点值
+    struct DropCounter {
+        name: &'static str,
+        drops: Rc<Cell<usize>>,
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    /// 测试树节点可以导航到父节点并计算深度
+    #[test]
+    fn test_tree_node_parent_and_depth() {
+        let root = TreeNode::new("root");
+        let branch = TreeNode::add_child(&root, "branch");
+        let leaf = TreeNode::add_child(&branch, "leaf");
+        TreeNode::add_child(&root, "sibling");
+
+        assert_eq!(root.borrow().depth(), 0);
+        assert!(root.borrow().parent().is_none());
+        assert_eq!(leaf.borrow().depth(), 2);
+
+        let parent = leaf.borrow().parent().expect("leaf has a parent");
+        assert!(Rc::ptr_eq(&parent, &branch));
+        assert_eq!(*parent.borrow().value(), "branch");
+        assert_eq!(root.borrow().children().len(), 2);
+    }
+
+    /// 测试丢弃根节点后整棵树都被释放，没有引用循环
+    #[test]
+    fn test_tree_node_is_freed_when_root_dropped() {
+        let drops = Rc::new(Cell::new(0));
+        let counter = |name| DropCounter {
+            name,
+            drops: Rc::clone(&drops),
+        };
+
+        let root = TreeNode::new(counter("root"));
+        {
+            let branch = TreeNode::add_child(&root, counter("branch"));
+            TreeNode::add_child(&branch, counter("leaf"));
+            TreeNode::add_child(&root, counter("sibling"));
+        }
+        assert_eq!(drops.get(), 0, "children are still owned by the root");
+        assert_eq!(root.borrow().children()[0].borrow().value().name, "branch");
+
+        drop(root);
+        assert_eq!(drops.get(), 4);
+    }
+
+    /// 测试有界整数接受范围内的值
+    #[test]
+    fn test_bounded_u32_accepts_in_range() {
+        use advanced_types::BoundedU32;
+
+        let percent = BoundedU32::<0, 100>::new(42).unwrap();
+        assert_eq!(percent.get(), 42);
+        assert!(BoundedU32::<10, 20>::new(15).is_some());
+    }
+
+    /// 测试有界整数拒绝范围外的值
+    #[test]
+    fn test_bounded_u32_rejects_out_of_range() {
+        use advanced_types::BoundedU32;
+
+        assert!(BoundedU32::<0, 100>::new(101).is_none());
+        assert!(BoundedU32::<10, 20>::new(9).is_none());
+        assert!(BoundedU32::<10, 20>::new(21).is_none());
+        assert!(BoundedU32::<10, 20>::new(u32::MAX).is_none());
+    }
+
+    /// 测试边界值 MIN 与 MAX 本身被接受
+    #[test]
+    fn test_bounded_u32_accepts_boundaries() {
+        use advanced_types::BoundedU32;
+
+        assert_eq!(BoundedU32::<10, 20>::new(10).map(|v| v.get()), Some(10));
+        assert_eq!(BoundedU32::<10, 20>::new(20).map(|v| v.get()), Some(20));
+        assert_eq!(BoundedU32::<0, 100>::new(0).map(|v| v.get()), Some(0));
+        assert_eq!(
+            BoundedU32::<0, { u32::MAX }>::new(u32::MAX).map(|v| v.get()),
+            Some(u32::MAX)
+        );
+    }
+
+    /// 测试 2x3 与 3x2 矩阵相乘得到 2x2 矩阵
+    #[test]
+    fn test_matrix_multiplies_2x3_by_3x2() {
+        use advanced_types::Matrix;
+
+        let a = Matrix::new([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::new([[7, 8], [9, 10], [11, 12]]);
+
+        let product: Matrix<2, 2> = a.matmul(&b);
+        assert_eq!(product, Matrix::new([[58, 64], [139, 154]]));
+        assert_eq!(a * b, product);
+        assert_eq!(product.get(1, 0), 139);
+    }
+
+    /// 测试逐元素加法与单位矩阵
+    #[test]
+    fn test_matrix_add_and_identity() {
+        use advanced_types::Matrix;
+
+        let m = Matrix::new([[2, 3], [5, 7]]);
+        assert_eq!(m * Matrix::identity(), m);
+        assert_eq!(Matrix::<2, 2>::identity() * m, m);
+        assert_eq!(m + m, Matrix::new([[4, 6], [10, 14]]));
+        assert_eq!(m + Matrix::zero(), m);
+        assert_eq!(
+            Matrix::<3, 3>::identity().as_array(),
+            &[[1, 0, 0], [0, 1, 0], [0, 0, 1]]
+        );
+    }
+}
+
+// ==================== 错误处理详细测试（通过聚合入口 re-export） ====================
+
+mod error_handling_tests {
+    use super::*;
+
+    /// 测试现代panic处理
+    #[test]
+    fn test_modern_panic_handling() {
+        error_handling::modern_panic_handling();
+    }
+
+    /// 测试现代Result处理
+    #[test]
+    fn test_modern_result_handling() {
+        error_handling::modern_result_handling();
+    }
+
+    /// 测试现代问号模式
+    #[test]
+    fn test_modern_question_mark_patterns() {
+        error_handling::modern_question_mark_patterns();
+    }
+
+    /// 测试现代错误类型
+    #[test]
+    fn test_modern_error_types() {
+        error_handling::modern_error_types();
+    }
+
+    /// 测试现代错误恢复
+    #[test]
+    fn test_modern_error_recovery() {
+        error_handling::modern_error_recovery();
+    }
+
+    /// 测试现代错误日志
+    #[test]
+    fn test_modern_error_logging() {
+        error_handling::modern_error_logging();
+    }
+
+    /// 测试网络错误处理
+    #[test]
+    fn test_network_error_handling() {
+        error_handling::network_error_handling();
+    }
+
+    /// 测试文件系统错误处理
+    #[test]
+    fn test_file_system_error_handling() {
+        error_handling::file_system_error_handling();
+    }
+
+    /// 测试配置错误处理
+    #[test]
+    fn test_configuration_error_handling() {
+        error_handling::configuration_error_handling();
+    }
+
+    /// 测试业务验证错误处理
+    #[test]
+    fn test_business_validation_error_handling() {
+        error_handling::business_validation_error_handling();
+    }
+
+    /// 测试资源加载错误处理
+    #[test]
+    fn test_resource_loading_error_handling() {
+        error_handling::resource_loading_error_handling();
+    }
+
+    /// 测试外部服务错误处理
+    #[test]
+    fn test_external_service_error_handling() {
+        error_handling::external_service_error_handling();
+    }
+
+    /// 测试同步 retry 遵循重试策略的最大尝试次数
+    #[test]
+    fn test_retry_stops_after_max_attempts() {
+        use best_practices::{Backoff, RetryPolicy};
+        use std::time::Duration;
+
+        let policy = RetryPolicy::new(4, Duration::from_millis(1), Backoff::Exponential);
+
+        let mut attempts = Vec::new();
+        let result: Result<(), u32> = error_handling::retry(&policy, |attempt| {
+            attempts.push(attempt);
+            Err(attempt)
+        });
+        assert_eq!(result, Err(4));
+        assert_eq!(attempts, vec![1, 2, 3, 4]);
+
+        let result: Result<&str, u32> =
+            error_handling::retry(
+                &policy,
+                |attempt| if attempt == 2 { Ok("ok") } else { Err(attempt) },
+            );
+        assert_eq!(result, Ok("ok"));
+    }
+
+    /// 测试 tap_ok 只在 Ok 时运行且不改变结果
+    #[test]
+    fn test_result_ext_tap_ok_runs_only_on_ok() {
+        use error_handling::ResultExt;
+
+        let mut seen = Vec::new();
+        let ok: Result<i32, String> = Ok(3);
+        assert_eq!(ok.tap_ok(|value| seen.push(*value)), Ok(3));
+
+        let err: Result<i32, String> = Err("boom".to_string());
+        assert_eq!(
+            err.tap_ok(|value| seen.push(*value)),
+            Err("boom".to_string())
+        );
+        assert_eq!(seen, vec![3]);
+    }
+
+    /// 测试 tap_err 只在 Err 时运行且不改变结果
+    #[test]
+    fn test_result_ext_tap_err_runs_only_on_err() {
+        use error_handling::ResultExt;
+
+        let mut seen = Vec::new();
+        let ok: Result<i32, String> = Ok(3);
+        assert_eq!(ok.tap_err(|error| seen.push(error.clone())), Ok(3));
+
+        let err: Result<i32, String> = Err("boom".to_string());
+        assert_eq!(
+            err.tap_err(|error| seen.push(error.clone())),
+            Err("boom".to_string())
+        );
+        assert_eq!(seen, vec!["boom".to_string()]);
+    }
+
+    /// 测试 timed 记录失败结果与耗时，并原样返回错误
+    #[test]
+    fn test_timed_records_err_outcome_and_duration() {
+        use std::time::Duration;
+
+        let mut captured = None;
+        let result: Result<u32, &str> = error_handling::timed_with_sink(
+            "慢操作",
+            || {
+                std::thread::sleep(Duration::from_millis(2));
+                Err("boom")
+            },
+            |record| captured = Some(record),
+        );
+
+        assert_eq!(result, Err("boom"));
+        let record = captured.expect("sink should receive a record");
+        assert_eq!(record.label, "慢操作");
+        assert!(!record.ok);
+        assert!(record.duration > Duration::ZERO);
+
+        assert_eq!(error_handling::timed("快操作", || Ok::<_, ()>(7)), Ok(7));
+    }
+
+    /// 测试范围内的值（含边界与首尾空白）解析成功
+    #[test]
+    fn test_parse_in_range_accepts_in_range_value() {
+        use error_handling::parse_in_range;
+
+        assert_eq!(parse_in_range::<u16>("8080", 1, 65_535), Ok(8080));
+        assert_eq!(parse_in_range::<i32>(" -5 ", -5, 5), Ok(-5));
+        assert_eq!(parse_in_range::<i32>("5", -5, 5), Ok(5));
+        assert_eq!(parse_in_range::<f64>("0.5", 0.0, 1.0), Ok(0.5));
+    }
+
+    /// 测试越界值返回 OutOfRange
+    #[test]
+    fn test_parse_in_range_rejects_out_of_range_value() {
+        use error_handling::{ParseRangeError, parse_in_range};
+
+        assert_eq!(
+            parse_in_range::<u32>("70000", 1, 65_535),
+            Err(ParseRangeError::OutOfRange {
+                input: "70000".to_string()
+            })
+        );
+        assert!(matches!(
+            parse_in_range::<i32>("-6", -5, 5),
+            Err(ParseRangeError::OutOfRange { .. })
+        ));
+    }
+
+    /// 测试 NaN 无法与边界比较，按越界拒绝
+    #[test]
+    fn test_parse_in_range_rejects_nan() {
+        use error_handling::{ParseRangeError, parse_in_range};
+
+        assert_eq!(
+            parse_in_range::<f64>("NaN", 0.0, 1.0),
+            Err(ParseRangeError::OutOfRange {
+                input: "NaN".to_string()
+            })
+        );
+    }
+
+    /// 测试无法解析的输入返回 Invalid
+    #[test]
+    fn test_parse_in_range_rejects_garbage_input() {
+        use error_handling::{ParseRangeError, parse_in_range};
+
+        assert_eq!(
+            parse_in_range::<u32>("port", 1, 65_535),
+            Err(ParseRangeError::Invalid {
+                input: "port".to_string()
+            })
+        );
+        // 负数对无符号类型是解析失败，而不是越界
+        assert!(matches!(
+            parse_in_range::<u8>("-1", 0, 10),
+            Err(ParseRangeError::Invalid { .. })
+        ));
+        assert!(matches!(
+            parse_in_range::<i32>("", 0, 10),
+            Err(ParseRangeError::Invalid { .. })
+        ));
+    }
+}
+
+// ==================== 并发模块详细测试 ====================
+
+mod concurrency_tests {
+    use super::*;
+
+    /// 测试现代同步线程
+    #[test]
+    fn test_modern_sync_threads() {
+        concurrency::modern_sync_threads();
+    }
+
+    /// 测试现代消息传递
+    #[test]
+    fn test_modern_message_passing() {
+        concurrency::modern_message_passing();
+    }
+
+    /// 测试现代共享状态
+    #[test]
+    fn test_modern_shared_state() {
+        concurrency::modern_shared_state();
+    }
+
+    /// 测试现代同步
+    #[test]
+    fn test_modern_synchronization() {
+        concurrency::modern_synchronization();
+    }
+
+    /// 测试现代生产者消费者
+    #[test]
+    fn test_modern_producer_consumer() {
+        concurrency::modern_producer_consumer();
+    }
+
+    /// 测试现代工作池
+    #[test]
+    fn test_modern_work_pool() {
+        concurrency::modern_work_pool();
+    }
+
+    /// 测试Web服务器并发处理
+    #[test]
+    fn test_web_server_concurrent_handling() {
+        concurrency::web_server_concurrent_handling();
+    }
+
+    /// 测试数据库连接池
+    #[test]
+    fn test_database_connection_pool() {
+        concurrency::database_connection_pool();
+    }
+
+    /// 测试令牌桶耗尽后会随时间补充
+    #[test]
+    fn test_token_bucket_refills_after_draining() {
+        use std::time::Duration;
+
+        let bucket = concurrency::TokenBucket::new(2, 20.0);
+
+        assert!(bucket.try_acquire(1));
+        assert!(bucket.try_acquire(1));
+        assert!(
+            !bucket.try_acquire(1),
+            "drained bucket should reject further requests"
+        );
+
+        std::thread::sleep(Duration::from_millis(120));
+
+        assert!(
+            bucket.try_acquire(1),
+            "bucket should refill after the refill interval"
+        );
+        assert!(
+            !bucket.try_acquire(5),
+            "requests larger than capacity should never succeed"
+        );
+    }
+
+    /// 测试并行映射与顺序映射结果一致且保序
+    #[test]
+    fn test_par_map_matches_sequential_map_in_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let expected: Vec<u64> = items.iter().map(|x| x * x + 1).collect();
+
+        let actual = concurrency::par_map(items, |x| x * x + 1);
+        assert_eq!(actual, expected);
+
+        let words = vec!["alpha", "beta", "gamma"];
+        assert_eq!(
+            concurrency::par_map(words, str::len),
+            vec![5, 4, 5],
+            "par_map should preserve input order even for tiny inputs"
+        );
+        assert!(concurrency::par_map(Vec::<u8>::new(), |x| x).is_empty());
+    }
+
+    /// 测试有界并行映射不超过并发上限且保持输入顺序
+    #[test]
+    fn test_bounded_par_map_respects_limit_and_order() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let output = concurrency::bounded_par_map((0..20u32).collect(), 3, |x| {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(5));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            x * 2
+        });
+
+        assert_eq!(output, (0..20u32).map(|x| x * 2).collect::<Vec<_>>());
+        let peak = peak.load(Ordering::SeqCst);
+        assert!(peak <= 3, "observed {peak} concurrent calls, limit is 3");
+        assert!(peak >= 1);
+        assert_eq!(
+            concurrency::bounded_par_map(vec![1, 2], 0, |x| x + 1),
+            [2, 3]
+        );
+    }
+
+    /// 测试屏障分阶段计算的结果是确定的
+    #[test]
+    fn test_phased_compute_is_deterministic() {
+        // 阶段 0: 1+2+3+4 = 10；阶段 1: 4*10 + 10 = 50；阶段 2: 4*50 + 10 = 210
+        for _ in 0..5 {
+            assert_eq!(concurrency::phased_compute(4, 3), vec![10, 50, 210]);
+        }
+
+        assert_eq!(concurrency::phased_compute(1, 2), vec![1, 2]);
+        assert_eq!(concurrency::phased_compute(0, 2), vec![0, 0]);
+    }
+
+    /// 测试 Actor 处理来自多个线程的消息
+    #[test]
+    fn test_actor_counts_increments_from_many_threads() {
+        let (actor, actor_thread) = concurrency::Actor::spawn(10);
+
+        let senders: Vec<_> = (0..4)
+            .map(|_| {
+                let actor = actor.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        assert!(actor.increment());
+                    }
+                })
+            })
+            .collect();
+        for sender in senders {
+            sender.join().unwrap();
+        }
+
+        assert_eq!(actor.get(), 110);
+
+        actor.stop();
+        assert_eq!(actor_thread.join().unwrap(), 110);
+        assert!(!actor.increment(), "stopped actor should reject messages");
+    }
+
+    /// 测试 Actor 停止后查询会 panic
+    #[test]
+    #[should_panic(expected = "actor has stopped")]
+    fn test_actor_get_panics_after_stop() {
+        let (actor, actor_thread) = concurrency::Actor::spawn(0);
+        actor.stop();
+        actor_thread.join().unwrap();
+
+        actor.get();
+    }
+
+    /// 测试事件总线向多个订阅者广播并清理已关闭的订阅者
+    #[test]
+    fn test_event_bus_fans_out_and_prunes_dropped_subscribers() {
+        let bus = concurrency::EventBus::new();
+        let first = bus.subscribe();
+        let second = bus.subscribe();
+        let dropped = bus.subscribe();
+        assert_eq!(bus.subscriber_count(), 3);
+
+        drop(dropped);
+        bus.publish(42);
+
+        assert_eq!(first.recv().unwrap(), 42);
+        assert_eq!(second.recv().unwrap(), 42);
+        assert_eq!(bus.subscriber_count(), 2);
+    }
+
+    /// 测试取消父令牌后子令牌也能观察到，并触发取消守卫
+    #[test]
+    fn test_cancellation_token_parent_cancel_seen_by_child() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let parent = concurrency::CancellationToken::new();
+        let child = parent.child();
+        assert!(!child.is_cancelled());
+
+        let observed = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            let worker = scope.spawn(|| {
+                let _guard = child.cancelled_guard(|| observed.store(true, Ordering::SeqCst));
+                while !child.is_cancelled() {
+                    std::thread::yield_now();
+                }
+            });
+
+            parent.cancel();
+            worker.join().unwrap();
+        });
+
+        assert!(child.is_cancelled());
+        assert!(
+            observed.load(Ordering::SeqCst),
+            "guard should run once cancelled"
+        );
+
+        let untouched = AtomicBool::new(false);
+        let idle = concurrency::CancellationToken::new();
+        drop(idle.cancelled_guard(|| untouched.store(true, Ordering::SeqCst)));
+        assert!(
+            !untouched.load(Ordering::SeqCst),
+            "guard must not run without cancellation"
+        );
+    }
+
+    /// 测试 join_timeout 取回快速线程的结果，并对卡住的线程返回超时
+    #[test]
+    fn test_join_timeout_returns_value_or_times_out() {
+        use concurrency::{JoinTimeout, join_timeout};
+        use std::time::{Duration, Instant};
+
+        let quick = std::thread::spawn(|| 6 * 7);
+        assert_eq!(join_timeout(quick, Duration::from_secs(5)), Ok(42));
+
+        let sleepy = std::thread::spawn(|| std::thread::sleep(Duration::from_secs(2)));
+        let started = Instant::now();
+        assert_eq!(
+            join_timeout(sleepy, Duration::from_millis(50)),
+            Err(JoinTimeout::TimedOut)
+        );
+        assert!(
+            started.elapsed() < Duration::from_secs(1),
+            "caller must not wait for the sleeping thread"
+        );
+
+        let panicking = std::thread::spawn(|| -> u32 { panic!("boom") });
+        assert_eq!(
+            join_timeout(panicking, Duration::from_secs(5)),
+            Err(JoinTimeout::Panicked)
+        );
+    }
+
+    /// 测试 ask 把请求交给工作线程并取回计算结果
+    #[test]
+    fn test_ask_returns_worker_response() {
+        use std::sync::mpsc;
+
+        let (tx, rx) = mpsc::channel::<(String, mpsc::Sender<String>)>();
+        let worker = std::thread::spawn(move || {
+            for (request, reply) in rx {
+                let _ = reply.send(format!("echo: {}", request.to_uppercase()));
+            }
+        });
+
+        assert_eq!(
+            concurrency::ask(&tx, "ping".to_string()),
+            Ok("echo: PING".to_string())
+        );
+        assert_eq!(
+            concurrency::ask(&tx, "rust".to_string()),
+            Ok("echo: RUST".to_string())
+        );
+
+        drop(tx);
+        worker.join().unwrap();
+    }
+
+    /// 测试服务线程已退出时 ask 返回错误而不是阻塞
+    #[test]
+    fn test_ask_fails_when_server_is_gone() {
+        use std::sync::mpsc;
+
+        let (tx, rx) = mpsc::channel::<(u32, mpsc::Sender<u32>)>();
+        drop(rx);
+
+        assert_eq!(concurrency::ask(&tx, 1), Err(mpsc::RecvError));
+    }
+
+    /// 测试多线程通过克隆句柄累加同一个计数器
+    #[test]
+    fn test_counter_clones_share_total_across_threads() {
+        let counter = concurrency::Counter::new();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let counter = counter.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1_000 {
+                        counter.inc();
+                    }
+                    counter.add(10);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(counter.get(), 8 * (1_000 + 10));
+    }
+
+    /// 测试懒加载池达到上限后第三个借用者等待归还
+    #[test]
+    fn test_lazy_pool_blocks_third_borrower_until_return() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        let created = Arc::new(AtomicUsize::new(0));
+        let factory_count = Arc::clone(&created);
+        let pool =
+            concurrency::LazyPool::new(2, move || factory_count.fetch_add(1, Ordering::SeqCst));
+        assert_eq!(pool.created(), 0, "instances are created on demand");
+
+        let first = pool.get();
+        let second = pool.get();
+        assert_eq!((*first, *second), (0, 1));
+
+        let third_acquired = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            let waiter = scope.spawn(|| {
+                let guard = pool.get();
+                third_acquired.store(true, Ordering::SeqCst);
+                *guard
+            });
+
+            std::thread::sleep(Duration::from_millis(50));
+            assert!(
+                !third_acquired.load(Ordering::SeqCst),
+                "third borrower should wait"
+            );
+
+            drop(first);
+            assert_eq!(
+                waiter.join().unwrap(),
+                0,
+                "third borrower reuses the returned instance"
+            );
+        });
+
+        drop(second);
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.created(), 2);
+        assert_eq!(pool.idle(), 2);
+    }
+
+    /// 测试容量为 0 的对象池在构造时被拒绝
+    #[test]
+    #[should_panic(expected = "capacity must be at least 1")]
+    fn test_lazy_pool_rejects_zero_capacity() {
+        concurrency::LazyPool::new(0, || 0);
+    }
+
+    /// 测试工厂 panic 不会永久占用名额
+    #[test]
+    fn test_lazy_pool_releases_slot_when_factory_panics() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let factory_calls = Arc::clone(&calls);
+        let pool = concurrency::LazyPool::new(1, move || {
+            if factory_calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                panic!("first connection attempt fails");
+            }
+            42
+        });
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            pool.get();
+        }));
+        assert!(result.is_err());
+        assert_eq!(
+            pool.created(),
+            0,
+            "the failed creation should free its slot"
+        );
+
+        assert_eq!(
+            *pool.get(),
+            42,
+            "the next borrower should be able to create"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
+
+// ==================== 宏模块详细测试 ====================
+
+mod macros_tests {
+    use super::*;
+
+    /// 测试 HttpMethod 不区分大小写解析
+    #[test]
+    fn test_http_method_parse_case_insensitive() {
+        use macros::HttpMethod;
+
+        assert_eq!("get".parse::<HttpMethod>(), Ok(HttpMethod::GET));
+        assert_eq!("POST".parse::<HttpMethod>(), Ok(HttpMethod::POST));
+        assert_eq!("Patch".parse::<HttpMethod>(), Ok(HttpMethod::PATCH));
+        assert!("FOO".parse::<HttpMethod>().is_err());
+    }
+
+    /// 测试 HttpMethod 经 Display 输出后可以解析回原值
+    #[test]
+    fn test_http_method_display_round_trip() {
+        use macros::HttpMethod;
+
+        let methods = [
+            HttpMethod::GET,
+            HttpMethod::POST,
+            HttpMethod::PUT,
+            HttpMethod::PATCH,
+            HttpMethod::DELETE,
+            HttpMethod::HEAD,
+        ];
+        for method in methods {
+            assert_eq!(method.to_string().parse::<HttpMethod>(), Ok(method));
+        }
+        assert_eq!(HttpMethod::HEAD.to_string(), "HEAD");
+    }
+
+    /// 测试 Router 捕获 `:param` 段和结尾 `*` 通配段
+    #[test]
+    fn test_router_resolves_params_and_wildcard_tail() {
+        use macros::{HttpMethod, Router};
+
+        let mut router = Router::new();
+        router
+            .route(HttpMethod::GET, "/users/:id", "get_user")
+            .route(HttpMethod::GET, "/files/*", "serve_file");
+
+        let user = router.resolve(HttpMethod::GET, "/users/42").unwrap();
+        assert_eq!(user.handler, "get_user");
+        assert_eq!(user.params.get("id").map(String::as_str), Some("42"));
+        assert_eq!(user.tail(), None);
+
+        let file = router.resolve(HttpMethod::GET, "/files/a/b/c").unwrap();
+        assert_eq!(file.handler, "serve_file");
+        assert_eq!(file.tail(), Some("a/b/c"));
+
+        assert!(router.resolve(HttpMethod::GET, "/users/42/posts").is_none());
+        assert!(router.resolve(HttpMethod::POST, "/users/42").is_none());
+        assert!(router.resolve(HttpMethod::GET, "/other/a").is_none());
+    }
+
+    /// 测试 Point 经 serde 往返后保持相等
+    #[test]
+    fn test_point_serde_round_trip() {
+        let point = macros::Point { x: 1.5, y: -2.0 };
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(json, r#"{"x":1.5,"y":-2.0}"#);
+        assert_eq!(serde_json::from_str::<macros::Point>(&json).unwrap(), point);
+    }
+
+    /// 测试从元组构造 Point
+    #[test]
+    fn test_point_from_tuple() {
+        assert_eq!(
+            macros::Point::from((1.0, 2.0)),
+            macros::Point { x: 1.0, y: 2.0 }
+        );
+        let point: macros::Point = (3.0, 4.0).into();
+        assert_eq!(point.distance(&macros::Point::from((0.0, 0.0))), 5.0);
+    }
+
+    rust_learn::accessors! {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Config {
+            name: String => (get_name, set_name),
+            retries: u32 => (get_retries, set_retries),
+        }
+    }
+
+    /// 测试 accessors! 生成的构造函数与 get_/set_ 方法
+    #[test]
+    fn test_accessors_macro_generates_getters_and_setters() {
+        let mut config = Config::new("primary".to_string(), 3);
+        assert_eq!(config.get_name(), "primary");
+        assert_eq!(*config.get_retries(), 3);
+
+        config.set_name("fallback".to_string());
+        config.set_retries(5);
+        assert_eq!(config, Config::new("fallback".to_string(), 5));
+    }
+
+    #[derive(Debug)]
+    enum Shape {
+        Circle(f64),
+        Rect { width: f64, height: f64 },
+    }
+
+    /// 测试 match_variant! 在匹配时返回绑定值
+    #[test]
+    fn test_match_variant_extracts_payload_from_matching_variant() {
+        let circle = Shape::Circle(2.0);
+        assert_eq!(
+            rust_learn::match_variant!(circle, Shape::Circle(radius) => radius),
+            Some(2.0)
+        );
+
+        let rect = Shape::Rect {
+            width: 2.0,
+            height: 3.0,
+        };
+        assert_eq!(
+            rust_learn::match_variant!(rect, Shape::Rect { width, height } => width * height),
+            Some(6.0)
+        );
+    }
+
+    /// 测试 match_variant! 在不匹配或守卫失败时返回 None
+    #[test]
+    fn test_match_variant_returns_none_for_non_matching_variant() {
+        let rect = Shape::Rect {
+            width: 1.0,
+            height: 1.0,
+        };
+        assert_eq!(
+            rust_learn::match_variant!(rect, Shape::Circle(radius) => radius),
+            None
+        );
+
+        let small = Shape::Circle(0.5);
+        assert_eq!(
+            rust_learn::match_variant!(small, Shape::Circle(r) if r > 1.0 => r),
+            None
+        );
+    }
+}
+
+// ==================== 安全模块详细测试 ====================
+
+mod security_tests {
+    use super::*;
+
+    /// 测试安全随机数生成
+    #[test]
+    fn test_secure_random_generation() {
+        security::secure_random_generation();
+    }
+
+    /// 测试密码学哈希函数
+    #[test]
+    fn test_cryptography_hash_functions() {
+        security::cryptography_hash_functions();
+    }
+
+    /// 测试HMAC消息认证
+    #[test]
+    fn test_hmac_message_authentication() {
+        security::hmac_message_authentication();
+    }
+
+    /// 测试Base64编码解码
+    #[test]
+    fn test_base64_encoding_decoding() {
+        security::base64_encoding_decoding();
+    }
+
+    /// 测试输入验证和清理
+    #[test]
+    fn test_input_validation_sanitization() {
+        security::input_validation_sanitization();
+    }
+
+    /// 测试安全密码存储
+    #[test]
+    fn test_secure_password_storage() {
+        security::secure_password_storage();
+    }
+
+    /// 测试安全随机字符串
+    #[test]
+    fn test_secure_random_strings() {
+        security::secure_random_strings();
+    }
+
+    /// 测试内存安全保证
+    #[test]
+    fn test_memory_safety_guarantees() {
+        security::memory_safety_guarantees();
+    }
+
+    /// 测试常量时间比较
+    #[test]
+    fn test_constant_time_comparison() {
+        security::constant_time_comparison();
+    }
+
+    /// 测试组合验证器会收集所有失败规则
+    #[test]
+    fn test_validator_collects_all_rule_failures() {
+        let validator = security::Validator::<str>::new()
+            .rule(|name| {
+                if name.len() >= 3 {
+                    Ok(())
+                } else {
+                    Err("too short".to_string())
+                }
+            })
+            .rule(|name| {
+                if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    Ok(())
+                } else {
+                    Err("invalid characters".to_string())
+                }
+            })
+            .rule(|name| {
+                if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    Err("must not start with a digit".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+
+        assert_eq!(validator.rule_count(), 3);
+        assert!(validator.validate("valid_user").is_ok());
+
+        let errors = validator
+            .validate("1@")
+            .expect_err("bad username should fail several rules");
+        assert_eq!(
+            errors,
+            vec![
+                "too short".to_string(),
+                "invalid characters".to_string(),
+                "must not start with a digit".to_string(),
+            ]
+        );
+    }
+
+    /// 测试十六进制解码的成功与失败路径
+    #[test]
+    fn test_from_hex_decodes_and_reports_errors() {
+        assert_eq!(security::from_hex("00ff10"), Ok(vec![0x00, 0xff, 0x10]));
+        assert_eq!(
+            security::from_hex("DEADbeef"),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+
+        let odd = security::from_hex("abc").expect_err("odd-length input should be rejected");
+        assert!(
+            odd.contains("偶数"),
+            "odd-length error should explain the cause: {odd}"
+        );
+
+        let invalid = security::from_hex("zz").expect_err("non-hex input should be rejected");
+        assert!(
+            invalid.contains("'z'"),
+            "invalid-char error should name the character: {invalid}"
+        );
+    }
+
+    /// 测试 Base64 编解码往返
+    #[test]
+    fn test_base64_round_trip() {
+        use security::Base64Variant;
+
+        for variant in [Base64Variant::Standard, Base64Variant::UrlSafe] {
+            let encoded = security::base64_encode(b"\xfb\xff token", variant);
+            assert_eq!(
+                security::base64_decode(&encoded, variant).unwrap(),
+                b"\xfb\xff token"
+            );
+        }
+    }
+
+    /// 测试非法填充返回带类型的错误
+    #[test]
+    fn test_base64_decode_invalid_padding() {
+        let error = security::base64_decode("QQ", security::Base64Variant::Standard)
+            .expect_err("missing padding should be rejected");
+        assert_eq!(error.input_len(), 2);
+        assert_eq!(error.decode_error(), &base64::DecodeError::InvalidPadding);
+    }
+
+    /// 测试非法字符返回带类型的错误
+    #[test]
+    fn test_base64_decode_invalid_character() {
+        let error = security::base64_decode("Zm9v!A==", security::Base64Variant::Standard)
+            .expect_err("invalid character should be rejected");
+        assert_eq!(error.input_len(), 8);
+        assert_eq!(
+            error.decode_error(),
+            &base64::DecodeError::InvalidByte(4, b'!')
+        );
+    }
+
+    /// 测试令牌匹配
+    #[test]
+    fn test_verify_token_matching() {
+        let token = security::base64_encode(b"api-token-123", security::Base64Variant::UrlSafe);
+        assert!(security::verify_token(&token, &token));
+    }
+
+    /// 测试等长但内容不同的令牌不匹配
+    #[test]
+    fn test_verify_token_mismatch_same_length() {
+        let expected = security::base64_encode(b"api-token-123", security::Base64Variant::UrlSafe);
+        let presented = security::base64_encode(b"api-token-124", security::Base64Variant::UrlSafe);
+        assert_eq!(presented.len(), expected.len());
+        assert!(!security::verify_token(&presented, &expected));
+    }
+
+    /// 测试畸形 Base64 输入被拒绝
+    #[test]
+    fn test_verify_token_malformed_input() {
+        let expected = security::base64_encode(b"api-token-123", security::Base64Variant::UrlSafe);
+        assert!(!security::verify_token("not base64!", &expected));
+        assert!(!security::verify_token(&expected, "***"));
+    }
+
+    /// 测试百分号编码往返，覆盖空格、保留字符与多字节 UTF-8
+    #[test]
+    fn test_percent_encode_round_trip() {
+        let cases = [
+            ("hello world", "hello%20world"),
+            ("user@example.com", "user%40example.com"),
+            ("p:ss/w@rd?", "p%3Ass%2Fw%40rd%3F"),
+            ("密码", "%E5%AF%86%E7%A0%81"),
+            ("safe-._~AZaz09", "safe-._~AZaz09"),
+        ];
+
+        for (raw, encoded) in cases {
+            assert_eq!(security::percent_encode(raw), encoded);
+            assert_eq!(security::percent_decode(encoded).unwrap(), raw);
+        }
+    }
+
+    /// 测试百分号解码拒绝不完整、非法十六进制与非 UTF-8 的输入
+    #[test]
+    fn test_percent_decode_rejects_malformed_input() {
+        assert!(security::percent_decode("abc%2").is_err());
+        assert!(security::percent_decode("abc%zz").is_err());
+        assert!(security::percent_decode("%+1").is_err());
+        assert!(security::percent_decode("%FF").is_err());
+    }
+
+    /// 测试多种位移下 rot_n 编码后可还原，且保留大小写与非字母字符
+    #[test]
+    fn test_rot_n_round_trip_for_several_shifts() {
+        let text = "Hello, World! Rust 2024 — 你好 xyz";
+
+        for n in [0, 1, 3, 13, 25, 26, 40, 255] {
+            let encoded = security::rot_n(text, n);
+            assert_eq!(security::rot_n_decode(&encoded, n), text, "shift {n}");
+        }
+
+        assert_eq!(security::rot_n("abc XYZ!", 3), "def ABC!");
+        assert_eq!(security::rot_n("abc", 26), "abc");
+        assert_eq!(security::rot_n("abc", 29), security::rot_n("abc", 3));
+    }
+
+    /// 测试 ROT13 对自身对称
+    #[test]
+    fn test_rot13_is_symmetric() {
+        let text = "Why did the chicken cross the road?";
+        let once = security::rot_n(text, 13);
+
+        assert_eq!(once, "Jul qvq gur puvpxra pebff gur ebnq?");
+        assert_eq!(security::rot_n(&once, 13), text);
+        assert_eq!(security::rot_n_decode(text, 13), once);
+    }
+
+    /// 测试维吉尼亚密码的经典已知答案
+    #[test]
+    fn test_vigenere_known_answer() {
+        assert_eq!(
+            security::vigenere_encrypt("ATTACKATDAWN", "LEMON").unwrap(),
+            "LXFOPVEFRNHR"
+        );
+        assert_eq!(
+            security::vigenere_encrypt("Attack at dawn!", "lemon").unwrap(),
+            "Lxfopv ef rnhr!"
+        );
+    }
+
+    /// 测试维吉尼亚密码往返还原，并拒绝空密钥与非字母密钥
+    #[test]
+    fn test_vigenere_round_trip_and_key_validation() {
+        let text = "Meet me near the old oak tree at 10pm, 好吗?";
+
+        for key in ["KEY", "rust", "a", "Zebra"] {
+            let encrypted = security::vigenere_encrypt(text, key).unwrap();
+            assert_eq!(security::vigenere_decrypt(&encrypted, key).unwrap(), text);
+        }
+        assert_eq!(security::vigenere_encrypt(text, "a").unwrap(), text);
+
+        assert!(security::vigenere_encrypt(text, "").is_err());
+        assert!(security::vigenere_encrypt(text, "key1").is_err());
+        assert!(security::vigenere_decrypt(text, "密钥").is_err());
+    }
+
+    /// 测试已规范的邮箱直接借用原字符串
+    #[test]
+    fn test_normalize_email_borrows_when_already_normalized() {
+        use std::borrow::Cow;
+
+        let input = "user@example.com";
+        let normalized = security::normalize_email(input);
+        assert!(matches!(normalized, Cow::Borrowed(_)));
+        assert_eq!(normalized, "user@example.com");
+
+        // 只需去掉首尾空白时仍是原字符串的切片
+        assert!(matches!(
+            security::normalize_email("  user@example.com\n"),
+            Cow::Borrowed("user@example.com")
+        ));
+    }
+
+    /// 测试含大写字符的邮箱被转换为新分配的小写字符串
+    #[test]
+    fn test_normalize_email_owns_when_lowercasing() {
+        use std::borrow::Cow;
+
+        let normalized = security::normalize_email(" User@Example.COM ");
+        assert!(matches!(normalized, Cow::Owned(_)));
+        assert_eq!(normalized, "user@example.com");
+        assert_eq!(
+            security::validate_email(" User@Example.COM ").unwrap(),
+            "user@example.com"
+        );
+    }
+
+    /// 测试模幂运算的已知结果与大数不溢出
+    #[test]
+    fn test_mod_pow_known_values() {
+        assert_eq!(security::mod_pow(2, 10, 1000), 24);
+        assert_eq!(security::mod_pow(5, 6, 23), 8);
+        assert_eq!(security::mod_pow(7, 0, 13), 1);
+        assert_eq!(security::mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        // 费马小定理：a^(p-1) ≡ 1 (mod p)
+        let prime = 1_000_000_007;
+        assert_eq!(security::mod_pow(123_456_789, prime - 1, prime), 1);
+    }
+
+    /// 测试模数为 1 时结果恒为 0
+    #[test]
+    fn test_mod_pow_modulus_one_is_zero() {
+        assert_eq!(security::mod_pow(5, 0, 1), 0);
+        assert_eq!(security::mod_pow(u64::MAX, u64::MAX, 1), 0);
+    }
+
+    /// 测试非默认迭代次数的哈希可以验证，且次数写入编码结果
+    #[test]
+    fn test_hash_password_with_custom_rounds_verifies() {
+        let stored = security::hash_password_with("correct horse", 1_000).unwrap();
+
+        assert!(stored.starts_with("1000$"));
+        assert_eq!(stored.split('$').count(), 3);
+        assert!(security::verify_password("correct horse", &stored));
+        assert!(!security::verify_password("wrong horse", &stored));
+        assert_ne!(
+            stored,
+            security::hash_password_with("correct horse", 1_000).unwrap(),
+            "each hash should use a fresh salt"
+        );
+        assert!(security::hash_password_with("correct horse", 0).is_err());
+    }
+
+    /// 测试篡改迭代次数字段后验证失败
+    #[test]
+    fn test_verify_password_rejects_tampered_rounds() {
+        let stored = security::hash_password_with("secret", 1_000).unwrap();
+        let (_, rest) = stored.split_once('$').unwrap();
+
+        for rounds in ["999", "0", "abc", "99999999999"] {
+            let tampered = format!("{}${}", rounds, rest);
+            assert!(
+                !security::verify_password("secret", &tampered),
+                "rounds {rounds}"
+            );
+        }
+        assert!(!security::verify_password("secret", rest));
+    }
+}
+
+// ==================== 数据库模块详细测试 ====================
+
+mod database_tests {
+    use super::*;
+    use database::{MemoryDb, MemoryDbError};
+
+    /// 测试内存数据库的创建、查询与删除
+    #[tokio::test]
+    async fn test_memory_db_create_get_delete() {
+        let db = MemoryDb::new();
+        let alice = db.create_user("alice", "alice@example.com").await.unwrap();
+        let bob = db.create_user("bob", "bob@example.com").await.unwrap();
+        assert_ne!(alice.id, bob.id);
+
+        let fetched = db.get_user(alice.id).await.unwrap().expect("alice exists");
+        assert_eq!(fetched.username, "alice");
+        assert!(fetched.is_active);
+        assert_eq!(db.get_all_users().await.unwrap().len(), 2);
+
+        assert!(db.delete_user(alice.id).await.unwrap());
+        assert!(!db.delete_user(alice.id).await.unwrap());
+        assert!(db.get_user(alice.id).await.unwrap().is_none());
+    }
+
+    /// 测试重复用户名被拒绝
+    #[tokio::test]
+    async fn test_memory_db_rejects_duplicate_username() {
+        let db = MemoryDb::new();
+        db.create_user("alice", "alice@example.com").await.unwrap();
+
+        let error = db
+            .create_user("alice", "other@example.com")
+            .await
+            .expect_err("duplicate username should be rejected");
+        assert!(
+            matches!(error, MemoryDbError::Validation(message) if message.contains("用户名已存在"))
+        );
+        assert_eq!(db.get_all_users().await.unwrap().len(), 1);
+    }
+
+    /// 测试分页边界
+    #[tokio::test]
+    async fn test_memory_db_list_users_paging_boundaries() {
+        let db = MemoryDb::new();
+        for i in 0..5 {
+            db.create_user(&format!("user{i}"), &format!("user{i}@example.com"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(db.count_users().await, 5);
+
+        let page = |users: Vec<database::User>| -> Vec<String> {
+            users.into_iter().map(|user| user.username).collect()
+        };
+        assert_eq!(page(db.list_users(0, 2, false).await), ["user0", "user1"]);
+        assert_eq!(page(db.list_users(4, 2, false).await), ["user4"]);
+        assert!(db.list_users(5, 2, false).await.is_empty());
+        assert!(db.list_users(0, 0, false).await.is_empty());
+    }
+
+    /// 测试只返回激活用户
+    #[tokio::test]
+    async fn test_memory_db_list_users_active_only() {
+        let db = MemoryDb::new();
+        let alice = db.create_user("alice", "alice@example.com").await.unwrap();
+        let bob = db.create_user("bob", "bob@example.com").await.unwrap();
+        assert!(db.set_active(alice.id, false).await.unwrap());
+
+        let active = db.list_users(0, 10, true).await;
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, bob.id);
+        assert_eq!(db.list_users(0, 10, false).await.len(), 2);
+    }
+
+    /// 测试批量创建中途失败时整体回滚
+    #[tokio::test]
+    async fn test_memory_db_bulk_create_atomic_rolls_back() {
+        let db = MemoryDb::new();
+        let batch = vec![
+            ("alice".to_string(), "alice@example.com".to_string()),
+            ("bob".to_string(), "bob@example.com".to_string()),
+            ("alice".to_string(), "alice2@example.com".to_string()),
+        ];
+
+        let error = db
+            .bulk_create_atomic(&batch)
+            .await
+            .expect_err("duplicate username inside the batch should fail");
+        assert!(matches!(error, MemoryDbError::Validation(_)));
+        assert_eq!(db.count_users().await, 0);
+
+        let committed = db.bulk_create_atomic(&batch[..2]).await.unwrap();
+        assert_eq!(committed.len(), 2);
+        assert_eq!(committed[0].id, 1);
+        assert_eq!(db.count_users().await, 2);
+    }
+}
+
+// ==================== 热门库详细测试 ====================
+
+mod popular_libraries_tests {
+    use super::*;
+    use popular_libraries::{LibraryError, User};
+
+    /// 测试构建器生成有效用户
+    #[test]
+    fn test_user_builder_valid() {
+        let user = User::builder()
+            .id(7)
+            .name("王五")
+            .email("  WangWu@Example.com ")
+            .theme("dark")
+            .language("en-US")
+            .notifications(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(user.id, 7);
+        assert_eq!(user.name, "王五");
+        assert_eq!(user.email, "wangwu@example.com");
+        assert_eq!(user.preferences.theme, "dark");
+        assert_eq!(user.preferences.language, "en-US");
+        assert!(!user.preferences.notifications);
+    }
+
+    /// 测试无效邮箱被拒绝
+    #[test]
+    fn test_user_builder_rejects_invalid_email() {
+        let error = User::builder()
+            .name("无效用户")
+            .email("not-an-email")
+            .build()
+            .expect_err("invalid email should be rejected");
+        assert!(matches!(error, LibraryError::InvalidUser(_)));
+    }
+
+    /// 测试 init_tracing_once 可重复调用而不 panic
+    #[test]
+    fn test_init_tracing_once_is_idempotent() {
+        popular_libraries::init_tracing_once();
+        popular_libraries::init_tracing_once();
+        tracing::info!("tracing 已初始化");
+    }
+
+    /// 测试通过构建器构造 CliArgs 并离线执行序列化演示
+    #[tokio::test]
+    async fn test_cli_args_builder_drives_serialize_demo() {
+        use popular_libraries::{CliArgs, Operation};
+
+        let args = CliArgs::builder()
+            .operation(Operation::Serialize)
+            .verbose(false)
+            .url("https://example.com")
+            .build();
+        assert!(matches!(args.operation, Operation::Serialize));
+        assert_eq!(args.url.as_deref(), Some("https://example.com"));
+
+        popular_libraries::run_popular_libraries_demo(&args)
+            .await
+            .unwrap();
+    }
+
+    /// 测试 Serialize 分支返回包含用户姓名的 JSON
+    #[tokio::test]
+    async fn test_serialize_operation_returns_user_json() {
+        use popular_libraries::{CliArgs, Operation, OperationOutput};
+
+        let args = CliArgs::builder().operation(Operation::Serialize).build();
+        let output = popular_libraries::run_popular_libraries_demo(&args)
+            .await
+            .unwrap();
+
+        let OperationOutput::Serialized(json) = output else {
+            panic!("Serialize should produce OperationOutput::Serialized, got {output:?}");
+        };
+        assert!(json.contains("张三"), "{json}");
+        let user: User = serde_json::from_str(&json).unwrap();
+        assert_eq!(user.name, "张三");
+    }
+
+    /// 测试 Deserialize 与 Cli 分支返回对应的结构化结果
+    #[tokio::test]
+    async fn test_deserialize_and_cli_operations_return_structured_output() {
+        use popular_libraries::{CliArgs, Operation, OperationOutput};
+
+        let args = CliArgs::builder().operation(Operation::Deserialize).build();
+        match popular_libraries::run_popular_libraries_demo(&args)
+            .await
+            .unwrap()
+        {
+            OperationOutput::Deserialized(user) => assert_eq!(user.id, 42),
+            other => panic!("unexpected output: {other:?}"),
+        }
+
+        let args = CliArgs::builder().operation(Operation::Cli).build();
+        match popular_libraries::run_popular_libraries_demo(&args)
+            .await
+            .unwrap()
+        {
+            OperationOutput::CliParsed(operation) => {
+                assert!(matches!(operation, Operation::Cli))
+            }
+            other => panic!("unexpected output: {other:?}"),
+        }
+    }
+
+    /// 测试新增键被报告为 Added
+    #[test]
+    fn test_json_diff_reports_added_key() {
+        use popular_libraries::JsonDiff;
+
+        let a = serde_json::json!({ "id": 1 });
+        let b = serde_json::json!({ "id": 1, "name": "张三" });
+
+        assert_eq!(
+            popular_libraries::json_diff(&a, &b),
+            vec![JsonDiff::Added {
+                pointer: "/name".to_string(),
+                value: serde_json::json!("张三"),
+            }]
+        );
+    }
+
+    /// 测试删除的键被报告为 Removed
+    #[test]
+    fn test_json_diff_reports_removed_key() {
+        use popular_libraries::JsonDiff;
+
+        let a = serde_json::json!({ "id": 1, "a/b": true });
+        let b = serde_json::json!({ "id": 1 });
+
+        assert_eq!(
+            popular_libraries::json_diff(&a, &b),
+            vec![JsonDiff::Removed {
+                pointer: "/a~1b".to_string(),
+                value: serde_json::json!(true),
+            }]
+        );
+        assert!(popular_libraries::json_diff(&a, &a).is_empty());
+    }
+
+    /// 测试标量变化被报告为 Changed
+    #[test]
+    fn test_json_diff_reports_changed_scalar() {
+        use popular_libraries::JsonDiff;
+
+        let a = serde_json::json!({ "age": 28, "active": true });
+        let b = serde_json::json!({ "age": 29, "active": true });
+
+        assert_eq!(
+            popular_libraries::json_diff(&a, &b),
+            vec![JsonDiff::Changed {
+                pointer: "/age".to_string(),
+                from: serde_json::json!(28),
+                to: serde_json::json!(29),
+            }]
+        );
+        assert_eq!(
+            popular_libraries::json_diff(&serde_json::json!(1), &serde_json::json!("1")),
+            vec![JsonDiff::Changed {
+                pointer: String::new(),
+                from: serde_json::json!(1),
+                to: serde_json::json!("1"),
+            }]
+        );
+    }
+
+    /// 测试嵌套对象与数组下标的差异路径
+    #[test]
+    fn test_json_diff_reports_nested_changes() {
+        use popular_libraries::JsonDiff;
+
+        let a = serde_json::json!({
+            "users": [
+                { "name": "张三", "skills": ["Rust"] },
+                { "name": "李四", "skills": [] }
+            ]
+        });
+        let b = serde_json::json!({
+            "users": [
+                { "name": "张三", "skills": ["Rust", "Go"] },
+                { "name": "王五", "skills": [] }
+            ]
+        });
+
+        let diffs = popular_libraries::json_diff(&a, &b);
+        assert_eq!(
+            diffs,
+            vec![
+                JsonDiff::Added {
+                    pointer: "/users/0/skills/1".to_string(),
+                    value: serde_json::json!("Go"),
+                },
+                JsonDiff::Changed {
+                    pointer: "/users/1/name".to_string(),
+                    from: serde_json::json!("李四"),
+                    to: serde_json::json!("王五"),
+                },
+            ]
+        );
+        assert_eq!(
+            popular_libraries::json_get_str(&b, "/users/1/name"),
+            Some("王五")
+        );
+    }
+
+    /// 测试按路径读取嵌套值，缺失路径返回 None
+    #[test]
+    fn test_json_get_nested_and_missing_paths() {
+        let value = serde_json::json!({
+            "team": {
+                "members": [
+                    { "name": "张三", "age": 28 },
+                    { "name": "李四", "age": 32 }
+                ]
+            }
+        });
+
+        assert_eq!(
+            popular_libraries::json_get_str(&value, "/team/members/1/name"),
+            Some("李四")
+        );
+        assert_eq!(
+            popular_libraries::json_get_str(&value, "team/members/0/name"),
+            Some("张三")
+        );
+        assert_eq!(
+            popular_libraries::json_get_u64(&value, "team/members/0/age"),
+            Some(28)
+        );
+        assert_eq!(popular_libraries::json_get(&value, ""), Some(&value));
+
+        assert!(popular_libraries::json_get(&value, "team/leader").is_none());
+        assert!(popular_libraries::json_get_str(&value, "team/members/5/name").is_none());
+        assert!(popular_libraries::json_get_u64(&value, "team/members/0/name").is_none());
+    }
+}
+
+#[cfg(feature = "http")]
+mod popular_libraries_http_tests {
+    use super::*;
+    use best_practices::{Backoff, RetryPolicy};
+    use popular_libraries::LibraryError;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 启动本地 HTTP 桩服务：第 n 个请求（从 1 开始）的状态码与响应体由 `respond` 决定
+    async fn spawn_http_stub<F>(respond: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(usize) -> (u16, String) + Send + Sync + 'static,
+    {
+        spawn_raw_http_stub(move |attempt| {
+            let (status, body) = respond(attempt);
+            format!(
+                "HTTP/1.1 {status} STUB\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+        })
+        .await
+    }
+
+    /// 与 `spawn_http_stub` 相同，但由 `respond` 直接给出完整的原始响应报文
+    async fn spawn_raw_http_stub<F>(respond: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(usize) -> String + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match socket.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+
+                let response = respond(counter.fetch_add(1, Ordering::SeqCst) + 1);
+                let _ = socket.write_all(response.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        (url, hits)
+    }
+
+    fn fast_retry_policy() -> RetryPolicy {
+        RetryPolicy::new(3, Duration::from_millis(1), Backoff::Fixed)
+    }
+
+    /// 测试 get_json_retry 在两次 503 后第三次成功
+    #[tokio::test]
+    async fn test_get_json_retry_recovers_from_server_errors() {
+        let (url, hits) = spawn_http_stub(|attempt| {
+            if attempt <= 2 {
+                (503, r#"{"error":"unavailable"}"#.to_string())
+            } else {
+                (200, r#"{"value":42}"#.to_string())
+            }
+        })
+        .await;
+
+        let client = reqwest::Client::new();
+        let body: serde_json::Value =
+            popular_libraries::get_json_retry(&client, &url, fast_retry_policy())
+                .await
+                .unwrap();
+
+        assert_eq!(body["value"], 42);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    /// 测试 get_json_retry 遇到 4xx 时不重试
+    #[tokio::test]
+    async fn test_get_json_retry_does_not_retry_client_errors() {
+        let (url, hits) = spawn_http_stub(|_| (404, "{}".to_string())).await;
+
+        let client = reqwest::Client::new();
+        let error = popular_libraries::get_json_retry::<serde_json::Value>(
+            &client,
+            &url,
+            fast_retry_policy(),
+        )
+        .await
+        .expect_err("404 should not be retried into success");
+
+        assert!(matches!(error, LibraryError::HttpStatus(404)));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    /// 测试 get_text_limited 在声明的 Content-Length 超限时直接报错
+    #[tokio::test]
+    async fn test_get_text_limited_rejects_declared_large_body() {
+        let (url, _) = spawn_http_stub(|_| (200, "x".repeat(64 * 1024))).await;
+
+        let client = reqwest::Client::new();
+        let error = popular_libraries::get_text_limited(&client, &url, 1024)
+            .await
+            .expect_err("body above the limit should be rejected");
+
+        assert!(matches!(
+            error,
+            LibraryError::ResponseTooLarge { limit: 1024 }
+        ));
+    }
+
+    /// 测试 get_text_limited 在未声明长度的流式响应超限时中止读取
+    #[tokio::test]
+    async fn test_get_text_limited_stops_streaming_large_body() {
+        let (url, _) = spawn_raw_http_stub(|_| {
+            format!(
+                "HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{}",
+                "y".repeat(256 * 1024)
+            )
+        })
+        .await;
+
+        let client = reqwest::Client::new();
+        let error = popular_libraries::get_text_limited(&client, &url, 4096)
+            .await
+            .expect_err("streamed body above the limit should be rejected");
+
+        assert!(matches!(
+            error,
+            LibraryError::ResponseTooLarge { limit: 4096 }
+        ));
+    }
+
+    /// 测试 get_text_limited 正常返回未超限的响应体
+    #[tokio::test]
+    async fn test_get_text_limited_returns_small_body() {
+        let (url, _) = spawn_http_stub(|_| (200, "hello".to_string())).await;
+
+        let client = reqwest::Client::new();
+        let text = popular_libraries::get_text_limited(&client, &url, 5)
+            .await
+            .unwrap();
+
+        assert_eq!(text, "hello");
+    }
+}
+
+// ==================== 最佳实践详细测试 ====================
+
+mod best_practices_tests {
+    use super::*;
+
+    /// 测试现代错误处理最佳实践
     #[test]
-    fn test_structs() {
-        types::structs();
+    fn test_modern_error_handling_best_practices() {
+        best_practices::modern_error_handling_best_practices();
     }
 
-    /// 测试枚举
+    /// 测试资源管理最佳实践
     #[test]
-    fn test_enums() {
-        types::enums();
+    fn test_resource_management_best_practices() {
+        best_practices::resource_management_best_practices();
     }
 
-    /// 测试特征
+    /// 测试性能优化最佳实践
     #[test]
-    fn test_traits() {
-        types::traits();
+    fn test_performance_optimization_best_practices() {
+        best_practices::performance_optimization_best_practices();
     }
 
-    /// 测试泛型
+    /// 测试API设计最佳实践
     #[test]
-    fn test_generics() {
-        types::generics();
+    fn test_api_design_best_practices() {
+        best_practices::api_design_best_practices();
     }
 
-    /// 测试高级特征对象
+    /// 测试测试最佳实践
     #[test]
-    fn test_advanced_trait_objects() {
-        types::advanced_trait_objects();
+    fn test_testing_best_practices() {
+        best_practices::testing_best_practices();
     }
 
-    /// 测试关联类型
+    /// 测试文档最佳实践
     #[test]
-    fn test_associated_types() {
-        types::associated_types();
+    fn test_documentation_best_practices() {
+        best_practices::documentation_best_practices();
     }
 
-    /// 测试NewType模式
+    /// 测试 read_json 解析有效文件
     #[test]
-    fn test_newtype_pattern() {
-        types::newtype_pattern();
-    }
+    fn test_read_json_parses_valid_file() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Profile {
+            name: String,
+            age: u32,
+        }
 
-    /// 测试高级特征约束
-    #[test]
-    fn test_advanced_trait_bounds() {
-        types::advanced_trait_bounds();
-    }
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile.json");
+        std::fs::write(&path, r#"{"name": "张三", "age": 25}"#).unwrap();
 
-    /// 测试类型级编程
-    #[test]
-    fn test_type_level_programming() {
-        types::type_level_programming();
+        let profile: Profile = best_practices::read_json(&path).unwrap();
+        assert_eq!(
+            profile,
+            Profile {
+                name: "张三".to_string(),
+                age: 25,
+            }
+        );
     }
 
-    /// 测试组合模式
+    /// 测试 read_json 的错误信息包含缺失路径
     #[test]
-    fn test_composition_patterns() {
-        types::composition_patterns();
+    fn test_read_json_error_names_missing_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("does_not_exist.json");
+
+        let error = best_practices::read_json::<serde_json::Value>(&path)
+            .expect_err("missing file should fail");
+        let message = format!("{error:#}");
+        assert!(
+            message.contains(&path.display().to_string()),
+            "error should name the missing path, got: {message}"
+        );
     }
 
-    /// 测试高级类型模式
+    /// 测试固定与指数退避的延迟序列
     #[test]
-    fn test_advanced_type_patterns() {
-        types::advanced_type_patterns();
+    fn test_retry_policy_fixed_and_exponential_delays() {
+        use best_practices::{Backoff, RetryPolicy};
+        use std::time::Duration;
+
+        let base = Duration::from_millis(100);
+        let fixed = RetryPolicy::new(5, base, Backoff::Fixed);
+        let fixed_delays: Vec<Duration> = (1..=4).map(|attempt| fixed.delay_for(attempt)).collect();
+        assert_eq!(fixed_delays, vec![base; 4]);
+
+        let exponential = RetryPolicy::new(5, base, Backoff::Exponential);
+        let exponential_delays: Vec<Duration> = (1..=4)
+            .map(|attempt| exponential.delay_for(attempt))
+            .collect();
+        assert_eq!(
+            exponential_delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(400),
+                Duration::from_millis(800),
+            ]
+        );
+        assert_eq!(exponential.delay_for(200), Duration::MAX);
     }
 
-    /// 测试类型安全API模式
+    /// 测试抖动退避在注入的随机数生成器下可复现且不超过指数上限
     #[test]
-    fn test_type_safe_api_patterns() {
-        types::type_safe_api_patterns();
+    fn test_retry_policy_jitter_with_injected_rng() {
+        use best_practices::{Backoff, RetryPolicy};
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        use std::time::Duration;
+
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Backoff::ExponentialJitter);
+        let sequence = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (1..=5)
+                .map(|attempt| policy.delay_for_with_rng(attempt, &mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        let first = sequence(42);
+        assert_eq!(first, sequence(42), "same seed should give the same delays");
+        for (index, delay) in first.iter().enumerate() {
+            let cap = Duration::from_millis(100) * 2u32.pow(index as u32);
+            assert!(*delay <= cap, "delay {delay:?} exceeds cap {cap:?}");
+        }
+        assert_ne!(
+            first,
+            sequence(7),
+            "different seeds should jitter differently"
+        );
     }
-}
 
-// ==================== 错误处理详细测试（通过聚合入口 re-export） ====================
+    /// 测试 retry_async 在成功前按策略重试，耗尽后返回最后一次错误
+    #[tokio::test]
+    async fn test_retry_async_uses_policy_attempts() {
+        use best_practices::{Backoff, RetryPolicy, retry_async};
+        use std::time::Duration;
 
-mod error_handling_tests {
-    use super::*;
+        let policy = RetryPolicy::new(3, Duration::from_millis(1), Backoff::Fixed);
 
-    /// 测试现代panic处理
-    #[test]
-    fn test_modern_panic_handling() {
-        error_handling::modern_panic_handling();
+        let result: Result<u32, String> = retry_async(&policy, |attempt| async move {
+            if attempt < 3 {
+                Err(format!("attempt {attempt}"))
+            } else {
+                Ok(attempt)
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+
+        let mut calls = 0;
+        let result: Result<(), String> = retry_async(&policy, |attempt| {
+            calls += 1;
+            async move { Err(format!("attempt {attempt}")) }
+        })
+        .await;
+        assert_eq!(result, Err("attempt 3".to_string()));
+        assert_eq!(calls, 3);
     }
 
-    /// 测试现代Result处理
-    #[test]
-    fn test_modern_result_handling() {
-        error_handling::modern_result_handling();
+    /// 测试 run_with_timeout 在暂停的时钟下确定性地超时，且不消耗真实时间
+    #[tokio::test]
+    async fn test_run_with_timeout_times_out_on_paused_clock() {
+        use std::time::{Duration, Instant};
+
+        tokio::time::pause();
+        let wall_clock = Instant::now();
+
+        let slow = tokio::spawn(best_practices::run_with_timeout(
+            Duration::from_millis(50),
+            async {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                "慢操作完成"
+            },
+        ));
+        tokio::task::yield_now().await;
+        tokio::time::advance(Duration::from_millis(50)).await;
+
+        assert!(slow.await.unwrap().is_err());
+        assert!(wall_clock.elapsed() < Duration::from_millis(50));
     }
 
-    /// 测试现代问号模式
-    #[test]
-    fn test_modern_question_mark_patterns() {
-        error_handling::modern_question_mark_patterns();
-    }
+    /// 测试 run_with_timeout 在预算内完成时返回 future 的输出
+    #[tokio::test(start_paused = true)]
+    async fn test_run_with_timeout_returns_output_within_budget() {
+        use std::time::Duration;
 
-    /// 测试现代错误类型
-    #[test]
-    fn test_modern_error_types() {
-        error_handling::modern_error_types();
+        let result = best_practices::run_with_timeout(Duration::from_millis(50), async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            42
+        })
+        .await;
+
+        assert_eq!(result, Ok(42));
     }
 
-    /// 测试现代错误恢复
+    /// 测试字节数按二进制单位格式化
     #[test]
-    fn test_modern_error_recovery() {
-        error_handling::modern_error_recovery();
+    fn test_format_bytes_binary_units() {
+        assert_eq!(best_practices::format_bytes(0), "0 B");
+        assert_eq!(best_practices::format_bytes(1023), "1023 B");
+        assert_eq!(best_practices::format_bytes(1024), "1.0 KiB");
+        assert_eq!(best_practices::format_bytes(1_048_575), "1.0 MiB");
+        assert_eq!(best_practices::format_bytes(1_048_524), "1023.9 KiB");
+        assert_eq!(best_practices::format_bytes(3_565_158), "3.4 MiB");
+        assert_eq!(
+            best_practices::format_bytes(5 * 1024 * 1024 * 1024 + 512 * 1024 * 1024),
+            "5.5 GiB"
+        );
+        assert_eq!(best_practices::format_bytes(u64::MAX), "16.0 EiB");
     }
 
-    /// 测试现代错误日志
+    /// 测试全部为 None 时返回 None
     #[test]
-    fn test_modern_error_logging() {
-        error_handling::modern_error_logging();
+    fn test_first_some_all_none() {
+        assert_eq!(best_practices::first_some([None::<i32>, None, None]), None);
+        assert_eq!(best_practices::first_some(Vec::<Option<i32>>::new()), None);
     }
 
-    /// 测试网络错误处理
+    /// 测试第一个元素即为 Some 时不再消费后续元素
     #[test]
-    fn test_network_error_handling() {
-        error_handling::network_error_handling();
+    fn test_first_some_short_circuits_on_first() {
+        let mut evaluated = 0;
+        let result = best_practices::first_some(
+            [Some(1), Some(2), None]
+                .into_iter()
+                .inspect(|_| evaluated += 1),
+        );
+        assert_eq!(result, Some(1));
+        assert_eq!(evaluated, 1);
     }
 
-    /// 测试文件系统错误处理
+    /// 测试跳过前面的 None 取到后面的 Some
     #[test]
-    fn test_file_system_error_handling() {
-        error_handling::file_system_error_handling();
+    fn test_first_some_later_some() {
+        assert_eq!(
+            best_practices::first_some([None, None, Some("default"), Some("x")]),
+            Some("default")
+        );
     }
 
-    /// 测试配置错误处理
+    /// 测试 Lazy 的初始化闭包在多次访问中只执行一次
     #[test]
-    fn test_configuration_error_handling() {
-        error_handling::configuration_error_handling();
+    fn test_lazy_initializer_runs_exactly_once() {
+        use std::cell::Cell;
+
+        let calls = Cell::new(0);
+        let lazy = best_practices::Lazy::new();
+        assert_eq!(lazy.get(), None);
+
+        for _ in 0..3 {
+            let value = lazy.get_or_init(|| {
+                calls.set(calls.get() + 1);
+                "expensive".to_string()
+            });
+            assert_eq!(value, "expensive");
+        }
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(lazy.get().map(String::as_str), Some("expensive"));
     }
 
-    /// 测试业务验证错误处理
+    /// 测试分段计时器记录带标签且单调递增的累计耗时
     #[test]
-    fn test_business_validation_error_handling() {
-        error_handling::business_validation_error_handling();
+    fn test_stopwatch_records_monotonic_laps() {
+        use std::time::Duration;
+
+        let mut stopwatch = best_practices::Stopwatch::start();
+        std::thread::sleep(Duration::from_millis(5));
+        stopwatch.lap("load");
+        std::thread::sleep(Duration::from_millis(5));
+        stopwatch.lap("parse");
+
+        let report = stopwatch.report();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].0, "load");
+        assert_eq!(report[1].0, "parse");
+        assert!(report[0].1 >= Duration::from_millis(5));
+        assert!(report[1].1 >= report[0].1 + Duration::from_millis(5));
     }
 
-    /// 测试资源加载错误处理
+    /// 测试 percentile 按最近秩法返回样本值
     #[test]
-    fn test_resource_loading_error_handling() {
-        error_handling::resource_loading_error_handling();
+    fn test_percentile_nearest_rank() {
+        use best_practices::percentile;
+
+        let mut samples = vec![15.0, 20.0, 35.0, 40.0, 50.0, 5.0, 10.0, 25.0, 30.0, 45.0];
+        assert_eq!(percentile(&mut samples, 50.0), Some(25.0));
+        assert_eq!(percentile(&mut samples, 90.0), Some(45.0));
+        assert_eq!(percentile(&mut samples, 100.0), Some(50.0));
+        assert_eq!(percentile(&mut samples, 0.0), Some(5.0));
+        assert_eq!(percentile(&mut samples, 250.0), Some(50.0));
+        assert_eq!(percentile(&mut samples, -5.0), Some(5.0));
+        assert!(samples.windows(2).all(|w| w[0] <= w[1]));
     }
 
-    /// 测试外部服务错误处理
+    /// 测试 percentile 在没有样本时返回 None
     #[test]
-    fn test_external_service_error_handling() {
-        error_handling::external_service_error_handling();
+    fn test_percentile_empty_returns_none() {
+        assert_eq!(best_practices::percentile(&mut [], 50.0), None);
     }
-}
 
-// ==================== 并发模块详细测试 ====================
+    /// 测试延迟直方图的 p50/p99 落在样本所在的指数桶内
+    #[test]
+    fn test_latency_histogram_percentiles_fall_in_expected_buckets() {
+        use best_practices::LatencyHistogram;
+        use std::time::Duration;
 
-mod concurrency_tests {
-    use super::*;
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.percentile(50.0), Duration::ZERO);
 
-    /// 测试现代同步线程
-    #[test]
-    fn test_modern_sync_threads() {
-        concurrency::modern_sync_threads();
+        // 98 个约 100µs 的快请求 + 2 个约 10ms 的慢请求
+        for _ in 0..98 {
+            histogram.record(Duration::from_micros(100));
+        }
+        histogram.record(Duration::from_millis(10));
+        histogram.record(Duration::from_millis(10));
+        assert_eq!(histogram.count(), 100);
+
+        // 100_000ns 位于 [2^16, 2^17) 桶，10_000_000ns 位于 [2^23, 2^24) 桶
+        let p50 = histogram.percentile(50.0);
+        assert_eq!(p50, Duration::from_nanos((1 << 17) - 1));
+        assert!(p50 >= Duration::from_micros(100) && p50 < Duration::from_micros(200));
+        assert_eq!(histogram.percentile(98.0), p50);
+
+        let p99 = histogram.percentile(99.0);
+        assert_eq!(p99, Duration::from_nanos((1 << 24) - 1));
+        assert!(p99 >= Duration::from_millis(10) && p99 < Duration::from_millis(20));
+        assert_eq!(histogram.percentile(100.0), p99);
+        assert_eq!(
+            histogram.percentile(0.0),
+            p50,
+            "p0 is the smallest sample's bucket"
+        );
     }
 
-    /// 测试现代消息传递
+    /// 测试 describe_layout 报告大小、对齐与填充
     #[test]
-    fn test_modern_message_passing() {
-        concurrency::modern_message_passing();
+    fn test_describe_layout_reports_size_and_padding() {
+        use best_practices::{LayoutInfo, describe_layout};
+
+        assert_eq!(describe_layout::<u64>(), LayoutInfo { size: 8, align: 8 });
+
+        let padded = describe_layout::<(u8, u64)>();
+        assert_eq!(padded.align, 8);
+        assert_eq!(
+            padded.size, 16,
+            "u8 should be padded up to the u64 alignment"
+        );
+        assert!(padded.size > std::mem::size_of::<u8>() + std::mem::size_of::<u64>());
     }
 
-    /// 测试现代共享状态
+    /// 测试 wrap_text 按词边界折行
     #[test]
-    fn test_modern_shared_state() {
-        concurrency::modern_shared_state();
+    fn test_wrap_text_breaks_on_word_boundaries() {
+        let lines = best_practices::wrap_text("the quick  brown fox jumps over the lazy dog", 10);
+        assert_eq!(
+            lines,
+            vec!["the quick", "brown fox", "jumps over", "the lazy", "dog"]
+        );
+        assert!(lines.iter().all(|line| line.chars().count() <= 10));
+        assert!(best_practices::wrap_text("   ", 10).is_empty());
     }
 
-    /// 测试现代同步
+    /// 测试 wrap_text 硬切分超长单词
     #[test]
-    fn test_modern_synchronization() {
-        concurrency::modern_synchronization();
+    fn test_wrap_text_hard_splits_long_words() {
+        assert_eq!(
+            best_practices::wrap_text("see abcdefghijkl ok", 5),
+            vec!["see", "abcde", "fghij", "kl ok"]
+        );
+        assert_eq!(
+            best_practices::wrap_text("一二三四五六七", 3),
+            vec!["一二三", "四五六", "七"]
+        );
     }
 
-    /// 测试现代生产者消费者
+    /// 测试 wrap_text 宽度为 0 时原样返回
     #[test]
-    fn test_modern_producer_consumer() {
-        concurrency::modern_producer_consumer();
+    fn test_wrap_text_zero_width_returns_input_unchanged() {
+        assert_eq!(
+            best_practices::wrap_text("keep  me as is", 0),
+            vec!["keep  me as is"]
+        );
     }
 
-    /// 测试现代工作池
+    /// 测试 strip_ansi 移除 CSI 序列并保留纯文本
     #[test]
-    fn test_modern_work_pool() {
-        concurrency::modern_work_pool();
+    fn test_strip_ansi_removes_csi_sequences() {
+        use best_practices::strip_ansi;
+
+        assert_eq!(
+            strip_ansi("\x1b[1;31m[URGENT]\x1b[0m done"),
+            "[URGENT] done"
+        );
+        assert_eq!(strip_ansi("\x1b[2Kline\x1b[?25h"), "line");
+        assert_eq!(strip_ansi("plain 文本 [x]"), "plain 文本 [x]");
+        assert_eq!(strip_ansi("cut \x1b[1;3"), "cut ");
     }
 
-    /// 测试Web服务器并发处理
+    /// 测试 Debouncer 把时间窗口内的多次触发合并为一次调用
     #[test]
-    fn test_web_server_concurrent_handling() {
-        concurrency::web_server_concurrent_handling();
-    }
+    fn test_debouncer_coalesces_rapid_triggers() {
+        use best_practices::Debouncer;
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use std::time::{Duration, Instant};
 
-    /// 测试数据库连接池
-    #[test]
-    fn test_database_connection_pool() {
-        concurrency::database_connection_pool();
+        let start = Instant::now();
+        let now = Rc::new(Cell::new(start));
+        let clock = {
+            let now = Rc::clone(&now);
+            move || now.get()
+        };
+        let calls = Cell::new(0);
+        let mut debouncer = Debouncer::with_clock(
+            Duration::from_millis(100),
+            || calls.set(calls.get() + 1),
+            clock,
+        );
+
+        assert!(debouncer.trigger());
+        now.set(start + Duration::from_millis(30));
+        assert!(!debouncer.trigger());
+        now.set(start + Duration::from_millis(60));
+        assert!(!debouncer.trigger());
+
+        now.set(start + Duration::from_millis(100));
+        assert!(
+            debouncer.trigger(),
+            "trigger after the window should fire again"
+        );
+        drop(debouncer);
+        assert_eq!(calls.get(), 2);
     }
 }
 
-// ==================== 安全模块详细测试 ====================
+// ==================== 进阶设计模式详细测试 ====================
 
-mod security_tests {
+mod advanced_patterns_tests {
     use super::*;
+    use advanced_patterns::PaymentError;
 
-    /// 测试安全随机数生成
+    /// 测试拒付错误的 Display 包含原因且可装箱为 Box<dyn Error>
     #[test]
-    fn test_secure_random_generation() {
-        security::secure_random_generation();
+    fn test_declined_payment_error_display_and_boxing() {
+        let error = PaymentError::Declined {
+            reason: "余额不足".to_string(),
+        };
+        assert!(error.to_string().contains("余额不足"));
+
+        let boxed: Box<dyn std::error::Error> = Box::new(error);
+        assert!(boxed.to_string().contains("余额不足"));
     }
 
-    /// 测试密码学哈希函数
+    /// 测试 charge_all 汇总成功扣款并收集错误
     #[test]
-    fn test_cryptography_hash_functions() {
-        security::cryptography_hash_functions();
-    }
+    fn test_charge_all_sums_successes_and_collects_errors() {
+        use advanced_patterns::{CreditCardPayment, PayPalPayment, PaymentStrategy};
 
-    /// 测试HMAC消息认证
-    #[test]
-    fn test_hmac_message_authentication() {
-        security::hmac_message_authentication();
-    }
+        let strategies: Vec<Box<dyn PaymentStrategy>> = vec![
+            Box::new(CreditCardPayment::new("4532015112830366", "123")),
+            Box::new(PayPalPayment::new("invalid-account")),
+            Box::new(PayPalPayment::new("user@example.com")),
+        ];
 
-    /// 测试Base64编码解码
-    #[test]
-    fn test_base64_encoding_decoding() {
-        security::base64_encoding_decoding();
+        let (total, errors) = advanced_patterns::charge_all(&strategies, 25.0);
+        assert!((total - 50.0).abs() < f64::EPSILON);
+        assert_eq!(errors, vec![PaymentError::InvalidAccount]);
     }
 
-    /// 测试输入验证和清理
+    /// 测试弱引用发布者在订阅者释放后不再通知并清理列表
     #[test]
-    fn test_input_validation_sanitization() {
-        security::input_validation_sanitization();
-    }
+    fn test_weak_publisher_prunes_dropped_subscribers() {
+        use advanced_patterns::{Observer, WeakEventPublisher};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        #[derive(Debug)]
+        struct CountingObserver {
+            calls: Rc<Cell<usize>>,
+        }
 
-    /// 测试安全密码存储
-    #[test]
-    fn test_secure_password_storage() {
-        security::secure_password_storage();
-    }
+        impl Observer for CountingObserver {
+            fn update(&self, _message: &str) -> Result<(), String> {
+                self.calls.set(self.calls.get() + 1);
+                Ok(())
+            }
+        }
 
-    /// 测试安全随机字符串
-    #[test]
-    fn test_secure_random_strings() {
-        security::secure_random_strings();
+        let kept_calls = Rc::new(Cell::new(0));
+        let dropped_calls = Rc::new(Cell::new(0));
+        let kept: Rc<dyn Observer> = Rc::new(CountingObserver {
+            calls: Rc::clone(&kept_calls),
+        });
+        let dropped: Rc<dyn Observer> = Rc::new(CountingObserver {
+            calls: Rc::clone(&dropped_calls),
+        });
+
+        let mut publisher = WeakEventPublisher::new();
+        publisher.subscribe(&kept);
+        publisher.subscribe(&dropped);
+        publisher.notify("first");
+        assert_eq!(publisher.observer_count(), 2);
+
+        drop(dropped);
+        publisher.notify("second");
+
+        assert_eq!(kept_calls.get(), 2);
+        assert_eq!(dropped_calls.get(), 1);
+        assert_eq!(publisher.observer_count(), 1);
     }
 
-    /// 测试内存安全保证
+    /// 测试订阅者失败时 notify 记录后继续通知其余订阅者
     #[test]
-    fn test_memory_safety_guarantees() {
-        security::memory_safety_guarantees();
+    fn test_notify_keeps_going_after_failing_observer() {
+        use advanced_patterns::{EventPublisher, Observer, WeakEventPublisher};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        #[derive(Debug)]
+        struct FlakyObserver {
+            calls: Rc<Cell<usize>>,
+            fail: bool,
+        }
+
+        impl Observer for FlakyObserver {
+            fn update(&self, _message: &str) -> Result<(), String> {
+                self.calls.set(self.calls.get() + 1);
+                if self.fail {
+                    Err("mailbox full".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        let calls = Rc::new(Cell::new(0));
+        let observer = |fail| FlakyObserver {
+            calls: Rc::clone(&calls),
+            fail,
+        };
+
+        let mut publisher = EventPublisher::new();
+        let failing = publisher.subscribe(Box::new(observer(true)));
+        publisher.subscribe(Box::new(observer(false)));
+        publisher.notify("boxed");
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            publisher.notify_collecting("boxed"),
+            vec![(failing, "mailbox full".to_string())]
+        );
+
+        let failing: Rc<dyn Observer> = Rc::new(observer(true));
+        let healthy: Rc<dyn Observer> = Rc::new(observer(false));
+        let mut weak_publisher = WeakEventPublisher::new();
+        weak_publisher.subscribe(&failing);
+        weak_publisher.subscribe(&healthy);
+        weak_publisher.notify("weak");
+        assert_eq!(calls.get(), 6);
+        assert_eq!(weak_publisher.observer_count(), 2);
     }
 
-    /// 测试常量时间比较
+    /// 测试批量通知会通知所有订阅者并报告失败订阅的标识
     #[test]
-    fn test_constant_time_comparison() {
-        security::constant_time_comparison();
-    }
-}
+    fn test_event_publisher_notify_collecting_reports_failures() {
+        use advanced_patterns::{EventPublisher, Observer};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        #[derive(Debug)]
+        struct FlakyObserver {
+            calls: Rc<Cell<usize>>,
+            fail: bool,
+        }
 
-// ==================== 最佳实践详细测试 ====================
+        impl Observer for FlakyObserver {
+            fn update(&self, message: &str) -> Result<(), String> {
+                self.calls.set(self.calls.get() + 1);
+                if self.fail {
+                    Err(format!("gateway down: {message}"))
+                } else {
+                    Ok(())
+                }
+            }
+        }
 
-mod best_practices_tests {
-    use super::*;
+        let calls = Rc::new(Cell::new(0));
+        let observer = |fail| {
+            Box::new(FlakyObserver {
+                calls: Rc::clone(&calls),
+                fail,
+            })
+        };
+
+        let mut publisher = EventPublisher::new();
+        let ok_first = publisher.subscribe(observer(false));
+        let failing = publisher.subscribe(observer(true));
+        let ok_last = publisher.subscribe(observer(false));
+        assert_ne!(ok_first, ok_last);
+
+        let failures = publisher.notify_collecting("hello");
+        assert_eq!(calls.get(), 3);
+        assert_eq!(failures, vec![(failing, "gateway down: hello".to_string())]);
+    }
 
-    /// 测试现代错误处理最佳实践
+    /// 测试 DatabaseConfig 序列化往返（密码不会被写出）
     #[test]
-    fn test_modern_error_handling_best_practices() {
-        best_practices::modern_error_handling_best_practices();
+    fn test_database_config_serde_round_trip() {
+        use advanced_patterns::{DatabaseConfig, DatabaseConfigBuilder};
+
+        let config = DatabaseConfigBuilder::new()
+            .host("db.internal")
+            .port(6543)
+            .database("orders")
+            .username("svc")
+            .password("s3cret")
+            .pool_size(4)
+            .timeout_secs(15)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("s3cret"));
+
+        let restored: DatabaseConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.host(), "db.internal");
+        assert_eq!(restored.port(), 6543);
+        assert_eq!(restored.database(), "orders");
+        assert_eq!(restored.username(), "svc");
+        assert_eq!(restored.password(), "");
+        assert_eq!(restored.pool_size(), 4);
+        assert_eq!(restored.timeout_secs(), 15);
     }
 
-    /// 测试资源管理最佳实践
+    /// 测试反序列化同样经过 Builder 校验
     #[test]
-    fn test_resource_management_best_practices() {
-        best_practices::resource_management_best_practices();
+    fn test_database_config_deserialize_runs_builder_validation() {
+        use advanced_patterns::DatabaseConfig;
+
+        let config = |host: &str, port: u16| {
+            serde_json::json!({
+                "host": host,
+                "port": port,
+                "database": "orders",
+                "username": "svc",
+                "pool_size": 4,
+                "timeout_secs": 15
+            })
+        };
+
+        let error = serde_json::from_value::<DatabaseConfig>(config("db", 0))
+            .expect_err("port 0 should be rejected");
+        assert!(error.to_string().contains("Port must be greater than 0"));
+        assert!(serde_json::from_value::<DatabaseConfig>(config("", 5432)).is_err());
+        assert!(serde_json::from_value::<DatabaseConfig>(config("db", 5432)).is_ok());
     }
 
-    /// 测试性能优化最佳实践
+    /// 测试从作用域内的环境变量加载配置，未设置的项回退到默认值
     #[test]
-    fn test_performance_optimization_best_practices() {
-        best_practices::performance_optimization_best_practices();
+    fn test_database_config_from_env_with_scoped_vars() {
+        use advanced_patterns::{BuilderError, DatabaseConfig};
+        use std::collections::HashMap;
+
+        let vars = HashMap::from([("DB_HOST", "env-host"), ("DB_PORT", "7000")]);
+        let config =
+            DatabaseConfig::from_env_with(|key| vars.get(key).map(|v| v.to_string())).unwrap();
+        assert_eq!(config.host(), "env-host");
+        assert_eq!(config.port(), 7000);
+        assert_eq!(config.database(), "postgres");
+        assert_eq!(config.pool_size(), 10);
+
+        let invalid = HashMap::from([("DB_PORT", "not-a-port")]);
+        let error = DatabaseConfig::from_env_with(|key| invalid.get(key).map(|v| v.to_string()))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            BuilderError::InvalidEnvVar {
+                name: "DB_PORT".to_string(),
+                value: "not-a-port".to_string(),
+            }
+        );
     }
 
-    /// 测试API设计最佳实践
+    /// 测试枚举订单状态机走完整个生命周期并停留在终态
     #[test]
-    fn test_api_design_best_practices() {
-        best_practices::api_design_best_practices();
+    fn test_order_status_full_lifecycle() {
+        use advanced_patterns::OrderStatus;
+
+        let mut status = OrderStatus::Pending;
+        let mut seen = vec![status];
+        for _ in 0..3 {
+            status = status.advance();
+            seen.push(status);
+        }
+        assert_eq!(
+            seen,
+            vec![
+                OrderStatus::Pending,
+                OrderStatus::Confirmed,
+                OrderStatus::Shipping,
+                OrderStatus::Delivered,
+            ]
+        );
+        assert_eq!(status.advance(), OrderStatus::Delivered);
+        assert_eq!(status.label(), "📦 Delivered");
     }
 
-    /// 测试测试最佳实践
+    /// 测试只有发货前的订单可以取消，取消后保持取消状态
     #[test]
-    fn test_testing_best_practices() {
-        best_practices::testing_best_practices();
+    fn test_order_status_cancellation_rules() {
+        use advanced_patterns::OrderStatus;
+
+        assert!(OrderStatus::Pending.can_cancel());
+        assert!(OrderStatus::Confirmed.can_cancel());
+        assert!(!OrderStatus::Shipping.can_cancel());
+        assert!(!OrderStatus::Delivered.can_cancel());
+        assert!(!OrderStatus::Cancelled.can_cancel());
+
+        assert_eq!(
+            OrderStatus::Confirmed.cancel(),
+            Some(OrderStatus::Cancelled)
+        );
+        assert_eq!(OrderStatus::Shipping.cancel(), None);
+        assert_eq!(OrderStatus::Cancelled.advance(), OrderStatus::Cancelled);
     }
 
-    /// 测试文档最佳实践
+    /// 测试运输中的订单经 JSON 往返后保持状态
     #[test]
-    fn test_documentation_best_practices() {
-        best_practices::documentation_best_practices();
+    fn test_order_json_round_trip_preserves_shipping() {
+        use advanced_patterns::{Order, OrderStatus};
+
+        let mut order = Order::new("ORD-42");
+        order.advance();
+        order.advance();
+        assert_eq!(order.status(), OrderStatus::Shipping);
+
+        let json = order.to_json().unwrap();
+        assert!(json.contains("\"shipping\""));
+
+        let mut restored = Order::from_json(&json).unwrap();
+        assert_eq!(restored.id(), "ORD-42");
+        assert_eq!(restored.status(), OrderStatus::Shipping);
+        assert_eq!(restored.get_status(), order.get_status());
+
+        restored.advance();
+        assert_eq!(restored.status(), OrderStatus::Delivered);
+        assert!(Order::from_json(r#"{"id":"x","status":"lost"}"#).is_err());
     }
 }
 
@@ -496,6 +3911,12 @@ mod pitfalls_tests {
     fn test_concurrency_pitfalls() {
         pitfalls::concurrency_pitfalls();
     }
+
+    /// 测试 Weak 父指针树丢弃后释放全部节点
+    #[test]
+    fn test_demonstrate_weak_tree_frees_every_node() {
+        assert_eq!(pitfalls::demonstrate_weak_tree(), 5);
+    }
 }
 
 // ==================== 测试模块详细测试（通过聚合入口 re-export） ====================
@@ -540,6 +3961,60 @@ mod testing_module_tests {
     }
 }
 
+// ==================== 生态系统模块测试 ====================
+
+mod ecosystem_tests {
+    use super::*;
+
+    /// 测试运行时构建配置与标准库常量一致
+    #[test]
+    fn test_detect_features_matches_env_consts() {
+        let features = ecosystem::detect_features();
+
+        assert_eq!(features.os, std::env::consts::OS);
+        assert_eq!(features.arch, std::env::consts::ARCH);
+        assert_eq!(features.family, std::env::consts::FAMILY);
+        assert!(!features.os.is_empty() && !features.arch.is_empty());
+        assert_eq!(
+            features.pointer_width as usize,
+            std::mem::size_of::<usize>() * 8
+        );
+        assert_eq!(features.debug_assertions, cfg!(debug_assertions));
+    }
+
+    /// 测试没有子节点的树只渲染根节点
+    #[test]
+    fn test_render_tree_single_node() {
+        assert_eq!(ecosystem::render_tree("root", &[]), "root\n");
+    }
+
+    /// 测试平铺子节点使用分支与末尾连接符
+    #[test]
+    fn test_render_tree_flat_children() {
+        let tree = ecosystem::render_tree("app", &[("app", vec!["serde", "tokio", "clap"])]);
+        assert_eq!(tree, "app\n├── serde\n├── tokio\n└── clap\n");
+    }
+
+    /// 测试嵌套子节点按层缩进并保留竖线
+    #[test]
+    fn test_render_tree_nested_children() {
+        let children = [
+            ("app", vec!["tokio", "clap"]),
+            ("tokio", vec!["mio", "bytes"]),
+            ("clap", vec!["strsim"]),
+        ];
+        let expected = "\
+app
+├── tokio
+│   ├── mio
+│   └── bytes
+└── clap
+    └── strsim
+";
+        assert_eq!(ecosystem::render_tree("app", &children), expected);
+    }
+}
+
 // ==================== 边界条件测试 ====================
 
 mod edge_case_tests {
@@ -620,12 +4095,11 @@ mod integration_scenario_tests {
 
         // 创建多个用户
         for i in 0..10 {
-            let user = User::new(
-                format!("User{}", i),
-                format!("user{}@example.com", i),
-                20 + i as u8,
-            )
-            .expect("Failed to create user");
+            let user = User::builder()
+                .email(&format!("user{}@example.com", i))
+                .age(20 + i as u8)
+                .build()
+                .expect("Failed to create user");
 
             manager.add_user(user).expect("Failed to add user");
         }
@@ -661,11 +4135,10 @@ mod integration_scenario_tests {
         for i in 0..10 {
             let success_count = Arc::clone(&success_count);
             let handle = thread::spawn(move || {
-                let result = User::new(
-                    format!("ConcurrentUser{}", i),
-                    format!("concurrent{}@example.com", i),
-                    25,
-                );
+                let result = User::builder()
+                    .name(&format!("ConcurrentUser{}", i))
+                    .email(&format!("concurrent{}@example.com", i))
+                    .build();
 
                 if result.is_ok() {
                     success_count.fetch_add(1, Ordering::SeqCst);
@@ -680,6 +4153,32 @@ mod integration_scenario_tests {
 
         assert_eq!(success_count.load(Ordering::SeqCst), 10);
     }
+
+    /// 测试构建器默认值可以直接构建用户
+    #[test]
+    fn test_user_builder_defaults_are_valid() {
+        let user = User::builder()
+            .build()
+            .expect("default fixture should be valid");
+        assert!(user.is_adult());
+        assert_eq!(user, User::builder().build().unwrap());
+
+        let named = User::builder().name("Alice").build().unwrap();
+        assert_eq!(named.name, "Alice");
+        assert_eq!(named.email, user.email);
+    }
+
+    /// 测试构建器沿用 User::new 的年龄校验
+    #[test]
+    fn test_user_builder_rejects_age_below_minimum() {
+        use rust_learn::testing::UserCreationError;
+
+        assert_eq!(
+            User::builder().age(12).build(),
+            Err(UserCreationError::AgeTooYoung)
+        );
+        assert!(User::builder().age(13).build().is_ok());
+    }
 }
 
 // ==================== 性能回归测试 ====================